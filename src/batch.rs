@@ -0,0 +1,320 @@
+use chrono::NaiveDate;
+use flate2::read::MultiGzDecoder;
+use html5ever::rcdom::{Handle, NodeData, RcDom};
+use std::collections::HashSet;
+use std::io::Read;
+use url::Url;
+
+use crate::core::Monolith;
+use crate::html::{get_node_attr, get_node_name, html_to_dom};
+use crate::url::{create_path_slug, create_slug};
+
+// Used when no output file name template is given, extension depends on output format
+pub const DEFAULT_OUTPUT_TEMPLATE: &str = "{section}/{host}/{path_slug}-{date}";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BatchTarget {
+    pub url: String,
+    // Bookmark folder or sitemap the target is listed in, empty for plain lists
+    pub section: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputFormat {
+    Bookmarks,
+    List,
+    Sitemap,
+    SitemapIndex,
+}
+
+pub fn detect_input_format(contents: &str) -> InputFormat {
+    let contents: &str = contents.trim_start_matches('\u{feff}').trim_start();
+
+    if !contents.starts_with('<') {
+        InputFormat::List
+    } else if contents
+        .to_uppercase()
+        .starts_with("<!DOCTYPE NETSCAPE-BOOKMARK-FILE-1")
+    {
+        InputFormat::Bookmarks
+    } else if contents.contains("<sitemapindex") {
+        InputFormat::SitemapIndex
+    } else if contents.contains("<urlset") {
+        InputFormat::Sitemap
+    } else {
+        InputFormat::List
+    }
+}
+
+// Bookmarks and sitemaps get recognized even when given as the target, plain lists don't
+pub fn is_batch_input(data: &[u8]) -> bool {
+    detect_input_format(&decode_input(data)) != InputFormat::List
+}
+
+// Figures out what kind of list given data is, retrieving sitemaps the sitemap index refers to
+pub fn collect_targets(monolith: &mut Monolith, data: &[u8]) -> Vec<BatchTarget> {
+    let contents: String = decode_input(data);
+
+    match detect_input_format(&contents) {
+        InputFormat::Bookmarks => parse_bookmarks(&contents),
+        InputFormat::List => parse_targets(&contents)
+            .into_iter()
+            .map(|url| BatchTarget {
+                url,
+                section: "".to_string(),
+            })
+            .collect(),
+        InputFormat::Sitemap | InputFormat::SitemapIndex => {
+            let mut targets: Vec<BatchTarget> = vec![];
+            collect_sitemap_targets(monolith, &contents, "", &mut HashSet::new(), &mut targets);
+            targets
+        }
+    }
+}
+
+fn collect_sitemap_targets(
+    monolith: &mut Monolith,
+    contents: &str,
+    section: &str,
+    seen_sitemap_urls: &mut HashSet<String>,
+    targets: &mut Vec<BatchTarget>,
+) {
+    match detect_input_format(contents) {
+        InputFormat::Sitemap => {
+            for url in parse_sitemap(contents) {
+                targets.push(BatchTarget {
+                    url,
+                    section: section.to_string(),
+                });
+            }
+        }
+        InputFormat::SitemapIndex => {
+            for sitemap_url in parse_sitemap(contents) {
+                // Indices may refer to each other
+                if !seen_sitemap_urls.insert(sitemap_url.clone()) {
+                    continue;
+                }
+
+                // Failures get reported as they happen, such sitemaps are simply left out
+                if let Ok(data) = monolith.retrieve(&sitemap_url) {
+                    collect_sitemap_targets(
+                        monolith,
+                        &decode_input(&data),
+                        &create_sitemap_section(&sitemap_url),
+                        seen_sitemap_urls,
+                        targets,
+                    );
+                }
+            }
+        }
+        // Not a sitemap (e.g. an error page)
+        InputFormat::Bookmarks | InputFormat::List => {}
+    }
+}
+
+// Sitemaps are often served compressed (sitemap.xml.gz)
+fn decode_input(data: &[u8]) -> String {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed: Vec<u8> = vec![];
+        if MultiGzDecoder::new(data)
+            .read_to_end(&mut decompressed)
+            .is_ok()
+        {
+            return String::from_utf8_lossy(&decompressed).to_string();
+        }
+    }
+
+    String::from_utf8_lossy(data).to_string()
+}
+
+// Targets are listed one per line, blank lines and lines starting with # are skipped
+pub fn parse_targets(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+// Bookmarks exported by browsers, sections are made out of names of folders they're in
+pub fn parse_bookmarks(contents: &str) -> Vec<BatchTarget> {
+    let dom: RcDom = html_to_dom(&contents.as_bytes().to_vec(), "utf-8".to_string());
+    let mut targets: Vec<BatchTarget> = vec![];
+    collect_bookmarks(&dom.document, &mut vec![], &mut targets);
+    targets
+}
+
+// Folder's name (H3) is followed by the list (DL) of what's in it
+fn collect_bookmarks(node: &Handle, folders: &mut Vec<String>, targets: &mut Vec<BatchTarget>) {
+    let mut folder_name: Option<String> = None;
+
+    for child_node in node.children.borrow().iter() {
+        match get_node_name(child_node) {
+            Some("a") => {
+                if let Some(href) = get_node_attr(child_node, "href") {
+                    // Browsers also keep things like smart folders (place:) as bookmarks
+                    if let Ok(url) = Url::parse(href.trim()) {
+                        if url.scheme() == "http" || url.scheme() == "https" {
+                            targets.push(BatchTarget {
+                                url: href.trim().to_string(),
+                                section: folders
+                                    .iter()
+                                    .filter(|folder| !folder.is_empty())
+                                    .cloned()
+                                    .collect::<Vec<String>>()
+                                    .join("/"),
+                            });
+                        }
+                    }
+                }
+            }
+            Some("h3") => {
+                folder_name = Some(create_slug(&get_text(child_node)).to_lowercase());
+            }
+            Some("dl") => match folder_name.take() {
+                Some(folder_name) => {
+                    folders.push(folder_name);
+                    collect_bookmarks(child_node, folders, targets);
+                    folders.pop();
+                }
+                None => collect_bookmarks(child_node, folders, targets),
+            },
+            _ => collect_bookmarks(child_node, folders, targets),
+        }
+    }
+}
+
+fn get_text(node: &Handle) -> String {
+    let mut text: String = String::new();
+
+    for child_node in node.children.borrow().iter() {
+        match child_node.data {
+            NodeData::Text { ref contents } => text.push_str(&contents.borrow()),
+            _ => text.push_str(&get_text(child_node)),
+        }
+    }
+
+    text
+}
+
+// Both sitemaps and sitemap indices list URLs of their entries within <loc> elements
+pub fn parse_sitemap(contents: &str) -> Vec<String> {
+    let mut urls: Vec<String> = vec![];
+    let mut rest: &str = contents;
+
+    while let Some(start) = rest.find("<loc>") {
+        rest = &rest[start + "<loc>".len()..];
+        let end: usize = match rest.find("</loc>") {
+            Some(end) => end,
+            None => break,
+        };
+
+        let loc: &str = rest[..end].trim();
+        let url: String = match loc
+            .strip_prefix("<![CDATA[")
+            .and_then(|loc| loc.strip_suffix("]]>"))
+        {
+            Some(cdata) => cdata.trim().to_string(),
+            None => unescape_xml(loc),
+        };
+        if !url.is_empty() {
+            urls.push(url);
+        }
+
+        rest = &rest[end + "</loc>".len()..];
+    }
+
+    urls
+}
+
+fn unescape_xml(text: &str) -> String {
+    let mut result: String = String::new();
+    let mut rest: &str = text;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end: usize = match rest.find(';') {
+            Some(end) => end,
+            None => break,
+        };
+        let character: Option<char> = match &rest[1..end] {
+            "amp" => Some('&'),
+            "apos" => Some('\''),
+            "gt" => Some('>'),
+            "lt" => Some('<'),
+            "quot" => Some('"'),
+            entity => match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => entity
+                    .strip_prefix('#')
+                    .and_then(|decimal| decimal.parse::<u32>().ok()),
+            }
+            .and_then(char::from_u32),
+        };
+
+        match character {
+            Some(character) => {
+                result.push(character);
+                rest = &rest[end + 1..];
+            }
+            // Not an entity, leave it as it is
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+// Sitemaps listed in sitemap indices are named after their file names (e.g. sitemap-posts.xml)
+fn create_sitemap_section(sitemap_url: &str) -> String {
+    let file_name: &str = sitemap_url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    let file_name: &str = file_name.strip_suffix(".gz").unwrap_or(file_name);
+    let file_name: &str = file_name.strip_suffix(".xml").unwrap_or(file_name);
+    let section: String = create_slug(file_name).to_lowercase();
+
+    if section.is_empty() {
+        "sitemap".to_string()
+    } else {
+        section
+    }
+}
+
+// Fills in {section}, {host}, {path_slug} and {date} placeholders of output file name template
+pub fn create_output_path(
+    template: &str,
+    section: &str,
+    target_url: &Url,
+    date: NaiveDate,
+) -> String {
+    let host: &str = match target_url.host_str() {
+        Some(host) if !host.is_empty() => host,
+        // Local files and data URLs
+        _ => "local",
+    };
+
+    let mut output_path: String = template.to_string();
+    if section.is_empty() {
+        // Don't leave an empty directory name behind
+        output_path = output_path.replace("{section}/", "");
+    }
+
+    output_path
+        .replace("{section}", section)
+        .replace("{host}", host)
+        .replace("{path_slug}", &create_path_slug(target_url))
+        .replace("{date}", &date.format("%Y-%m-%d").to_string())
+}
@@ -0,0 +1,180 @@
+use chrono::DateTime;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use url::Url;
+
+#[derive(Clone)]
+pub struct CacheEntry {
+    pub data: Vec<u8>,
+    pub final_url: Url,
+    pub media_type: String,
+    pub charset: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    // Unix time until which the asset may be reused without asking the server
+    pub fresh_until: Option<u64>,
+}
+
+// What Cache-Control and Expires headers of a response allow doing with it
+#[derive(Debug, PartialEq, Eq)]
+pub enum Freshness {
+    // Must not be stored at all
+    NoStore,
+    // May be reused without revalidation until given Unix time
+    FreshUntil(u64),
+    // Has to be revalidated before being reused
+    Stale,
+}
+
+impl CacheEntry {
+    pub fn new(data: Vec<u8>, final_url: Url, media_type: String, charset: String) -> CacheEntry {
+        CacheEntry {
+            data,
+            final_url,
+            media_type,
+            charset,
+            etag: None,
+            last_modified: None,
+            fresh_until: None,
+        }
+    }
+
+    pub fn can_be_revalidated(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+
+    pub fn is_fresh(&self) -> bool {
+        matches!(self.fresh_until, Some(fresh_until) if fresh_until > now())
+    }
+}
+
+pub fn parse_freshness(cache_control: Option<&str>, expires: Option<&str>) -> Freshness {
+    let mut no_cache: bool = false;
+    let mut max_age: Option<u64> = None;
+    for directive in cache_control.unwrap_or_default().split(',') {
+        let (name, value) = match directive.split_once('=') {
+            Some((name, value)) => (name.trim(), value.trim().trim_matches('"')),
+            None => (directive.trim(), ""),
+        };
+
+        match name.to_lowercase().as_str() {
+            "no-store" => return Freshness::NoStore,
+            "no-cache" => no_cache = true,
+            "max-age" => max_age = value.parse::<u64>().ok(),
+            _ => {}
+        }
+    }
+
+    // Max-age takes precedence over Expires, which is compared with local time
+    let fresh_until: Option<u64> = match max_age {
+        Some(max_age) => Some(now() + max_age),
+        None => expires
+            .and_then(|expires| DateTime::parse_from_rfc2822(expires).ok())
+            .map(|expires| expires.timestamp().max(0) as u64),
+    };
+
+    match fresh_until {
+        Some(fresh_until) if !no_cache && fresh_until > now() => Freshness::FreshUntil(fresh_until),
+        _ => Freshness::Stale,
+    }
+}
+
+pub fn cache_file_path(cache_dir: &str, cache_key: &str) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(cache_key.as_bytes());
+
+    Path::new(cache_dir).join(format!("{:x}", hasher.finalize()))
+}
+
+pub fn read_cache_entry(cache_dir: &str, cache_key: &str) -> Option<CacheEntry> {
+    let file_blob: Vec<u8> = fs::read(cache_file_path(cache_dir, cache_key)).ok()?;
+
+    // Metadata lines are separated from the body by an empty line
+    let separator_loc: usize = file_blob.windows(2).position(|w| w == b"\n\n")?;
+    let head: String = String::from_utf8_lossy(&file_blob[..separator_loc]).to_string();
+    let data: Vec<u8> = file_blob[separator_loc + 2..].to_vec();
+
+    let mut key: Option<&str> = None;
+    let mut final_url: Option<Url> = None;
+    let mut media_type: String = "".to_string();
+    let mut charset: String = "".to_string();
+    let mut etag: Option<String> = None;
+    let mut last_modified: Option<String> = None;
+    let mut fresh_until: Option<u64> = None;
+
+    for line in head.lines() {
+        if let Some((name, value)) = line.split_once(": ") {
+            match name {
+                "key" => key = Some(value),
+                "final-url" => final_url = Url::parse(value).ok(),
+                "media-type" => media_type = value.to_string(),
+                "charset" => charset = value.to_string(),
+                "etag" => etag = Some(value.to_string()),
+                "last-modified" => last_modified = Some(value.to_string()),
+                "fresh-until" => fresh_until = value.parse::<u64>().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    // Guard against hash collisions and truncated files
+    if key != Some(cache_key) {
+        return None;
+    }
+
+    Some(CacheEntry {
+        data,
+        final_url: final_url?,
+        media_type,
+        charset,
+        etag,
+        last_modified,
+        fresh_until,
+    })
+}
+
+pub fn write_cache_entry(
+    cache_dir: &str,
+    cache_key: &str,
+    cache_entry: &CacheEntry,
+) -> Result<(), Error> {
+    fs::create_dir_all(cache_dir)?;
+
+    let mut head: String = format!(
+        "key: {}\nfinal-url: {}\nmedia-type: {}\ncharset: {}\n",
+        cache_key, cache_entry.final_url, cache_entry.media_type, cache_entry.charset,
+    );
+    if let Some(etag) = &cache_entry.etag {
+        head += &format!("etag: {}\n", etag);
+    }
+    if let Some(last_modified) = &cache_entry.last_modified {
+        head += &format!("last-modified: {}\n", last_modified);
+    }
+    if let Some(fresh_until) = cache_entry.fresh_until {
+        head += &format!("fresh-until: {}\n", fresh_until);
+    }
+    head += "\n";
+
+    let mut file_blob: Vec<u8> = head.into_bytes();
+    file_blob.extend_from_slice(&cache_entry.data);
+
+    // Write into a temporary file first, so that parallel runs never see partial entries
+    let file_path: PathBuf = cache_file_path(cache_dir, cache_key);
+    let tmp_file_path: PathBuf = file_path.with_extension(format!(
+        "{}-{:?}.tmp",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    fs::write(&tmp_file_path, file_blob)?;
+    fs::rename(&tmp_file_path, &file_path)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time anomaly")
+        .as_secs()
+}
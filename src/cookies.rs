@@ -1,17 +1,52 @@
+use chrono::{DateTime, NaiveDateTime};
+use publicsuffix::{List, Psl};
+use reqwest::cookie::CookieStore;
+use reqwest::header::HeaderValue;
+#[cfg(feature = "firefox-cookies")]
+use rusqlite::{Connection, ErrorCode};
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::fmt;
+use std::path::Path;
+#[cfg(feature = "firefox-cookies")]
+use std::path::PathBuf;
+#[cfg(feature = "firefox-cookies")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(feature = "firefox-cookies")]
+use std::{env, fs, process};
 use url::Url;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Cookie {
     pub domain: String,
+    // Whether subdomains of the domain match too (false for host-only cookies)
     pub tailmatch: bool,
     pub path: String,
     pub secure: bool,
+    pub http_only: bool,
+    // Unix timestamp, 0 for session cookies
     pub expiration: u64,
     pub name: String,
     pub value: String,
 }
-pub struct ParseCookieError {}
+
+#[derive(Debug)]
+pub struct ParseCookieError {
+    // Not known for errors which aren't specific to a single line
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ParseCookieError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
 
 impl Cookie {
     pub fn encoded(&self) -> String {
@@ -20,10 +55,7 @@ impl Cookie {
     }
 
     pub fn is_expired(&self) -> bool {
-        let now = SystemTime::now();
-        let since_epoch = now.duration_since(UNIX_EPOCH).expect("Time anomaly");
-
-        self.expiration < since_epoch.as_secs()
+        self.expiration != 0 && self.expiration < now()
     }
 
     pub fn matches_url(&self, url: &str) -> bool {
@@ -38,12 +70,15 @@ impl Cookie {
                     "https" => {}
                     _ => return false,
                 }
-                if let Some(domain) = url.domain() {
-                    if !domain.eq_ignore_ascii_case(&self.domain) {
-                        return false;
+                match url.host_str() {
+                    Some(host) => {
+                        if !self.matches_domain(host) {
+                            return false;
+                        }
                     }
+                    None => return false,
                 }
-                if !url.path().starts_with(&self.path) {
+                if !path_matches(url.path(), &self.path) {
                     return false;
                 }
             }
@@ -52,42 +87,527 @@ impl Cookie {
 
         true
     }
+
+    // Netscape cookie files prefix domains of cookies which also match subdomains with a dot
+    fn bare_domain(&self) -> String {
+        self.domain.trim_start_matches('.').to_lowercase()
+    }
+
+    fn matches_domain(&self, host: &str) -> bool {
+        let domain: String = self.bare_domain();
+        let host: String = host.to_lowercase();
+
+        if host == domain {
+            true
+        } else {
+            self.tailmatch && domain_matches(&host, &domain)
+        }
+    }
+
+    // Cookies with the same name, domain, and path replace each other
+    fn is_same_as(&self, other: &Cookie) -> bool {
+        self.name == other.name
+            && self.bare_domain() == other.bare_domain()
+            && self.path == other.path
+    }
 }
 
-pub fn parse_cookies(file_contents: &str) -> Result<Vec<Cookie>, ParseCookieError> {
-    let cookie_lines = file_contents
-        .lines()
-        .filter(|l| !l.starts_with('#') && !l.is_empty());
+// Parses value of Set-Cookie header (RFC 6265, section 5.2) received in response to given URL
+pub fn parse_set_cookie(header: &str, url: &Url) -> Option<Cookie> {
+    let host: String = url.host_str()?.to_lowercase();
+    let mut parts = header.split(';');
+
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name: &str = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut domain: Option<String> = None;
+    let mut path: Option<String> = None;
+    let mut secure: bool = false;
+    let mut http_only: bool = false;
+    let mut expires: Option<u64> = None;
+    let mut max_age: Option<u64> = None;
+    for attribute in parts {
+        let (attribute_name, attribute_value) = match attribute.split_once('=') {
+            Some((attribute_name, attribute_value)) => {
+                (attribute_name.trim(), attribute_value.trim())
+            }
+            None => (attribute.trim(), ""),
+        };
+
+        match attribute_name.to_lowercase().as_str() {
+            "domain" => {
+                let attribute_value: &str = attribute_value.trim_start_matches('.');
+                if !attribute_value.is_empty() {
+                    domain = Some(attribute_value.to_lowercase());
+                }
+            }
+            "expires" => {
+                if let Some(date) = parse_cookie_date(attribute_value) {
+                    expires = Some(date);
+                }
+            }
+            "httponly" => http_only = true,
+            "max-age" => {
+                if let Ok(delta_seconds) = attribute_value.parse::<i64>() {
+                    max_age = Some(if delta_seconds <= 0 {
+                        // Earliest representable time, makes the cookie get removed
+                        1
+                    } else {
+                        now() + delta_seconds as u64
+                    });
+                }
+            }
+            "path" if attribute_value.starts_with('/') => {
+                path = Some(attribute_value.to_string());
+            }
+            "secure" => secure = true,
+            _ => {}
+        }
+    }
+
+    let (domain, tailmatch): (String, bool) = match domain {
+        // Cookies for public suffixes (like co.uk) would be sent to every site under them,
+        // unless that's the host itself, in which case it only gets them back (RFC 6265, 5.3)
+        Some(domain) if is_public_suffix(&domain) => {
+            if domain != host {
+                return None;
+            }
+            (host, false)
+        }
+        // Servers may only set cookies for their own domain (or domains it's part of)
+        Some(domain) if host == domain || domain_matches(&host, &domain) => (domain, true),
+        Some(_) => return None,
+        None => (host, false),
+    };
+
+    Some(Cookie {
+        domain,
+        tailmatch,
+        path: path.unwrap_or_else(|| default_path(url.path())),
+        secure,
+        http_only,
+        // Max-Age takes precedence over Expires
+        expiration: max_age.or(expires).unwrap_or(0),
+        name: name.to_string(),
+        value: value.trim().to_string(),
+    })
+}
+
+fn is_public_suffix(domain: &str) -> bool {
+    // Copy of https://publicsuffix.org/list/public_suffix_list.dat
+    static PUBLIC_SUFFIXES: OnceLock<List> = OnceLock::new();
+    let list: &List = PUBLIC_SUFFIXES.get_or_init(|| {
+        List::from_bytes(include_bytes!("../data/public_suffix_list.dat")).unwrap()
+    });
+
+    match list.suffix(domain.as_bytes()) {
+        Some(suffix) => suffix.is_known() && suffix.as_bytes() == domain.as_bytes(),
+        None => false,
+    }
+}
+
+fn parse_cookie_date(date: &str) -> Option<u64> {
+    let timestamp: i64 = match DateTime::parse_from_rfc2822(date) {
+        Ok(date_time) => date_time.timestamp(),
+        Err(_) => [
+            "%a, %d-%b-%Y %H:%M:%S GMT",
+            "%a, %d-%b-%y %H:%M:%S GMT",
+            "%A, %d-%b-%y %H:%M:%S GMT",
+            "%a %b %e %H:%M:%S %Y",
+        ]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(date, format).ok())?
+        .timestamp(),
+    };
+
+    // Dates before the epoch make cookies expire right away
+    Some(timestamp.max(1) as u64)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time anomaly")
+        .as_secs()
+}
+
+// Host is a subdomain of given domain (IP addresses have no subdomains)
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host.ends_with(&format!(".{}", domain)) && host.parse::<std::net::IpAddr>().is_err()
+}
 
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    match request_path.strip_prefix(cookie_path) {
+        Some(rest) => rest.is_empty() || cookie_path.ends_with('/') || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+// Cookies without Path attribute apply to the directory of the URL they were set by
+fn default_path(request_path: &str) -> String {
+    match request_path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => request_path[..index].to_string(),
+    }
+}
+
+// Cookies loaded from file and set by servers during this run
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    cookies: Mutex<Vec<Cookie>>,
+}
+
+impl CookieJar {
+    pub fn new(cookies: Vec<Cookie>) -> CookieJar {
+        let cookie_jar: CookieJar = CookieJar::default();
+        for cookie in cookies {
+            cookie_jar.store(cookie);
+        }
+        cookie_jar
+    }
+
+    pub fn cookies(&self) -> Vec<Cookie> {
+        self.cookies.lock().unwrap().clone()
+    }
+
+    // Value of Cookie header for given URL, cookies with longer paths go first
+    pub fn cookie_header(&self, url: &Url) -> Option<String> {
+        let mut matching_cookies: Vec<Cookie> = self
+            .cookies
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|cookie| !cookie.is_expired() && cookie.matches_url(url.as_str()))
+            .cloned()
+            .collect();
+        if matching_cookies.is_empty() {
+            return None;
+        }
+        matching_cookies.sort_by_key(|cookie| Reverse(cookie.path.len()));
+
+        Some(
+            matching_cookies
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<String>>()
+                .join("; "),
+        )
+    }
+
+    pub fn set_cookie(&self, header: &str, url: &Url) {
+        if let Some(cookie) = parse_set_cookie(header, url) {
+            self.store(cookie);
+        }
+    }
+
+    // Replaces cookie with the same name, domain, and path, expired cookies remove it
+    pub fn store(&self, cookie: Cookie) {
+        let mut cookies = self.cookies.lock().unwrap();
+        let existing_index: Option<usize> = cookies.iter().position(|c| c.is_same_as(&cookie));
+
+        match existing_index {
+            Some(index) if cookie.is_expired() => {
+                cookies.remove(index);
+            }
+            Some(index) => cookies[index] = cookie,
+            None if cookie.is_expired() => {}
+            None => cookies.push(cookie),
+        }
+    }
+
+    // Writes cookies in the same format they're loaded in
+    pub fn to_netscape(&self) -> String {
+        let mut file_contents: String = "# Netscape HTTP Cookie File\n".to_string();
+
+        for cookie in self.cookies.lock().unwrap().iter() {
+            let domain: String = if cookie.tailmatch {
+                format!(".{}", cookie.bare_domain())
+            } else {
+                cookie.bare_domain()
+            };
+            file_contents.push_str(&format!(
+                "{}{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                if cookie.http_only { "#HttpOnly_" } else { "" },
+                domain,
+                if cookie.tailmatch { "TRUE" } else { "FALSE" },
+                cookie.path,
+                if cookie.secure { "TRUE" } else { "FALSE" },
+                cookie.expiration,
+                cookie.name,
+                cookie.value,
+            ));
+        }
+
+        file_contents
+    }
+}
+
+// Makes the HTTP client send cookies and remember ones set by servers, including redirects
+impl CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        for cookie_header in cookie_headers {
+            if let Ok(cookie_header) = cookie_header.to_str() {
+                self.set_cookie(cookie_header, url);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.cookie_header(url)
+            .and_then(|cookie_header| HeaderValue::from_str(&cookie_header).ok())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CookieFormat {
+    // Exported by browser extensions, also Playwright's storage state
+    Json,
+    // cookies.txt, as used by curl and wget
+    Netscape,
+    // Firefox's cookies.sqlite
+    Sqlite,
+}
+
+pub fn detect_cookie_format(data: &[u8]) -> CookieFormat {
+    if data.starts_with(b"SQLite format 3\0") {
+        return CookieFormat::Sqlite;
+    }
+
+    match String::from_utf8_lossy(data)
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .chars()
+        .next()
+    {
+        Some('[') | Some('{') => CookieFormat::Json,
+        _ => CookieFormat::Netscape,
+    }
+}
+
+pub fn parse_cookies(file_contents: &str) -> Result<Vec<Cookie>, ParseCookieError> {
     let mut cookies = vec![];
-    for line in cookie_lines {
-        match line.split('\t').collect::<Vec<&str>>().get(0..7) {
-            Some(cookie_parts) => {
-                let domain = cookie_parts[0].to_string();
-                let tailmatch = cookie_parts[1] == "TRUE";
-                let path = cookie_parts[2].to_string();
-                let secure = cookie_parts[3] == "TRUE";
-
-                let expiration: u64 = cookie_parts[4].parse().expect("Failed to parse expiration");
-
-                let name = cookie_parts[5].to_string();
-                let mut value = cookie_parts[6].to_string();
-                // drop quotes
-                value.remove(0);
-                value.remove(value.len() - 1);
-
-                cookies.push(Cookie {
-                    domain,
-                    tailmatch,
-                    path,
-                    secure,
-                    expiration,
-                    name,
-                    value,
-                });
+    for (index, line) in file_contents.lines().enumerate() {
+        let line_error = |message: String| ParseCookieError {
+            line: Some(index + 1),
+            message,
+        };
+
+        // HttpOnly cookies are commented out, so that older programs skip them
+        let (line, http_only) = match line.strip_prefix("#HttpOnly_") {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+        let line: &str = line.trim_end_matches('\r');
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+
+        // Value may be left out entirely along with the tab before it
+        let cookie_parts: Vec<&str> = line.split('\t').collect();
+        if cookie_parts.len() < 6 {
+            return Err(line_error(format!(
+                "expected 7 tab-separated fields, found {}",
+                cookie_parts.len()
+            )));
+        }
+
+        let domain = cookie_parts[0].to_string();
+        let tailmatch = cookie_parts[1].eq_ignore_ascii_case("TRUE");
+        let path = cookie_parts[2].to_string();
+        let secure = cookie_parts[3].eq_ignore_ascii_case("TRUE");
+
+        // Some programs write fractions of a second
+        let expiration: u64 = match cookie_parts[4].trim().parse::<f64>() {
+            Ok(expiration) if expiration >= 0.0 => expiration as u64,
+            _ => {
+                return Err(line_error(format!(
+                    "invalid expiration date: {}",
+                    cookie_parts[4]
+                )))
             }
-            None => return Err(ParseCookieError {}),
+        };
+
+        let name = cookie_parts[5].to_string();
+        let value: &str = cookie_parts.get(6).copied().unwrap_or_default();
+        // Drop quotes, if any
+        let value: &str = match value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+        {
+            Some(unquoted_value) => unquoted_value,
+            None => value,
+        };
+
+        cookies.push(Cookie {
+            domain,
+            tailmatch,
+            path,
+            secure,
+            http_only,
+            expiration,
+            name,
+            value: value.to_string(),
+        });
+    }
+    Ok(cookies)
+}
+
+// List of cookie objects, possibly wrapped into an object under "cookies"
+pub fn parse_json_cookies(file_contents: &str) -> Result<Vec<Cookie>, ParseCookieError> {
+    let json: Value = serde_json::from_str(file_contents).map_err(|error| {
+        // Line number is reported separately
+        let message: String = error.to_string();
+        ParseCookieError {
+            line: Some(error.line()),
+            message: match message.rfind(" at line ") {
+                Some(index) => message[..index].to_string(),
+                None => message,
+            },
         }
+    })?;
+    let error = |message: String| ParseCookieError {
+        line: None,
+        message,
+    };
+
+    let entries: &Vec<Value> = match &json {
+        Value::Array(entries) => entries,
+        Value::Object(object) => match object.get("cookies") {
+            Some(Value::Array(entries)) => entries,
+            _ => return Err(error("expected a list of cookies".to_string())),
+        },
+        _ => return Err(error("expected a list of cookies".to_string())),
+    };
+
+    let mut cookies = vec![];
+    for (index, entry) in entries.iter().enumerate() {
+        let string_field = |name: &str| entry.get(name).and_then(Value::as_str);
+        let bool_field = |name: &str| entry.get(name).and_then(Value::as_bool);
+
+        let name: &str = string_field("name")
+            .ok_or_else(|| error(format!("cookie {} has no name", index + 1)))?;
+        let domain: &str = string_field("domain")
+            .ok_or_else(|| error(format!("cookie {} has no domain", index + 1)))?;
+
+        // Session cookies have no expiration date, or a negative one
+        let expiration: u64 = if bool_field("session") == Some(true) {
+            0
+        } else {
+            entry
+                .get("expirationDate")
+                .or_else(|| entry.get("expires"))
+                .and_then(Value::as_f64)
+                .filter(|expiration| *expiration > 0.0)
+                .map(|expiration| expiration as u64)
+                .unwrap_or(0)
+        };
+
+        cookies.push(Cookie {
+            domain: domain.to_string(),
+            tailmatch: match bool_field("hostOnly") {
+                Some(host_only) => !host_only,
+                None => domain.starts_with('.'),
+            },
+            path: string_field("path").unwrap_or("/").to_string(),
+            secure: bool_field("secure").unwrap_or(false),
+            http_only: bool_field("httpOnly").unwrap_or(false),
+            expiration,
+            name: name.to_string(),
+            value: string_field("value").unwrap_or_default().to_string(),
+        });
     }
     Ok(cookies)
 }
+
+// Reads moz_cookies table of Firefox's profile database
+#[cfg(feature = "firefox-cookies")]
+pub fn read_sqlite_cookies(path: &Path) -> Result<Vec<Cookie>, ParseCookieError> {
+    static COPIES: AtomicUsize = AtomicUsize::new(0);
+
+    // Running Firefox keeps the database locked, and its latest changes may only be
+    // in the write-ahead log next to it, so both get copied and the copy gets read
+    let copy_dir: PathBuf = env::temp_dir().join(format!(
+        "monolith-cookies-{}-{}",
+        process::id(),
+        COPIES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = copy_sqlite_database(path, &copy_dir)
+        .and_then(|copy_path| query_sqlite_cookies(&copy_path));
+    fs::remove_dir_all(&copy_dir).ok();
+
+    result
+}
+
+#[cfg(not(feature = "firefox-cookies"))]
+pub fn read_sqlite_cookies(_path: &Path) -> Result<Vec<Cookie>, ParseCookieError> {
+    Err(ParseCookieError {
+        line: None,
+        message: "Firefox cookies.sqlite support was not compiled in (feature \"firefox-cookies\")"
+            .to_string(),
+    })
+}
+
+#[cfg(feature = "firefox-cookies")]
+fn copy_sqlite_database(path: &Path, copy_dir: &Path) -> Result<PathBuf, ParseCookieError> {
+    let error = |error: std::io::Error| ParseCookieError {
+        line: None,
+        message: format!("could not make a copy of {}: {}", path.display(), error),
+    };
+
+    fs::create_dir_all(copy_dir).map_err(error)?;
+    let copy_path: PathBuf = copy_dir.join("cookies.sqlite");
+    fs::copy(path, &copy_path).map_err(error)?;
+    let mut wal_path = path.as_os_str().to_owned();
+    wal_path.push("-wal");
+    if Path::new(&wal_path).exists() {
+        fs::copy(&wal_path, copy_dir.join("cookies.sqlite-wal")).map_err(error)?;
+    }
+
+    Ok(copy_path)
+}
+
+#[cfg(feature = "firefox-cookies")]
+fn query_sqlite_cookies(path: &Path) -> Result<Vec<Cookie>, ParseCookieError> {
+    let error = |error: rusqlite::Error| ParseCookieError {
+        line: None,
+        message: match error.sqlite_error_code() {
+            Some(ErrorCode::DatabaseBusy) | Some(ErrorCode::DatabaseLocked) => {
+                "database is locked, close Firefox and try again".to_string()
+            }
+            _ => error.to_string(),
+        },
+    };
+
+    let connection: Connection = Connection::open(path).map_err(error)?;
+    let mut statement = connection
+        .prepare("SELECT host, path, isSecure, isHttpOnly, expiry, name, value FROM moz_cookies")
+        .map_err(error)?;
+    let rows = statement
+        .query_map([], |row| {
+            let domain: String = row.get(0)?;
+            let expiry: i64 = row.get(4)?;
+
+            Ok(Cookie {
+                tailmatch: domain.starts_with('.'),
+                domain,
+                path: row.get(1)?,
+                secure: row.get::<_, i64>(2)? != 0,
+                http_only: row.get::<_, i64>(3)? != 0,
+                // Newer versions of Firefox store expiration dates in milliseconds
+                expiration: if expiry > 100_000_000_000 {
+                    expiry as u64 / 1000
+                } else {
+                    expiry.max(0) as u64
+                },
+                name: row.get(5)?,
+                value: row.get(6)?,
+            })
+        })
+        .map_err(error)?;
+
+    rows.collect::<Result<Vec<Cookie>, rusqlite::Error>>()
+        .map_err(error)
+}
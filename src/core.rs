@@ -0,0 +1,748 @@
+use encoding_rs::Encoding;
+use html5ever::rcdom::RcDom;
+use reqwest::blocking::{Client, ClientBuilder};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::redirect::Policy;
+use reqwest::Proxy;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+use crate::fetcher::{AssetFetcher, ReferrerPolicy};
+use crate::html::{
+    add_favicon, collect_link_urls, create_metadata_tag, get_base_url, get_charset, has_favicon,
+    html_to_dom, prefetch_assets, serialize_document, set_base_url, set_charset,
+    walk_and_embed_assets,
+};
+use crate::mhtml::{create_mhtml, MhtmlArchive};
+use crate::opts::{Options, OutputFormat};
+use crate::proxy::ProxyConfig;
+use crate::report::AssetRecord;
+use crate::reporter::{report, Event, Reporter};
+use crate::session::{DocumentState, Session};
+use crate::tls::TlsConfig;
+use crate::url::{clean_url, create_path_slug, embed_asset, resolve_url};
+use crate::utils::{
+    domain_is_within_domain, parse_status_patterns, retrieve_asset, AssetError, Resource,
+};
+
+#[derive(Debug)]
+pub enum MonolithError {
+    NoTarget,
+    UnsupportedTargetScheme(String),
+    InvalidTargetPath(String),
+    TargetIsNotAFile(String),
+    UnknownEncoding(String),
+    InvalidBaseUrlPath(String),
+    InvalidUserAgent(String),
+    InvalidProxy(String),
+    UnknownReferrerPolicy(String),
+    InvalidStatusList(String),
+    InvalidCaCert(String),
+    InvalidClientCert(String),
+    InvalidClientKey(String),
+    NoClientCert,
+    HttpClient(reqwest::Error),
+    TargetUnreachable(AssetError),
+    InvalidMhtml(std::io::Error),
+}
+
+impl fmt::Display for MonolithError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MonolithError::NoTarget => write!(f, "No target specified"),
+            MonolithError::UnsupportedTargetScheme(scheme) => {
+                write!(f, "Unsupported target URL type: {}", scheme)
+            }
+            MonolithError::InvalidTargetPath(path) => {
+                write!(f, "Could not generate file URL out of given path: {}", path)
+            }
+            MonolithError::TargetIsNotAFile(path) => {
+                write!(f, "Local target is not a file: {}", path)
+            }
+            MonolithError::UnknownEncoding(charset) => write!(f, "Unknown encoding: {}", charset),
+            MonolithError::InvalidBaseUrlPath(path) => {
+                write!(f, "Could not map given path to base URL: {}", path)
+            }
+            MonolithError::InvalidUserAgent(user_agent) => {
+                write!(f, "Invalid User-Agent header specified: {}", user_agent)
+            }
+            MonolithError::InvalidProxy(proxy) => write!(f, "Invalid proxy specified: {}", proxy),
+            MonolithError::UnknownReferrerPolicy(policy) => {
+                write!(f, "Unknown referrer policy: {}", policy)
+            }
+            MonolithError::InvalidStatusList(list) => {
+                write!(f, "Invalid list of status codes specified: {}", list)
+            }
+            MonolithError::InvalidCaCert(path) => {
+                write!(f, "Could not load CA certificates from {}", path)
+            }
+            MonolithError::InvalidClientCert(path) => {
+                write!(f, "Could not load client certificate from {}", path)
+            }
+            MonolithError::InvalidClientKey(path) => write!(
+                f,
+                "Could not load client key from {} (unencrypted PKCS#8 PEM expected)",
+                path
+            ),
+            MonolithError::NoClientCert => write!(f, "Client key specified without certificate"),
+            MonolithError::HttpClient(_) => write!(f, "Failed to initialize HTTP client"),
+            MonolithError::TargetUnreachable(_) => write!(f, "Could not retrieve target document"),
+            MonolithError::InvalidMhtml(_) => write!(f, "Could not read MHTML archive"),
+        }
+    }
+}
+
+impl Error for MonolithError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MonolithError::HttpClient(error) => Some(error),
+            MonolithError::TargetUnreachable(error) => Some(error),
+            MonolithError::InvalidMhtml(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+pub struct SavedDocument {
+    // Resulting document (or target's own contents if it's not an HTML document)
+    pub data: Vec<u8>,
+    // Encoding the document got saved with, empty if it's unknown
+    pub encoding: String,
+    // Media type of the target, anything other than HTML gets passed through as is
+    pub media_type: String,
+    // Where the target ended up being retrieved from, after redirects
+    pub final_url: Url,
+    // Assets to be saved next to the document (only when saving into a directory)
+    pub assets: Vec<Resource>,
+    // Assets that could not be retrieved or embedded along with why, their references were kept
+    // or removed
+    pub failed_assets: Vec<(Url, AssetError)>,
+    // Every asset the document refers to (only recorded when writing a report)
+    pub asset_records: Vec<AssetRecord>,
+}
+
+impl SavedDocument {
+    pub fn is_html(&self) -> bool {
+        is_html_media_type(&self.media_type)
+    }
+}
+
+pub struct SavedPage {
+    // Links between pages saved during the same run rely on them being saved under these names
+    pub file_name: String,
+    pub document: SavedDocument,
+}
+
+// Document as it was retrieved, before its assets got embedded
+struct RetrievedDocument {
+    // Differs from the URL it got retrieved by for documents taken out of MHTML archives
+    url: Url,
+    data: Vec<u8>,
+    final_url: Url,
+    media_type: String,
+    charset: String,
+    archive: Option<MhtmlArchive>,
+}
+
+pub struct Monolith {
+    options: Options,
+    fetcher: Option<Box<dyn AssetFetcher>>,
+    session: Session,
+}
+
+impl Monolith {
+    pub fn new(options: Options) -> Monolith {
+        Monolith {
+            options,
+            fetcher: None,
+            session: Session::default(),
+        }
+    }
+
+    pub fn with_client(options: Options, client: Client) -> Monolith {
+        Monolith::with_fetcher(options, Box::new(client))
+    }
+
+    // Makes assets get retrieved using something other than the default HTTP client
+    pub fn with_fetcher(options: Options, fetcher: Box<dyn AssetFetcher>) -> Monolith {
+        Monolith {
+            options,
+            fetcher: Some(fetcher),
+            session: Session::default(),
+        }
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    // Makes progress get reported as events instead of being printed as a tree
+    pub fn set_reporter(&mut self, reporter: Box<dyn Reporter>) {
+        self.session.reporter = Some(Arc::from(reporter));
+    }
+
+    pub fn report(&self, event: Event) {
+        report(&self.session, &self.options, event);
+    }
+
+    // Retrieves the target (URL, file path, or domain name) and embeds all of its assets
+    pub fn fetch(&mut self, target: &str) -> Result<SavedDocument, MonolithError> {
+        validate_options(&self.options)?;
+        let target_url: Url = resolve_target(target)?;
+        self.init_fetcher()?;
+        self.forget_errors();
+
+        let document: RetrievedDocument = self.retrieve_document(&target_url, &target_url)?;
+        self.save_document(document)
+    }
+
+    // Retrieves the target as it is, without looking into it or embedding anything
+    pub fn retrieve(&mut self, target: &str) -> Result<Vec<u8>, MonolithError> {
+        validate_options(&self.options)?;
+        let target_url: Url = resolve_target(target)?;
+        self.init_fetcher()?;
+        self.forget_errors();
+
+        let (data, ..) = retrieve_asset(
+            &mut self.session,
+            self.fetcher.as_deref().unwrap(),
+            &target_url,
+            &target_url,
+            &self.options,
+            0,
+        )
+        .map_err(MonolithError::TargetUnreachable)?;
+
+        Ok(data)
+    }
+
+    // Saves the target along with pages of the same site it links to (up to crawl_depth links
+    // away), links between saved pages get pointed to their local copies
+    pub fn crawl(
+        &mut self,
+        target: &str,
+        file_name: &str,
+    ) -> Result<Vec<SavedPage>, MonolithError> {
+        validate_options(&self.options)?;
+        let target_url: Url = resolve_target(target)?;
+        self.init_fetcher()?;
+        self.forget_errors();
+
+        // Retrieve all pages first, only links to pages which can be saved should get rewritten
+        let mut documents: Vec<RetrievedDocument> = vec![];
+        let mut page_indices: HashMap<String, usize> = HashMap::new();
+        let mut seen_urls: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<(Url, Url, u32)> = VecDeque::new();
+        seen_urls.insert(clean_url(target_url.clone()).to_string());
+        queue.push_back((target_url.clone(), target_url.clone(), 0));
+        while let Some((parent_url, page_url, depth)) = queue.pop_front() {
+            let document: RetrievedDocument = match self.retrieve_document(&parent_url, &page_url) {
+                Ok(document) => document,
+                Err(error) if documents.is_empty() => return Err(error),
+                // Failures get reported as they happen, such pages are simply left out
+                Err(_) => continue,
+            };
+
+            if !is_html_media_type(&document.media_type) {
+                if documents.is_empty() {
+                    // There's nothing to crawl, the target gets passed through
+                    return Ok(vec![SavedPage {
+                        file_name: file_name.to_string(),
+                        document: self.save_document(document)?,
+                    }]);
+                }
+                continue;
+            }
+
+            // Same page may be linked to by more than one URL
+            let final_url_key: String = clean_url(document.final_url.clone()).to_string();
+            if let Some(index) = page_indices.get(&final_url_key).copied() {
+                page_indices.insert(clean_url(page_url).to_string(), index);
+                continue;
+            }
+
+            if depth < self.options.crawl_depth {
+                let dom: RcDom = html_to_dom(&document.data, document.charset.clone());
+                let base_url: Url = match get_base_url(&dom.document) {
+                    Some(base_url) => resolve_url(&document.final_url, &base_url),
+                    None => document.final_url.clone(),
+                };
+                for link_url in collect_link_urls(&base_url, &dom.document) {
+                    let link_url: Url = clean_url(link_url);
+                    if is_within_crawl_scope(&link_url, &target_url, &self.options)
+                        && seen_urls.insert(link_url.to_string())
+                    {
+                        queue.push_back((document.final_url.clone(), link_url, depth + 1));
+                    }
+                }
+            }
+
+            for url in [&page_url, &document.url, &document.final_url] {
+                page_indices.insert(clean_url(url.clone()).to_string(), documents.len());
+            }
+            documents.push(document);
+        }
+
+        // Name files ahead of saving pages, so that they can link to each other
+        let extension: &str = Path::new(file_name)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or("html");
+        let mut file_names: Vec<String> = vec![file_name.to_string()];
+        for document in documents.iter().skip(1) {
+            let slug: String = create_path_slug(&document.final_url);
+            let mut page_file_name: String = format!("{}.{}", slug, extension);
+            let mut n: usize = 2;
+            while file_names.contains(&page_file_name) {
+                page_file_name = format!("{}-{}.{}", slug, n, extension);
+                n += 1;
+            }
+            file_names.push(page_file_name);
+        }
+        self.session.page_files = page_indices
+            .into_iter()
+            .map(|(url, index)| (url, file_names[index].clone()))
+            .collect();
+
+        let mut pages: Vec<SavedPage> = vec![];
+        for (document, file_name) in documents.into_iter().zip(file_names) {
+            match self.save_document(document) {
+                Ok(document) => pages.push(SavedPage {
+                    file_name,
+                    document,
+                }),
+                Err(error) => {
+                    self.session.page_files.clear();
+                    return Err(error);
+                }
+            }
+        }
+        self.session.page_files.clear();
+
+        Ok(pages)
+    }
+
+    // Embeds assets of an already obtained HTML document (e.g. read from STDIN)
+    pub fn from_data(
+        &mut self,
+        data: Vec<u8>,
+        target_url: &Url,
+    ) -> Result<SavedDocument, MonolithError> {
+        validate_options(&self.options)?;
+        self.create_document(None, data, "".to_string(), target_url, target_url)
+    }
+
+    fn retrieve_document(
+        &mut self,
+        parent_url: &Url,
+        url: &Url,
+    ) -> Result<RetrievedDocument, MonolithError> {
+        let (data, final_url, media_type, charset) = retrieve_asset(
+            &mut self.session,
+            self.fetcher.as_deref().unwrap(),
+            parent_url,
+            url,
+            &self.options,
+            0,
+        )
+        .map_err(MonolithError::TargetUnreachable)?;
+
+        let mut document: RetrievedDocument = RetrievedDocument {
+            url: url.clone(),
+            data,
+            final_url,
+            media_type,
+            charset,
+            archive: None,
+        };
+
+        // Saved pages get their assets out of the archive instead of the network
+        if is_mhtml_media_type(&document.media_type) {
+            let archive: MhtmlArchive =
+                MhtmlArchive::parse(&document.data).map_err(MonolithError::InvalidMhtml)?;
+            let archived_document: Resource = archive.document().clone();
+            document = RetrievedDocument {
+                url: archived_document.url.clone(),
+                data: archived_document.data,
+                final_url: archived_document.url,
+                media_type: archived_document.media_type,
+                charset: archived_document.charset,
+                archive: Some(archive),
+            };
+        }
+
+        Ok(document)
+    }
+
+    fn save_document(
+        &mut self,
+        document: RetrievedDocument,
+    ) -> Result<SavedDocument, MonolithError> {
+        // Provide output as text without processing it, the way browsers do
+        if !is_html_media_type(&document.media_type) {
+            self.report(Event::DocumentFinished {
+                url: &document.url,
+                bytes: document.data.len(),
+            });
+
+            return Ok(SavedDocument {
+                data: document.data,
+                encoding: document.charset,
+                media_type: document.media_type,
+                final_url: document.final_url,
+                assets: vec![],
+                failed_assets: vec![],
+                asset_records: vec![],
+            });
+        }
+
+        let fetcher: Option<&dyn AssetFetcher> = document
+            .archive
+            .as_ref()
+            .map(|archive| archive as &dyn AssetFetcher);
+        self.create_document(
+            fetcher,
+            document.data,
+            document.charset,
+            &document.url,
+            &document.final_url,
+        )
+    }
+
+    // Targets failing to be retrieved may be given another try later, unlike their assets
+    fn forget_errors(&mut self) {
+        self.session.document.errors.clear();
+    }
+
+    fn init_fetcher(&mut self) -> Result<(), MonolithError> {
+        if self.fetcher.is_none() {
+            self.fetcher = Some(Box::new(create_http_client(&self.options)?));
+        }
+
+        Ok(())
+    }
+
+    // Assets get retrieved using given fetcher, if any, instead of the one set up for this instance
+    fn create_document(
+        &mut self,
+        fetcher: Option<&dyn AssetFetcher>,
+        data: Vec<u8>,
+        encoding: String,
+        target_url: &Url,
+        final_url: &Url,
+    ) -> Result<SavedDocument, MonolithError> {
+        self.init_fetcher()?;
+        let fetcher: &dyn AssetFetcher = match fetcher {
+            Some(fetcher) => fetcher,
+            None => self.fetcher.as_deref().unwrap(),
+        };
+        let options: &Options = &self.options;
+        let session: &mut Session = &mut self.session;
+
+        // Forget assets stored for documents saved earlier
+        session.document = DocumentState::default();
+
+        // At first we assume that base URL is the same as target URL
+        let mut base_url: Url = target_url.clone();
+        if options.base_url.clone().unwrap_or_default().is_empty() {
+            base_url = final_url.clone();
+        }
+
+        let mut document_encoding: String = encoding;
+
+        // Initial parse
+        let mut dom: RcDom = html_to_dom(&data, document_encoding.clone());
+
+        // TODO: investigate if charset from filesystem/data URL/HTTP headers
+        //       has say over what's specified in HTML
+
+        // Attempt to determine document's charset
+        if let Some(html_charset) = get_charset(&dom.document) {
+            if !html_charset.is_empty() {
+                // Check if the charset specified inside HTML is valid
+                if let Some(encoding) = Encoding::for_label_no_replacement(html_charset.as_bytes())
+                {
+                    document_encoding = html_charset;
+                    dom = html_to_dom(&data, encoding.name().to_string());
+                }
+            }
+        }
+
+        // Use custom base URL if specified, read and use what's in the DOM otherwise
+        let custom_base_url: String = options.base_url.clone().unwrap_or_default();
+        if custom_base_url.is_empty() {
+            // No custom base URL is specified
+            // Try to see if document has BASE element
+            if let Some(existing_base_url) = get_base_url(&dom.document) {
+                base_url = resolve_url(target_url, &existing_base_url);
+            }
+        } else {
+            // Custom base URL provided
+            match Url::parse(&custom_base_url) {
+                Ok(parsed_url) => {
+                    if parsed_url.scheme() == "file" {
+                        // File base URLs can only work with
+                        // documents saved from filesystem
+                        if target_url.scheme() == "file" {
+                            base_url = parsed_url;
+                        }
+                    } else {
+                        base_url = parsed_url;
+                    }
+                }
+                Err(_) => {
+                    // Failed to parse given base URL, perhaps it's a filesystem path?
+                    if target_url.scheme() == "file" {
+                        // Relative paths could work for documents saved from filesystem
+                        let path: &Path = Path::new(&custom_base_url);
+                        if path.exists() {
+                            match Url::from_file_path(fs::canonicalize(path).unwrap()) {
+                                Ok(file_url) => {
+                                    base_url = file_url;
+                                }
+                                Err(_) => {
+                                    return Err(MonolithError::InvalidBaseUrlPath(custom_base_url));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Retrieve remote assets in parallel ahead of embedding them
+        if options.jobs > 1 {
+            prefetch_assets(session, fetcher, &base_url, &dom.document, options);
+        }
+
+        // Traverse through the document and embed remote assets
+        walk_and_embed_assets(session, fetcher, &base_url, &dom.document, options, 0);
+
+        // Update or add new BASE element to reroute network requests and hash-links
+        if let Some(new_base_url) = options.base_url.clone() {
+            dom = set_base_url(&dom.document, new_base_url);
+        }
+
+        // Request and embed /favicon.ico (unless it's already linked in the document)
+        if !options.no_images
+            && (target_url.scheme() == "http" || target_url.scheme() == "https")
+            && !has_favicon(&dom.document)
+        {
+            let favicon_ico_url: Url = resolve_url(&base_url, "/favicon.ico");
+
+            if let Ok((data, final_url, media_type, charset)) =
+                retrieve_asset(session, fetcher, target_url, &favicon_ico_url, options, 0)
+            {
+                let favicon_data_url: String = embed_asset(
+                    session,
+                    &media_type,
+                    &charset,
+                    &data,
+                    &final_url,
+                    None,
+                    options,
+                    0,
+                );
+                dom = add_favicon(&dom.document, favicon_data_url);
+            }
+        }
+
+        // Save using specified charset, if given
+        if let Some(custom_charset) = options.charset.clone() {
+            document_encoding = custom_charset;
+            dom = set_charset(dom, document_encoding.clone());
+        }
+
+        // Serialize DOM tree
+        let mut result: Vec<u8> = serialize_document(dom, document_encoding.clone(), options);
+
+        // Prepend metadata comment tag
+        if !options.no_metadata {
+            let mut metadata_comment: String = create_metadata_tag(target_url);
+            metadata_comment += "\n";
+            result.splice(0..0, metadata_comment.as_bytes().to_vec());
+        }
+
+        // Put document and its assets into separate parts of an MHTML archive
+        let mut media_type: String = "text/html".to_string();
+        let document: DocumentState = std::mem::take(&mut session.document);
+        let mut assets: Vec<Resource> = document.resources;
+        if options.output_format == OutputFormat::Mhtml {
+            result = create_mhtml(&result, &document_encoding, target_url, &assets);
+            media_type = "multipart/related".to_string();
+            assets.clear();
+        }
+
+        report(
+            session,
+            options,
+            Event::DocumentFinished {
+                url: target_url,
+                bytes: result.len(),
+            },
+        );
+
+        Ok(SavedDocument {
+            data: result,
+            encoding: document_encoding,
+            media_type,
+            final_url: final_url.clone(),
+            assets,
+            failed_assets: document.failed_assets,
+            asset_records: document.asset_records,
+        })
+    }
+}
+
+pub fn create_http_client(options: &Options) -> Result<Client, MonolithError> {
+    let mut header_map = HeaderMap::new();
+    if let Some(user_agent) = &options.user_agent {
+        header_map.insert(
+            USER_AGENT,
+            HeaderValue::from_str(user_agent)
+                .map_err(|_| MonolithError::InvalidUserAgent(user_agent.clone()))?,
+        );
+    }
+
+    let mut client_builder: ClientBuilder = if options.timeout > 0 {
+        Client::builder().timeout(Duration::from_secs(options.timeout))
+    } else {
+        // No timeout is default
+        Client::builder()
+    }
+    .danger_accept_invalid_certs(options.insecure)
+    .default_headers(header_map)
+    .cookie_provider(options.__cookies.clone())
+    // Redirects get followed by the fetcher, one request at a time
+    .redirect(Policy::none())
+    // Proxy environment variables are taken care of by ProxyConfig
+    .no_proxy();
+
+    let proxy_config: ProxyConfig = ProxyConfig::from_options(options)?;
+    if !proxy_config.is_empty() {
+        client_builder = client_builder.proxy(Proxy::custom(move |url| {
+            proxy_config.proxy_for(url).cloned()
+        }));
+    }
+
+    let tls_config: TlsConfig = TlsConfig::from_options(options)?;
+    for certificate in tls_config.root_certificates {
+        client_builder = client_builder.add_root_certificate(certificate);
+    }
+    if let Some(identity) = tls_config.identity {
+        client_builder = client_builder.identity(identity);
+    }
+
+    client_builder.build().map_err(MonolithError::HttpClient)
+}
+
+pub fn validate_options(options: &Options) -> Result<(), MonolithError> {
+    // Check if custom charset is valid
+    if let Some(custom_charset) = &options.charset {
+        if Encoding::for_label_no_replacement(custom_charset.as_bytes()).is_none() {
+            return Err(MonolithError::UnknownEncoding(custom_charset.clone()));
+        }
+    }
+
+    // Check if lists of status codes are valid
+    for status_list in [&options.accept_status, &options.fail_on_status]
+        .iter()
+        .copied()
+        .flatten()
+    {
+        if parse_status_patterns(status_list).is_none() {
+            return Err(MonolithError::InvalidStatusList(status_list.clone()));
+        }
+    }
+
+    // Check if referrer policy is known
+    if let Some(referrer_policy) = &options.referrer_policy {
+        if ReferrerPolicy::parse(referrer_policy).is_none() {
+            return Err(MonolithError::UnknownReferrerPolicy(
+                referrer_policy.clone(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn is_html_media_type(media_type: &str) -> bool {
+    media_type.eq_ignore_ascii_case("text/html")
+        || media_type.eq_ignore_ascii_case("application/xhtml+xml")
+}
+
+// Pages of the target's site (or of the domains specified in options) are worth saving
+pub fn is_within_crawl_scope(url: &Url, target_url: &Url, options: &Options) -> bool {
+    match (url.scheme(), target_url.scheme()) {
+        ("http" | "https", "http" | "https") => {}
+        // Local pages have to be in the same directory as the target or under it
+        ("file", "file") => {
+            let target_directory: &str =
+                &target_url.path()[..=target_url.path().rfind('/').unwrap()];
+            return url.path().starts_with(target_directory);
+        }
+        _ => return false,
+    }
+
+    let host: &str = url.host_str().unwrap_or_default();
+    let is_target_host: bool = host == target_url.host_str().unwrap_or_default();
+    match &options.domains {
+        Some(domains) => {
+            let domain_matches: bool = domains
+                .iter()
+                .any(|domain| domain_is_within_domain(host, domain.trim()));
+            if options.blacklist_domains {
+                is_target_host && !domain_matches
+            } else {
+                domain_matches
+            }
+        }
+        None => is_target_host,
+    }
+}
+
+// Web pages saved by browsers as a single file (.mht/.mhtml)
+pub fn is_mhtml_media_type(media_type: &str) -> bool {
+    media_type.eq_ignore_ascii_case("multipart/related")
+        || media_type.eq_ignore_ascii_case("message/rfc822")
+        || media_type.eq_ignore_ascii_case("application/x-mimearchive")
+}
+
+pub fn resolve_target(target: &str) -> Result<Url, MonolithError> {
+    if target.is_empty() {
+        return Err(MonolithError::NoTarget);
+    }
+
+    match Url::parse(target) {
+        Ok(url) => match url.scheme() {
+            "data" | "file" | "http" | "https" => Ok(url),
+            unsupported_scheme => Err(MonolithError::UnsupportedTargetScheme(
+                unsupported_scheme.to_string(),
+            )),
+        },
+        Err(_) => {
+            // Failed to parse given base URL (perhaps it's a filesystem path?)
+            let path: &Path = Path::new(target);
+            if path.exists() {
+                if path.is_file() {
+                    let canonical_path = fs::canonicalize(path).unwrap();
+                    Url::from_file_path(canonical_path)
+                        .map_err(|_| MonolithError::InvalidTargetPath(target.to_string()))
+                } else {
+                    Err(MonolithError::TargetIsNotAFile(target.to_string()))
+                }
+            } else {
+                // It is not a FS path, now we do what browsers do:
+                // prepend "http://" and hope it points to a website
+                Ok(Url::parse(&format!("http://{hopefully_url}", hopefully_url = target)).unwrap())
+            }
+        }
+    }
+}
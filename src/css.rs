@@ -1,13 +1,14 @@
 use cssparser::{
     serialize_identifier, serialize_string, ParseError, Parser, ParserInput, SourcePosition, Token,
 };
-use reqwest::blocking::Client;
-use std::collections::HashMap;
 use url::Url;
 
+use crate::fetcher::AssetFetcher;
 use crate::opts::Options;
-use crate::url::{create_data_url, resolve_url, EMPTY_IMAGE_DATA_URL};
-use crate::utils::retrieve_asset;
+use crate::report::{record_asset, AssetRecord};
+use crate::session::Session;
+use crate::url::{embed_asset, resolve_url, EMPTY_IMAGE_DATA_URL};
+use crate::utils::{record_failed_asset, report_failure, retrieve_asset, AssetError};
 
 const CSS_PROPS_WITH_IMAGE_URLS: &[&str] = &[
     // Universal
@@ -30,20 +31,32 @@ const CSS_PROPS_WITH_IMAGE_URLS: &[&str] = &[
     "symbols",
 ];
 
+// Finds assets the stylesheet refers to the same way embed_css() does, without retrieving them
+pub fn collect_css_urls(
+    fetcher: &dyn AssetFetcher,
+    document_url: &Url,
+    css: &str,
+    options: &Options,
+) -> Vec<Url> {
+    let mut session: Session = Session::collecting();
+    let _ = embed_css(&mut session, fetcher, document_url, css, options, 0);
+    session.collected_urls.unwrap_or_default()
+}
+
 pub fn embed_css(
-    cache: &mut HashMap<String, Vec<u8>>,
-    client: &Client,
+    session: &mut Session,
+    fetcher: &dyn AssetFetcher,
     document_url: &Url,
     css: &str,
     options: &Options,
     depth: u32,
-) -> String {
+) -> Result<String, AssetError> {
     let mut input = ParserInput::new(&css);
     let mut parser = Parser::new(&mut input);
 
     process_css(
-        cache,
-        client,
+        session,
+        fetcher,
         document_url,
         &mut parser,
         options,
@@ -52,7 +65,7 @@ pub fn embed_css(
         "",
         "",
     )
-    .unwrap()
+    .map_err(|_| AssetError::InvalidCss)
 }
 
 pub fn format_ident(ident: &str) -> String {
@@ -76,10 +89,10 @@ pub fn is_image_url_prop(prop_name: &str) -> bool {
 }
 
 pub fn process_css<'a>(
-    cache: &mut HashMap<String, Vec<u8>>,
-    client: &Client,
+    session: &mut Session,
+    fetcher: &dyn AssetFetcher,
     document_url: &Url,
-    parser: &mut Parser,
+    parser: &mut Parser<'a, '_>,
     options: &Options,
     depth: u32,
     rule_name: &str,
@@ -127,21 +140,19 @@ pub fn process_css<'a>(
                     closure = "}";
                 }
 
-                let block_css: String = parser
-                    .parse_nested_block(|parser| {
-                        process_css(
-                            cache,
-                            client,
-                            document_url,
-                            parser,
-                            options,
-                            depth,
-                            rule_name,
-                            curr_prop.as_str(),
-                            func_name,
-                        )
-                    })
-                    .unwrap();
+                let block_css: String = parser.parse_nested_block(|parser| {
+                    process_css(
+                        session,
+                        fetcher,
+                        document_url,
+                        parser,
+                        options,
+                        depth,
+                        rule_name,
+                        curr_prop.as_str(),
+                        func_name,
+                    )
+                })?;
                 result.push_str(block_css.as_str());
 
                 result.push_str(closure);
@@ -190,40 +201,78 @@ pub fn process_css<'a>(
                     }
 
                     let import_full_url: Url = resolve_url(&document_url, value);
+                    let record: AssetRecord =
+                        AssetRecord::new(None, "@import", value, document_url, &import_full_url);
+                    // Stylesheets which could not be processed are treated as not retrieved
                     match retrieve_asset(
-                        cache,
-                        client,
+                        session,
+                        fetcher,
                         &document_url,
                         &import_full_url,
                         options,
                         depth + 1,
+                    )
+                    .and_then(
+                        |(import_contents, import_final_url, import_media_type, import_charset)| {
+                            match embed_css(
+                                session,
+                                fetcher,
+                                &import_final_url,
+                                &String::from_utf8_lossy(&import_contents),
+                                options,
+                                depth + 1,
+                            ) {
+                                Ok(import_css) => Ok((
+                                    import_contents.len(),
+                                    import_css,
+                                    import_final_url,
+                                    import_media_type,
+                                    import_charset,
+                                )),
+                                Err(error) => Err(report_failure(
+                                    session,
+                                    &import_full_url,
+                                    error,
+                                    options,
+                                    depth + 1,
+                                )),
+                            }
+                        },
                     ) {
                         Ok((
-                            import_contents,
+                            import_size,
+                            import_css,
                             import_final_url,
                             import_media_type,
                             import_charset,
                         )) => {
-                            let mut import_data_url = create_data_url(
+                            record_asset(
+                                session,
+                                options,
+                                record.succeeded(
+                                    &import_final_url,
+                                    &import_media_type,
+                                    import_size,
+                                    session,
+                                ),
+                            );
+
+                            let import_data_url: String = embed_asset(
+                                session,
                                 &import_media_type,
                                 &import_charset,
-                                embed_css(
-                                    cache,
-                                    client,
-                                    &import_final_url,
-                                    &String::from_utf8_lossy(&import_contents),
-                                    options,
-                                    depth + 1,
-                                )
-                                .as_bytes(),
+                                import_css.as_bytes(),
                                 &import_final_url,
+                                import_full_url.fragment(),
+                                options,
+                                depth,
                             );
-                            import_data_url.set_fragment(import_full_url.fragment());
-                            result.push_str(
-                                format_quoted_string(&import_data_url.to_string()).as_str(),
-                            );
+                            result.push_str(format_quoted_string(&import_data_url).as_str());
                         }
-                        Err(_) => {
+                        Err(error) => {
+                            record_failed_asset(session, &import_full_url, &error);
+                            record_asset(session, options, record.failed(&error));
+
                             // Keep remote reference if unable to retrieve the asset
                             if import_full_url.scheme() == "http"
                                 || import_full_url.scheme() == "https"
@@ -245,23 +294,49 @@ pub fn process_css<'a>(
                             result.push_str(format_quoted_string(EMPTY_IMAGE_DATA_URL).as_str());
                         } else {
                             let resolved_url: Url = resolve_url(&document_url, value);
+                            let record: AssetRecord = AssetRecord::new(
+                                None,
+                                &curr_prop,
+                                value,
+                                document_url,
+                                &resolved_url,
+                            );
                             match retrieve_asset(
-                                cache,
-                                client,
+                                session,
+                                fetcher,
                                 &document_url,
                                 &resolved_url,
                                 options,
                                 depth + 1,
                             ) {
                                 Ok((data, final_url, media_type, charset)) => {
-                                    let mut data_url =
-                                        create_data_url(&media_type, &charset, &data, &final_url);
-                                    data_url.set_fragment(resolved_url.fragment());
-                                    result.push_str(
-                                        format_quoted_string(&data_url.to_string()).as_str(),
+                                    record_asset(
+                                        session,
+                                        options,
+                                        record.succeeded(
+                                            &final_url,
+                                            &media_type,
+                                            data.len(),
+                                            session,
+                                        ),
+                                    );
+
+                                    let data_url: String = embed_asset(
+                                        session,
+                                        &media_type,
+                                        &charset,
+                                        &data,
+                                        &final_url,
+                                        resolved_url.fragment(),
+                                        options,
+                                        depth,
                                     );
+                                    result.push_str(format_quoted_string(&data_url).as_str());
                                 }
-                                Err(_) => {
+                                Err(error) => {
+                                    record_failed_asset(session, &resolved_url, &error);
+                                    record_asset(session, options, record.failed(&error));
+
                                     // Keep remote reference if unable to retrieve the asset
                                     if resolved_url.scheme() == "http"
                                         || resolved_url.scheme() == "https"
@@ -341,33 +416,61 @@ pub fn process_css<'a>(
                 result.push_str("url(");
                 if is_import {
                     let full_url: Url = resolve_url(&document_url, value);
+                    let record: AssetRecord =
+                        AssetRecord::new(None, "@import", value, document_url, &full_url);
+                    // Stylesheets which could not be processed are treated as not retrieved
                     match retrieve_asset(
-                        cache,
-                        client,
+                        session,
+                        fetcher,
                         &document_url,
                         &full_url,
                         options,
                         depth + 1,
-                    ) {
-                        Ok((css, final_url, media_type, charset)) => {
-                            let mut data_url = create_data_url(
+                    )
+                    .and_then(|(css, final_url, media_type, charset)| {
+                        match embed_css(
+                            session,
+                            fetcher,
+                            &final_url,
+                            &String::from_utf8_lossy(&css),
+                            options,
+                            depth + 1,
+                        ) {
+                            Ok(embedded_css) => {
+                                Ok((css.len(), embedded_css, final_url, media_type, charset))
+                            }
+                            Err(error) => Err(report_failure(
+                                session,
+                                &full_url,
+                                error,
+                                options,
+                                depth + 1,
+                            )),
+                        }
+                    }) {
+                        Ok((css_size, embedded_css, final_url, media_type, charset)) => {
+                            record_asset(
+                                session,
+                                options,
+                                record.succeeded(&final_url, &media_type, css_size, session),
+                            );
+
+                            let data_url: String = embed_asset(
+                                session,
                                 &media_type,
                                 &charset,
-                                embed_css(
-                                    cache,
-                                    client,
-                                    &final_url,
-                                    &String::from_utf8_lossy(&css),
-                                    options,
-                                    depth + 1,
-                                )
-                                .as_bytes(),
+                                embedded_css.as_bytes(),
                                 &final_url,
+                                full_url.fragment(),
+                                options,
+                                depth,
                             );
-                            data_url.set_fragment(full_url.fragment());
-                            result.push_str(format_quoted_string(&data_url.to_string()).as_str());
+                            result.push_str(format_quoted_string(&data_url).as_str());
                         }
-                        Err(_) => {
+                        Err(error) => {
+                            record_failed_asset(session, &full_url, &error);
+                            record_asset(session, options, record.failed(&error));
+
                             // Keep remote reference if unable to retrieve the asset
                             if full_url.scheme() == "http" || full_url.scheme() == "https" {
                                 result
@@ -380,22 +483,39 @@ pub fn process_css<'a>(
                         result.push_str(format_quoted_string(EMPTY_IMAGE_DATA_URL).as_str());
                     } else {
                         let full_url: Url = resolve_url(&document_url, value);
+                        let record: AssetRecord =
+                            AssetRecord::new(None, &curr_prop, value, document_url, &full_url);
                         match retrieve_asset(
-                            cache,
-                            client,
+                            session,
+                            fetcher,
                             &document_url,
                             &full_url,
                             options,
                             depth + 1,
                         ) {
                             Ok((data, final_url, media_type, charset)) => {
-                                let mut data_url =
-                                    create_data_url(&media_type, &charset, &data, &final_url);
-                                data_url.set_fragment(full_url.fragment());
-                                result
-                                    .push_str(format_quoted_string(&data_url.to_string()).as_str());
+                                record_asset(
+                                    session,
+                                    options,
+                                    record.succeeded(&final_url, &media_type, data.len(), session),
+                                );
+
+                                let data_url: String = embed_asset(
+                                    session,
+                                    &media_type,
+                                    &charset,
+                                    &data,
+                                    &final_url,
+                                    full_url.fragment(),
+                                    options,
+                                    depth,
+                                );
+                                result.push_str(format_quoted_string(&data_url).as_str());
                             }
-                            Err(_) => {
+                            Err(error) => {
+                                record_failed_asset(session, &full_url, &error);
+                                record_asset(session, options, record.failed(&error));
+
                                 // Keep remote reference if unable to retrieve the asset
                                 if full_url.scheme() == "http" || full_url.scheme() == "https" {
                                     result.push_str(
@@ -415,21 +535,19 @@ pub fn process_css<'a>(
                 result.push_str(function_name);
                 result.push_str("(");
 
-                let block_css: String = parser
-                    .parse_nested_block(|parser| {
-                        process_css(
-                            cache,
-                            client,
-                            document_url,
-                            parser,
-                            options,
-                            depth,
-                            curr_rule.as_str(),
-                            curr_prop.as_str(),
-                            function_name,
-                        )
-                    })
-                    .unwrap();
+                let block_css: String = parser.parse_nested_block(|parser| {
+                    process_css(
+                        session,
+                        fetcher,
+                        document_url,
+                        parser,
+                        options,
+                        depth,
+                        curr_rule.as_str(),
+                        curr_prop.as_str(),
+                        function_name,
+                    )
+                })?;
                 result.push_str(block_css.as_str());
 
                 result.push_str(")");
@@ -0,0 +1,82 @@
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::io;
+use std::path::Path;
+use url::Url;
+
+use crate::utils::{detect_media_type, Resource};
+
+pub const ASSETS_DIRECTORY: &str = "assets";
+pub const INDEX_FILE_NAME: &str = "index.html";
+
+// Assets are named after their contents, which makes identical ones share the same file
+pub fn create_asset_file_name(media_type: &str, data: &[u8], url: &Url) -> String {
+    let digest: String = Sha1::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    let mut detected_media_type: String = detect_media_type(data, url);
+    if detected_media_type.is_empty() {
+        detected_media_type = media_type.to_string();
+    }
+
+    match get_file_extension(&detected_media_type) {
+        Some(extension) => format!("{}.{}", digest, extension),
+        None => digest,
+    }
+}
+
+pub fn get_file_extension(media_type: &str) -> Option<&'static str> {
+    let extension: &str = match media_type.to_lowercase().as_str() {
+        "application/javascript" | "text/javascript" => "js",
+        "application/json" => "json",
+        "application/pdf" => "pdf",
+        "application/x-shockwave-flash" => "swf",
+        "audio/flac" => "flac",
+        "audio/mpeg" => "mp3",
+        "audio/ogg" => "ogg",
+        "audio/wav" => "wav",
+        "font/otf" => "otf",
+        "font/ttf" => "ttf",
+        "font/woff" => "woff",
+        "font/woff2" => "woff2",
+        "image/avif" => "avif",
+        "image/bmp" => "bmp",
+        "image/gif" => "gif",
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/svg+xml" => "svg",
+        "image/tiff" => "tiff",
+        "image/webp" => "webp",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        "text/css" => "css",
+        "text/html" | "application/xhtml+xml" => "html",
+        "text/plain" => "txt",
+        "video/avi" => "avi",
+        "video/mp4" => "mp4",
+        "video/ogg" => "ogv",
+        "video/webm" => "webm",
+        _ => return None,
+    };
+
+    Some(extension)
+}
+
+// Writes the document and assets/ into given directory, creating it if needed
+pub fn save_to_directory(
+    directory_path: &Path,
+    document_file_name: &str,
+    document: &[u8],
+    assets: &[Resource],
+) -> io::Result<()> {
+    let assets_path = directory_path.join(ASSETS_DIRECTORY);
+    fs::create_dir_all(&assets_path)?;
+
+    for asset in assets {
+        let file_name: String = create_asset_file_name(&asset.media_type, &asset.data, &asset.url);
+        fs::write(assets_path.join(file_name), &asset.data)?;
+    }
+
+    fs::write(directory_path.join(document_file_name), document)
+}
@@ -0,0 +1,431 @@
+use reqwest::blocking::Client;
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, CACHE_CONTROL, CONTENT_TYPE, COOKIE, ETAG, EXPIRES, HOST,
+    IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LOCATION, ORIGIN, REFERER, USER_AGENT,
+};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use url::Url;
+
+use crate::opts::Options;
+use crate::url::parse_data_url;
+use crate::utils::{detect_media_type, domain_is_within_domain, parse_content_type, AssetError};
+
+pub const MAX_REDIRECTS: usize = 10;
+
+pub struct AssetRequest<'a> {
+    // Document which refers to the asset
+    pub parent_url: &'a Url,
+    pub url: &'a Url,
+    // Validators of a previously saved copy, make the request conditional when present
+    pub etag: Option<&'a str>,
+    pub last_modified: Option<&'a str>,
+}
+
+impl<'a> AssetRequest<'a> {
+    pub fn new(parent_url: &'a Url, url: &'a Url) -> AssetRequest<'a> {
+        AssetRequest {
+            parent_url,
+            url,
+            etag: None,
+            last_modified: None,
+        }
+    }
+}
+
+// Header specified by the user, sent only to given domains if there are any
+#[derive(Clone, Debug)]
+pub struct RequestHeader {
+    pub domains: Vec<String>,
+    pub name: HeaderName,
+    pub value: HeaderValue,
+}
+
+impl RequestHeader {
+    pub fn applies_to(&self, url: &Url) -> bool {
+        let host: &str = url.host_str().unwrap_or_default();
+
+        self.domains.is_empty()
+            || self
+                .domains
+                .iter()
+                .any(|domain| domain_is_within_domain(host, domain))
+    }
+}
+
+// Parses "Name: value", optionally prefixed with comma-separated domains (example.com=Name: value);
+// header names can't contain "=", which makes the prefix unambiguous
+pub fn parse_request_header(header: &str) -> Option<RequestHeader> {
+    let (name, value) = header.split_once(':')?;
+    let (domains, name) = match name.split_once('=') {
+        Some((domains, name)) => {
+            let domains: Vec<String> = domains
+                .split(',')
+                .map(|domain| domain.trim())
+                .filter(|domain| !domain.is_empty())
+                .map(|domain| domain.to_string())
+                .collect();
+            if domains.is_empty() {
+                return None;
+            }
+            (domains, name)
+        }
+        None => (vec![], name),
+    };
+
+    Some(RequestHeader {
+        domains,
+        name: HeaderName::from_bytes(name.trim().as_bytes()).ok()?,
+        value: HeaderValue::from_str(value.trim()).ok()?,
+    })
+}
+
+// How much of the referring document's URL gets revealed to servers (W3C Referrer Policy)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReferrerPolicy {
+    NoReferrer,
+    NoReferrerWhenDowngrade,
+    Origin,
+    OriginWhenCrossOrigin,
+    SameOrigin,
+    StrictOrigin,
+    // Browsers' default
+    #[default]
+    StrictOriginWhenCrossOrigin,
+    UnsafeUrl,
+}
+
+impl ReferrerPolicy {
+    pub fn parse(policy: &str) -> Option<ReferrerPolicy> {
+        match policy.trim().to_lowercase().as_str() {
+            "no-referrer" => Some(ReferrerPolicy::NoReferrer),
+            "no-referrer-when-downgrade" => Some(ReferrerPolicy::NoReferrerWhenDowngrade),
+            "origin" => Some(ReferrerPolicy::Origin),
+            "origin-when-cross-origin" => Some(ReferrerPolicy::OriginWhenCrossOrigin),
+            "same-origin" => Some(ReferrerPolicy::SameOrigin),
+            "strict-origin" => Some(ReferrerPolicy::StrictOrigin),
+            "strict-origin-when-cross-origin" => Some(ReferrerPolicy::StrictOriginWhenCrossOrigin),
+            "unsafe-url" => Some(ReferrerPolicy::UnsafeUrl),
+            _ => None,
+        }
+    }
+
+    // Value of Referer header for request to url made by document at parent_url, if any
+    pub fn referrer(&self, parent_url: &Url, url: &Url) -> Option<String> {
+        // Local documents and data URLs never get revealed
+        if !is_http_url(parent_url) {
+            return None;
+        }
+
+        let mut full: Url = parent_url.clone();
+        full.set_fragment(None);
+        let _ = full.set_username("");
+        let _ = full.set_password(None);
+        let origin: String = format!("{}/", parent_url.origin().ascii_serialization());
+
+        let is_same_origin: bool = parent_url.origin() == url.origin();
+        let is_downgrade: bool = parent_url.scheme() == "https" && url.scheme() != "https";
+
+        match self {
+            ReferrerPolicy::NoReferrer => None,
+            ReferrerPolicy::NoReferrerWhenDowngrade if is_downgrade => None,
+            ReferrerPolicy::NoReferrerWhenDowngrade => Some(full.to_string()),
+            ReferrerPolicy::Origin => Some(origin),
+            ReferrerPolicy::OriginWhenCrossOrigin if is_same_origin => Some(full.to_string()),
+            ReferrerPolicy::OriginWhenCrossOrigin => Some(origin),
+            ReferrerPolicy::SameOrigin if is_same_origin => Some(full.to_string()),
+            ReferrerPolicy::SameOrigin => None,
+            ReferrerPolicy::StrictOrigin if is_downgrade => None,
+            ReferrerPolicy::StrictOrigin => Some(origin),
+            ReferrerPolicy::StrictOriginWhenCrossOrigin if is_same_origin => Some(full.to_string()),
+            ReferrerPolicy::StrictOriginWhenCrossOrigin if is_downgrade => None,
+            ReferrerPolicy::StrictOriginWhenCrossOrigin => Some(origin),
+            ReferrerPolicy::UnsafeUrl => Some(full.to_string()),
+        }
+    }
+}
+
+// Browsers request fonts in CORS mode, which makes them send Origin for cross-origin ones
+pub fn is_cors_request(parent_url: &Url, url: &Url) -> bool {
+    let path: String = url.path().to_lowercase();
+
+    is_http_url(parent_url)
+        && parent_url.origin() != url.origin()
+        && [".eot", ".otf", ".ttf", ".woff", ".woff2"]
+            .iter()
+            .any(|extension| path.ends_with(extension))
+}
+
+fn is_http_url(url: &Url) -> bool {
+    url.scheme() == "http" || url.scheme() == "https"
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpExchange {
+    pub method: String,
+    pub request_http_version: String,
+    pub request_headers: Vec<(String, String)>,
+    pub http_version: String,
+    pub response_headers: Vec<(String, String)>,
+    // Time spent waiting for the response to start, and then reading its body
+    pub wait: Duration,
+    pub receive: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct FetchedAsset {
+    pub data: Vec<u8>,
+    // Where the asset ended up being retrieved from, after redirects
+    pub final_url: Url,
+    pub media_type: String,
+    pub charset: String,
+    // HTTP status code, local and data URL assets are always 200
+    pub status: u16,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    // Tell for how long the asset may be reused without revalidating it
+    pub cache_control: Option<String>,
+    pub expires: Option<String>,
+    // Headers sent and received, only present for assets retrieved over HTTP(S)
+    pub http: Option<HttpExchange>,
+    // Responses which pointed elsewhere on the way to final_url, in the order they were received
+    pub redirects: Vec<FetchedAsset>,
+}
+
+impl FetchedAsset {
+    pub fn new(data: Vec<u8>, final_url: Url, media_type: String, charset: String) -> FetchedAsset {
+        FetchedAsset {
+            data,
+            final_url,
+            media_type,
+            charset,
+            status: 200,
+            etag: None,
+            last_modified: None,
+            cache_control: None,
+            expires: None,
+            http: None,
+            redirects: vec![],
+        }
+    }
+
+    pub fn is_not_modified(&self) -> bool {
+        self.status == 304
+    }
+
+    // Where the response says to look for the asset instead, if it's a redirect
+    pub fn redirect_location(&self) -> Option<Url> {
+        if !matches!(self.status, 301 | 302 | 303 | 307 | 308) {
+            return None;
+        }
+
+        let (_, location) = self
+            .http
+            .as_ref()?
+            .response_headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(LOCATION.as_str()))?;
+        self.final_url.join(location).ok()
+    }
+}
+
+// Maps asset URLs to their contents; caching, logging and domain filtering
+// are done by retrieve_asset(), implementations only have to obtain the data
+pub trait AssetFetcher: Sync {
+    fn fetch(&self, request: &AssetRequest, options: &Options) -> Result<FetchedAsset, AssetError>;
+}
+
+// Default implementation: data URLs, local files, and HTTP(S) requests
+impl AssetFetcher for Client {
+    fn fetch(&self, request: &AssetRequest, options: &Options) -> Result<FetchedAsset, AssetError> {
+        let url: &Url = request.url;
+
+        match url.scheme() {
+            "data" => Ok(fetch_data_url(url)),
+            "file" => fetch_local_file(url),
+            _ => fetch_remote_asset(self, request, options),
+        }
+    }
+}
+
+pub fn fetch_data_url(url: &Url) -> FetchedAsset {
+    let (media_type, charset, data) = parse_data_url(url);
+    FetchedAsset::new(data, url.clone(), media_type, charset)
+}
+
+pub fn fetch_local_file(url: &Url) -> Result<FetchedAsset, AssetError> {
+    let path_buf: PathBuf = url.to_file_path().map_err(|_| AssetError::NotFound)?;
+    let path: &Path = path_buf.as_path();
+
+    if !path.exists() {
+        return Err(AssetError::NotFound);
+    }
+
+    if path.is_dir() {
+        return Err(io::Error::other("is a directory").into());
+    }
+
+    let file_blob: Vec<u8> = fs::read(path)?;
+    let media_type: String = detect_media_type(&file_blob, url);
+
+    Ok(FetchedAsset::new(
+        file_blob,
+        url.clone(),
+        media_type,
+        "".to_string(),
+    ))
+}
+
+// Redirects are followed here rather than by the client, so that every response gets recorded
+// and headers get sent only to domains they're meant for
+fn fetch_remote_asset(
+    client: &Client,
+    request: &AssetRequest,
+    options: &Options,
+) -> Result<FetchedAsset, AssetError> {
+    let mut redirects: Vec<FetchedAsset> = vec![];
+    let mut url: Url = request.url.clone();
+
+    loop {
+        let mut asset: FetchedAsset = fetch_remote_response(client, request, &url, options)?;
+
+        match asset.redirect_location() {
+            // Too many redirects leave the last one to be dealt with as a failed request
+            Some(next_url) if redirects.len() < MAX_REDIRECTS => {
+                url = next_url;
+                redirects.push(asset);
+            }
+            _ => {
+                asset.redirects = redirects;
+                return Ok(asset);
+            }
+        }
+    }
+}
+
+// Makes a single request for the asset, which may have been redirected to url
+fn fetch_remote_response(
+    client: &Client,
+    request: &AssetRequest,
+    url: &Url,
+    options: &Options,
+) -> Result<FetchedAsset, AssetError> {
+    let mut http_request = client.get(url.as_str());
+    // Ask the server to only send the asset if it has changed since it was saved
+    if let Some(etag) = request.etag {
+        http_request = http_request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = request.last_modified {
+        http_request = http_request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+    // Redirects may lead to other domains, which is why it's decided for every request
+    let headers: Vec<&RequestHeader> = options
+        .headers
+        .iter()
+        .filter(|header| header.applies_to(url))
+        .collect();
+    // Headers specified by the user take precedence
+    let is_overridden = |name: &HeaderName| headers.iter().any(|header| header.name == name);
+    // Targets are requested on their own, not by some other document
+    if request.parent_url != request.url {
+        let referrer_policy: ReferrerPolicy = options
+            .referrer_policy
+            .as_deref()
+            .and_then(ReferrerPolicy::parse)
+            .unwrap_or_default();
+        if let Some(referrer) = referrer_policy.referrer(request.parent_url, url) {
+            if !is_overridden(&REFERER) {
+                http_request = http_request.header(REFERER, referrer);
+            }
+        }
+        if is_cors_request(request.parent_url, url) && !is_overridden(&ORIGIN) {
+            http_request =
+                http_request.header(ORIGIN, request.parent_url.origin().ascii_serialization());
+        }
+    }
+    for header in headers {
+        http_request = http_request.header(header.name.clone(), header.value.clone());
+    }
+
+    let http_request = http_request.build()?;
+    let request_http_version: String = format!("{:?}", http_request.version());
+    let mut host: String = url.host_str().unwrap_or_default().to_string();
+    if let Some(port) = url.port() {
+        host = format!("{}:{}", host, port);
+    }
+    let mut request_headers: Vec<(String, String)> = vec![(HOST.to_string(), host)];
+    if let Some(user_agent) = &options.user_agent {
+        request_headers.push((USER_AGENT.to_string(), user_agent.clone()));
+    }
+    request_headers.extend(header_pairs(http_request.headers()));
+    // Cookies get attached by the client itself, this is only needed to record them
+    if let Some(cookie_header) = options.__cookies.cookie_header(url) {
+        request_headers.push((COOKIE.to_string(), cookie_header));
+    }
+
+    let started: Instant = Instant::now();
+    let response = client.execute(http_request)?;
+    let wait: Duration = started.elapsed();
+
+    let response_header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|header| header.to_str().ok())
+            .map(|value| value.to_string())
+    };
+
+    // Attempt to obtain media type and charset by reading Content-Type header
+    let content_type: String = response_header(CONTENT_TYPE).unwrap_or_default();
+    let (media_type, charset, _is_base64) = parse_content_type(&content_type);
+
+    // Remember validators and freshness, they're needed for caching assets on disk
+    let etag: Option<String> = response_header(ETAG);
+    let last_modified: Option<String> = response_header(LAST_MODIFIED);
+    let cache_control: Option<String> = response_header(CACHE_CONTROL);
+    let expires: Option<String> = response_header(EXPIRES);
+
+    let status: u16 = response.status().as_u16();
+    let final_url: Url = response.url().clone();
+    let mut http: HttpExchange = HttpExchange {
+        method: "GET".to_string(),
+        request_http_version,
+        request_headers,
+        http_version: format!("{:?}", response.version()),
+        response_headers: header_pairs(response.headers()),
+        wait,
+        receive: Duration::ZERO,
+    };
+
+    // Convert response into a byte array
+    let data: Vec<u8> = response.bytes()?.to_vec();
+    http.receive = started.elapsed() - wait;
+
+    Ok(FetchedAsset {
+        data,
+        final_url,
+        media_type,
+        charset,
+        status,
+        etag,
+        last_modified,
+        cache_control,
+        expires,
+        http: Some(http),
+        redirects: vec![],
+    })
+}
+
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect()
+}
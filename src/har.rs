@@ -0,0 +1,248 @@
+use chrono::prelude::*;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::fs;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+use crate::fetcher::{AssetFetcher, AssetRequest, FetchedAsset};
+use crate::opts::Options;
+use crate::utils::AssetError;
+
+const HAR_VERSION: &str = "1.2";
+
+// Entries of HTTP Archive (HAR) log, written into a file once all targets are saved
+#[derive(Default)]
+pub struct HarLog {
+    entries: Mutex<Vec<(DateTime<Utc>, Value)>>,
+}
+
+impl HarLog {
+    pub fn new() -> HarLog {
+        HarLog::default()
+    }
+
+    pub fn add_entry(&self, started: DateTime<Utc>, entry: Value) {
+        self.entries.lock().unwrap().push((started, entry));
+    }
+
+    pub fn to_json(&self) -> Value {
+        // Assets retrieved in parallel get added in the order their requests finished
+        let mut entries: Vec<(DateTime<Utc>, Value)> = self.entries.lock().unwrap().clone();
+        entries.sort_by_key(|(started, _)| *started);
+
+        json!({
+            "log": {
+                "version": HAR_VERSION,
+                "creator": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "pages": [],
+                "entries": entries
+                    .into_iter()
+                    .map(|(_, entry)| entry)
+                    .collect::<Vec<Value>>(),
+            },
+        })
+    }
+
+    pub fn write(&self, file_path: &str) -> io::Result<()> {
+        fs::write(
+            file_path,
+            serde_json::to_string_pretty(&self.to_json()).unwrap(),
+        )
+    }
+}
+
+// Adds every HTTP(S) request made by the wrapped fetcher to the HAR log, including failed ones
+pub struct HarRecorder {
+    fetcher: Box<dyn AssetFetcher>,
+    log: Arc<HarLog>,
+}
+
+impl HarRecorder {
+    pub fn new(fetcher: Box<dyn AssetFetcher>, log: Arc<HarLog>) -> HarRecorder {
+        HarRecorder { fetcher, log }
+    }
+}
+
+impl AssetFetcher for HarRecorder {
+    fn fetch(&self, request: &AssetRequest, options: &Options) -> Result<FetchedAsset, AssetError> {
+        // Data URLs and local files don't involve any network activity
+        if request.url.scheme() != "http" && request.url.scheme() != "https" {
+            return self.fetcher.fetch(request, options);
+        }
+
+        let started: DateTime<Utc> = Utc::now();
+        let timer: Instant = Instant::now();
+        let result: Result<FetchedAsset, AssetError> = self.fetcher.fetch(request, options);
+        let elapsed: Duration = timer.elapsed();
+
+        match &result {
+            Ok(asset) => {
+                // Every redirect followed gets its own entry, they're logged one after another
+                let mut hop_started: DateTime<Utc> = started;
+                let mut hops_time: Duration = Duration::ZERO;
+                for hop in &asset.redirects {
+                    let time: Duration = hop
+                        .http
+                        .as_ref()
+                        .map(|http| http.wait + http.receive)
+                        .unwrap_or_default();
+                    self.log.add_entry(
+                        hop_started,
+                        create_har_entry(&hop.final_url, hop_started, time, Ok(hop)),
+                    );
+                    hop_started += chrono::Duration::from_std(time).unwrap();
+                    hops_time += time;
+                }
+                self.log.add_entry(
+                    hop_started,
+                    create_har_entry(
+                        &asset.final_url,
+                        hop_started,
+                        elapsed.saturating_sub(hops_time),
+                        Ok(asset),
+                    ),
+                );
+            }
+            Err(error) => self.log.add_entry(
+                started,
+                create_har_entry(request.url, started, elapsed, Err(error)),
+            ),
+        }
+
+        result
+    }
+}
+
+pub fn create_har_entry(
+    url: &Url,
+    started: DateTime<Utc>,
+    time: Duration,
+    result: Result<&FetchedAsset, &AssetError>,
+) -> Value {
+    let query_string: Vec<Value> = url
+        .query_pairs()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect();
+    let mut request: Value = json!({
+        "method": "GET",
+        "url": url.as_str(),
+        "httpVersion": "",
+        "cookies": [],
+        "headers": [],
+        "queryString": query_string,
+        "headersSize": -1,
+        "bodySize": 0,
+    });
+    // DNS lookups and connecting are not measured separately, they are part of "wait"
+    let (wait, receive) = match result {
+        Ok(FetchedAsset {
+            http: Some(http), ..
+        }) => (http.wait, http.receive),
+        _ => (time, Duration::ZERO),
+    };
+    let mut entry: Value = json!({
+        "startedDateTime": started.to_rfc3339_opts(SecondsFormat::Millis, true),
+        "time": to_milliseconds(time),
+        "request": {},
+        "response": {},
+        "cache": {},
+        "timings": {
+            "blocked": to_milliseconds(time.saturating_sub(wait + receive)),
+            "dns": -1,
+            "connect": -1,
+            "send": 0,
+            "wait": to_milliseconds(wait),
+            "receive": to_milliseconds(receive),
+            "ssl": -1,
+        },
+    });
+
+    entry["response"] = match result {
+        Ok(asset) => {
+            let (http_version, response_headers) = match &asset.http {
+                Some(http) => {
+                    request["httpVersion"] = json!(http.request_http_version);
+                    request["headers"] = create_har_headers(&http.request_headers);
+                    (
+                        http.http_version.clone(),
+                        create_har_headers(&http.response_headers),
+                    )
+                }
+                None => ("".to_string(), json!([])),
+            };
+            // Content-Type as sent by the server, charset of parsed one may be a default
+            let mime_type: String = asset
+                .http
+                .as_ref()
+                .and_then(|http| {
+                    http.response_headers
+                        .iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+                })
+                .map(|(_, value)| value.clone())
+                .unwrap_or_else(|| asset.media_type.clone());
+            // Only redirect responses point somewhere else
+            let redirect_url: String = asset
+                .redirect_location()
+                .map(|location| location.to_string())
+                .unwrap_or_default();
+
+            json!({
+                "status": asset.status,
+                "statusText": StatusCode::from_u16(asset.status)
+                    .ok()
+                    .and_then(|status| status.canonical_reason())
+                    .unwrap_or_default(),
+                "httpVersion": http_version,
+                "cookies": [],
+                "headers": response_headers,
+                "content": {
+                    "size": asset.data.len(),
+                    "mimeType": mime_type,
+                },
+                "redirectURL": redirect_url,
+                "headersSize": -1,
+                "bodySize": asset.data.len(),
+            })
+        }
+        // No response was received, which HAR viewers display as status 0
+        Err(error) => {
+            entry["_error"] = json!(error.to_string());
+
+            json!({
+                "status": 0,
+                "statusText": "",
+                "httpVersion": "",
+                "cookies": [],
+                "headers": [],
+                "content": {
+                    "size": 0,
+                    "mimeType": "x-unknown",
+                },
+                "redirectURL": "",
+                "headersSize": -1,
+                "bodySize": -1,
+            })
+        }
+    };
+    entry["request"] = request;
+
+    entry
+}
+
+fn create_har_headers(headers: &[(String, String)]) -> Value {
+    headers
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+fn to_milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -9,19 +9,24 @@ use html5ever::tendril::{format_tendril, TendrilSink};
 use html5ever::tree_builder::{Attribute, TreeSink};
 use html5ever::{local_name, namespace_url, ns, LocalName};
 use regex::Regex;
-use reqwest::blocking::Client;
 use reqwest::Url;
 use sha2::{Digest, Sha256, Sha384, Sha512};
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::default::Default;
 
-use crate::css::embed_css;
+use crate::css::{collect_css_urls, embed_css};
+use crate::fetcher::AssetFetcher;
 use crate::js::attr_is_event_handler;
-use crate::opts::Options;
+use crate::opts::{Options, OutputFormat};
+use crate::report::{record_asset, AssetRecord};
+use crate::session::Session;
 use crate::url::{
-    clean_url, create_data_url, is_url_and_has_protocol, resolve_url, EMPTY_IMAGE_DATA_URL,
+    clean_url, embed_asset, is_url_and_has_protocol, resolve_url, EMPTY_IMAGE_DATA_URL,
+};
+use crate::utils::{
+    parse_content_type, record_failed_asset, report_failure, retrieve_asset, retrieve_assets,
+    AssetError,
 };
-use crate::utils::{parse_content_type, retrieve_asset};
 
 struct SrcSetItem<'a> {
     path: &'a str,
@@ -79,6 +84,55 @@ pub fn check_integrity(data: &[u8], integrity: &str) -> bool {
     }
 }
 
+// Finds assets the document refers to the same way walk_and_embed_assets() does, without
+// retrieving them
+pub fn collect_asset_urls(
+    fetcher: &dyn AssetFetcher,
+    document_url: &Url,
+    node: &Handle,
+    options: &Options,
+) -> Vec<Url> {
+    // Walking through the document alters it, assets get looked for in a copy of it
+    let mut data: Vec<u8> = Vec::new();
+    serialize(&mut data, node, SerializeOpts::default()).unwrap();
+    let dom: RcDom = html_to_dom(&data, "".to_string());
+
+    let mut session: Session = Session::collecting();
+    walk_and_embed_assets(
+        &mut session,
+        fetcher,
+        document_url,
+        &dom.document,
+        options,
+        0,
+    );
+    session.collected_urls.unwrap_or_default()
+}
+
+// Collects URLs of pages the document links to
+pub fn collect_link_urls(document_url: &Url, node: &Handle) -> Vec<Url> {
+    let mut urls: Vec<Url> = vec![];
+    collect_node_link_urls(document_url, node, &mut urls);
+    urls
+}
+
+fn collect_node_link_urls(document_url: &Url, node: &Handle, urls: &mut Vec<Url>) {
+    if let NodeData::Element { ref name, .. } = node.data {
+        if name.local.as_ref() == "a" || name.local.as_ref() == "area" {
+            if let Some(anchor_attr_href_value) = get_node_attr(node, "href") {
+                let href: &str = anchor_attr_href_value.trim();
+                if !href.is_empty() && !href.starts_with('#') && !href.starts_with("javascript:") {
+                    urls.push(resolve_url(document_url, href));
+                }
+            }
+        }
+    }
+
+    for child_node in node.children.borrow().iter() {
+        collect_node_link_urls(document_url, child_node, urls);
+    }
+}
+
 pub fn compose_csp(options: &Options) -> String {
     let mut string_list = vec![];
 
@@ -156,9 +210,10 @@ pub fn determine_link_node_type(node: &Handle) -> &str {
 }
 
 pub fn embed_srcset(
-    cache: &mut HashMap<String, Vec<u8>>,
-    client: &Client,
+    session: &mut Session,
+    fetcher: &dyn AssetFetcher,
     document_url: &Url,
+    node_name: &str,
     srcset: &str,
     options: &Options,
     depth: u32,
@@ -182,26 +237,50 @@ pub fn embed_srcset(
             result.push_str(EMPTY_IMAGE_DATA_URL);
         } else {
             let image_full_url: Url = resolve_url(&document_url, part.path);
+            let record: AssetRecord = AssetRecord::new(
+                Some(node_name),
+                "srcset",
+                part.path,
+                document_url,
+                &image_full_url,
+            );
             match retrieve_asset(
-                cache,
-                client,
+                session,
+                fetcher,
                 &document_url,
                 &image_full_url,
                 options,
                 depth + 1,
             ) {
                 Ok((image_data, image_final_url, image_media_type, image_charset)) => {
-                    let mut image_data_url = create_data_url(
+                    record_asset(
+                        session,
+                        options,
+                        record.succeeded(
+                            &image_final_url,
+                            &image_media_type,
+                            image_data.len(),
+                            session,
+                        ),
+                    );
+
+                    // Append retreved asset as a data URL
+                    let image_data_url: String = embed_asset(
+                        session,
                         &image_media_type,
                         &image_charset,
                         &image_data,
                         &image_final_url,
+                        image_full_url.fragment(),
+                        options,
+                        depth,
                     );
-                    // Append retreved asset as a data URL
-                    image_data_url.set_fragment(image_full_url.fragment());
-                    result.push_str(image_data_url.as_ref());
+                    result.push_str(&image_data_url);
                 }
-                Err(_) => {
+                Err(error) => {
+                    record_failed_asset(session, &image_full_url, &error);
+                    record_asset(session, options, record.failed(&error));
+
                     // Keep remote reference if unable to retrieve the asset
                     if image_full_url.scheme() == "http" || image_full_url.scheme() == "https" {
                         result.push_str(image_full_url.as_ref());
@@ -301,6 +380,36 @@ pub fn find_meta_charset_or_content_type_node(node: &Handle) -> Option<Handle> {
     None
 }
 
+// Refers to the local copy of the linked page, if it's one of the pages saved during this run
+fn find_saved_page(
+    session: &Session,
+    document_url: &Url,
+    href: &str,
+    options: &Options,
+    depth: u32,
+) -> Option<String> {
+    if href.trim().starts_with('#') {
+        return None;
+    }
+
+    let page_url: Url = resolve_url(document_url, href.trim());
+    let file_name: &String = session
+        .page_files
+        .get(clean_url(page_url.clone()).as_str())?;
+
+    // Documents of frames end up in the assets directory, one level below saved pages
+    let mut reference: String = if options.output_format == OutputFormat::Dir && depth > 0 {
+        format!("../{}", file_name)
+    } else {
+        file_name.clone()
+    };
+    if let Some(fragment) = page_url.fragment() {
+        reference = format!("{}#{}", reference, fragment);
+    }
+
+    Some(reference)
+}
+
 pub fn get_base_url(handle: &Handle) -> Option<String> {
     if let Some(base_node) = find_base_node(handle) {
         get_node_attr(&base_node, "href")
@@ -591,21 +700,84 @@ pub fn serialize_document(mut dom: RcDom, document_encoding: String, options: &O
     buf
 }
 
+pub fn prefetch_assets(
+    session: &mut Session,
+    fetcher: &dyn AssetFetcher,
+    document_url: &Url,
+    document: &Handle,
+    options: &Options,
+) {
+    let mut assets: Vec<(Url, Url)> = collect_asset_urls(fetcher, document_url, document, options)
+        .into_iter()
+        .map(|url| (document_url.clone(), url))
+        .collect();
+    let mut seen_cache_keys: HashSet<String> = HashSet::new();
+
+    while !assets.is_empty() {
+        // Never attempt to retrieve the same asset twice
+        assets.retain(|(_, url)| seen_cache_keys.insert(clean_url(url.clone()).to_string()));
+
+        retrieve_assets(session, fetcher, &assets, options, 1);
+
+        // Stylesheets and frames may refer to further assets
+        let mut nested_assets: Vec<(Url, Url)> = vec![];
+        for (_, url) in assets {
+            let cache_key: String = clean_url(url).as_str().to_string();
+            if let Some(cache_entry) = session.cache.get(&cache_key) {
+                let media_type: &str = &cache_entry.media_type;
+                let nested_urls: Vec<Url> = if media_type.eq_ignore_ascii_case("text/css") {
+                    collect_css_urls(
+                        fetcher,
+                        &cache_entry.final_url,
+                        &String::from_utf8_lossy(&cache_entry.data),
+                        options,
+                    )
+                } else if media_type.eq_ignore_ascii_case("text/html")
+                    || media_type.eq_ignore_ascii_case("application/xhtml+xml")
+                {
+                    let frame_dom = html_to_dom(&cache_entry.data, cache_entry.charset.clone());
+                    collect_asset_urls(
+                        fetcher,
+                        &cache_entry.final_url,
+                        &frame_dom.document,
+                        options,
+                    )
+                } else {
+                    vec![]
+                };
+
+                for nested_url in nested_urls {
+                    nested_assets.push((cache_entry.final_url.clone(), nested_url));
+                }
+            }
+        }
+
+        assets = nested_assets;
+    }
+}
+
 pub fn retrieve_and_embed_asset(
-    cache: &mut HashMap<String, Vec<u8>>,
-    client: &Client,
+    session: &mut Session,
+    fetcher: &dyn AssetFetcher,
     document_url: &Url,
     node: &Handle,
     attr_name: &str,
     attr_value: &str,
     options: &Options,
     depth: u32,
-) {
+) -> Result<(), AssetError> {
     let resolved_url: Url = resolve_url(document_url, attr_value.clone());
+    let mut record: AssetRecord = AssetRecord::new(
+        get_node_name(node),
+        attr_name,
+        attr_value,
+        document_url,
+        &resolved_url,
+    );
 
     match retrieve_asset(
-        cache,
-        client,
+        session,
+        fetcher,
         &document_url.clone(),
         &resolved_url,
         options,
@@ -613,14 +785,20 @@ pub fn retrieve_and_embed_asset(
     ) {
         Ok((data, final_url, mut media_type, charset)) => {
             let node_name: &str = get_node_name(&node).unwrap();
+            record = record.succeeded(&final_url, &media_type, data.len(), session);
 
             // Check integrity if it's a LINK or SCRIPT element
-            let mut ok_to_include: bool = true;
+            let mut embedded: Result<(), AssetError> = Ok(());
             if node_name == "link" || node_name == "script" {
                 // Check integrity
                 if let Some(node_integrity_attr_value) = get_node_attr(node, "integrity") {
                     if !node_integrity_attr_value.is_empty() {
-                        ok_to_include = check_integrity(&data, &node_integrity_attr_value);
+                        let ok_to_include: bool =
+                            check_integrity(&data, &node_integrity_attr_value);
+                        record.integrity = Some(ok_to_include);
+                        if !ok_to_include {
+                            embedded = Err(AssetError::Integrity);
+                        }
                     }
 
                     // Wipe the integrity attribute
@@ -628,7 +806,7 @@ pub fn retrieve_and_embed_asset(
                 }
             }
 
-            if ok_to_include {
+            if embedded.is_ok() {
                 let s: String;
                 if let Some(encoding) = Encoding::for_label(charset.as_bytes()) {
                     let (string, _, _) = encoding.decode(&data);
@@ -639,18 +817,31 @@ pub fn retrieve_and_embed_asset(
 
                 if node_name == "link" && determine_link_node_type(node) == "stylesheet" {
                     // Stylesheet LINK elements require special treatment
-                    let css: String = embed_css(cache, client, &final_url, &s, options, depth + 1);
-
-                    // Create and embed data URL
-                    let css_data_url =
-                        create_data_url(&media_type, &charset, css.as_bytes(), &final_url);
-                    set_node_attr(&node, attr_name, Some(css_data_url.to_string()));
+                    match embed_css(session, fetcher, &final_url, &s, options, depth + 1) {
+                        Ok(css) => {
+                            // Create and embed data URL
+                            let css_data_url: String = embed_asset(
+                                session,
+                                &media_type,
+                                &charset,
+                                css.as_bytes(),
+                                &final_url,
+                                None,
+                                options,
+                                depth,
+                            );
+                            set_node_attr(&node, attr_name, Some(css_data_url));
+                        }
+                        Err(error) => {
+                            embedded = Err(error);
+                        }
+                    }
                 } else if node_name == "frame" || node_name == "iframe" {
                     // (I)FRAMEs are also quite different from conventional resources
                     let frame_dom = html_to_dom(&data, charset.clone());
                     walk_and_embed_assets(
-                        cache,
-                        client,
+                        session,
+                        fetcher,
                         &final_url,
                         &frame_dom.document,
                         &options,
@@ -666,10 +857,17 @@ pub fn retrieve_and_embed_asset(
                     .unwrap();
 
                     // Create and embed data URL
-                    let mut frame_data_url =
-                        create_data_url(&media_type, &charset, &frame_data, &final_url);
-                    frame_data_url.set_fragment(resolved_url.fragment());
-                    set_node_attr(node, attr_name, Some(frame_data_url.to_string()));
+                    let frame_data_url: String = embed_asset(
+                        session,
+                        &media_type,
+                        &charset,
+                        &frame_data,
+                        &final_url,
+                        resolved_url.fragment(),
+                        options,
+                        depth,
+                    );
+                    set_node_attr(node, attr_name, Some(frame_data_url));
                 } else {
                     // Every other type of element gets processed here
 
@@ -686,13 +884,38 @@ pub fn retrieve_and_embed_asset(
                     }
 
                     // Create and embed data URL
-                    let mut data_url = create_data_url(&media_type, &charset, &data, &final_url);
-                    data_url.set_fragment(resolved_url.fragment());
-                    set_node_attr(node, attr_name, Some(data_url.to_string()));
+                    let data_url: String = embed_asset(
+                        session,
+                        &media_type,
+                        &charset,
+                        &data,
+                        &final_url,
+                        resolved_url.fragment(),
+                        options,
+                        depth,
+                    );
+                    set_node_attr(node, attr_name, Some(data_url));
+                }
+            }
+
+            match embedded {
+                Ok(()) => {
+                    record_asset(session, options, record);
+                    Ok(())
+                }
+                Err(error) => {
+                    let error: AssetError =
+                        report_failure(session, &resolved_url, error, options, depth + 1);
+                    record_failed_asset(session, &resolved_url, &error);
+                    record_asset(session, options, record.failed(&error));
+                    Err(error)
                 }
             }
         }
-        Err(_) => {
+        Err(error) => {
+            record_failed_asset(session, &resolved_url, &error);
+            record_asset(session, options, record.failed(&error));
+
             if resolved_url.scheme() == "http" || resolved_url.scheme() == "https" {
                 // Keep remote references if unable to retrieve the asset
                 set_node_attr(node, attr_name, Some(resolved_url.to_string()));
@@ -700,13 +923,15 @@ pub fn retrieve_and_embed_asset(
                 // Remove local references if they can't be successfully embedded as data URLs
                 set_node_attr(node, attr_name, None);
             }
+
+            Err(error)
         }
     }
 }
 
 pub fn walk_and_embed_assets(
-    cache: &mut HashMap<String, Vec<u8>>,
-    client: &Client,
+    session: &mut Session,
+    fetcher: &dyn AssetFetcher,
     document_url: &Url,
     node: &Handle,
     options: &Options,
@@ -716,7 +941,7 @@ pub fn walk_and_embed_assets(
         NodeData::Document => {
             // Dig deeper
             for child in node.children.borrow().iter() {
-                walk_and_embed_assets(cache, client, &document_url, child, options, depth);
+                walk_and_embed_assets(session, fetcher, &document_url, child, options, depth);
             }
         }
         NodeData::Element {
@@ -743,9 +968,9 @@ pub fn walk_and_embed_assets(
                         // Find and resolve LINK's href attribute
                         if let Some(link_attr_href_value) = get_node_attr(node, "href") {
                             if !options.no_images && !link_attr_href_value.is_empty() {
-                                retrieve_and_embed_asset(
-                                    cache,
-                                    client,
+                                let _ = retrieve_and_embed_asset(
+                                    session,
+                                    fetcher,
                                     &document_url,
                                     node,
                                     "href",
@@ -766,9 +991,9 @@ pub fn walk_and_embed_assets(
                                 set_node_attr(node, "integrity", None);
                             } else {
                                 if !link_attr_href_value.is_empty() {
-                                    retrieve_and_embed_asset(
-                                        cache,
-                                        client,
+                                    let _ = retrieve_and_embed_asset(
+                                        session,
+                                        fetcher,
                                         &document_url,
                                         node,
                                         "href",
@@ -792,7 +1017,10 @@ pub fn walk_and_embed_assets(
                     }
                 }
                 "base" => {
-                    if document_url.scheme() == "http" || document_url.scheme() == "https" {
+                    if options.output_format == OutputFormat::Dir {
+                        // Relative paths to saved assets must not get resolved against it
+                        set_node_attr(node, "href", None);
+                    } else if document_url.scheme() == "http" || document_url.scheme() == "https" {
                         // Ensure the BASE node doesn't have a relative URL
                         if let Some(base_attr_href_value) = get_node_attr(node, "href") {
                             let href_full_url: Url =
@@ -808,9 +1036,9 @@ pub fn walk_and_embed_assets(
                         set_node_attr(node, "background", None);
 
                         if !options.no_images && !body_attr_background_value.is_empty() {
-                            retrieve_and_embed_asset(
-                                cache,
-                                client,
+                            let _ = retrieve_and_embed_asset(
+                                session,
+                                fetcher,
                                 document_url,
                                 node,
                                 "background",
@@ -854,9 +1082,9 @@ pub fn walk_and_embed_assets(
                             } else {
                                 img_attr_src_value.unwrap_or_default()
                             };
-                            retrieve_and_embed_asset(
-                                cache,
-                                client,
+                            let _ = retrieve_and_embed_asset(
+                                session,
+                                fetcher,
                                 document_url,
                                 node,
                                 "src",
@@ -871,9 +1099,10 @@ pub fn walk_and_embed_assets(
                     if let Some(img_srcset) = get_node_attr(node, "srcset") {
                         if !img_srcset.is_empty() {
                             let resolved_srcset: String = embed_srcset(
-                                cache,
-                                client,
+                                session,
+                                fetcher,
                                 &document_url,
+                                "img",
                                 &img_srcset,
                                 options,
                                 depth,
@@ -899,9 +1128,9 @@ pub fn walk_and_embed_assets(
                                     };
                                     set_node_attr(node, "src", Some(value.to_string()));
                                 } else {
-                                    retrieve_and_embed_asset(
-                                        cache,
-                                        client,
+                                    let _ = retrieve_and_embed_asset(
+                                        session,
+                                        fetcher,
                                         document_url,
                                         node,
                                         "src",
@@ -932,9 +1161,9 @@ pub fn walk_and_embed_assets(
                     }
 
                     if !options.no_images && !image_href.is_empty() {
-                        retrieve_and_embed_asset(
-                            cache,
-                            client,
+                        let _ = retrieve_and_embed_asset(
+                            session,
+                            fetcher,
                             document_url,
                             node,
                             "href",
@@ -953,9 +1182,9 @@ pub fn walk_and_embed_assets(
                             if options.no_audio {
                                 set_node_attr(node, "src", None);
                             } else {
-                                retrieve_and_embed_asset(
-                                    cache,
-                                    client,
+                                let _ = retrieve_and_embed_asset(
+                                    session,
+                                    fetcher,
                                     document_url,
                                     node,
                                     "src",
@@ -968,9 +1197,9 @@ pub fn walk_and_embed_assets(
                             if options.no_video {
                                 set_node_attr(node, "src", None);
                             } else {
-                                retrieve_and_embed_asset(
-                                    cache,
-                                    client,
+                                let _ = retrieve_and_embed_asset(
+                                    session,
+                                    fetcher,
                                     document_url,
                                     node,
                                     "src",
@@ -993,9 +1222,10 @@ pub fn walk_and_embed_assets(
                                     );
                                 } else {
                                     let resolved_srcset: String = embed_srcset(
-                                        cache,
-                                        client,
+                                        session,
+                                        fetcher,
                                         &document_url,
+                                        "source",
                                         &source_attr_srcset_value,
                                         options,
                                         depth,
@@ -1017,6 +1247,15 @@ pub fn walk_and_embed_assets(
                                 // Replace with empty JS call to preserve original behavior
                                 set_node_attr(node, "href", Some("javascript:;".to_string()));
                             }
+                        } else if let Some(saved_page_reference) = find_saved_page(
+                            session,
+                            document_url,
+                            &anchor_attr_href_value,
+                            options,
+                            depth,
+                        ) {
+                            // Point links between pages saved during this run to their local copies
+                            set_node_attr(node, "href", Some(saved_page_reference));
                         } else {
                             // Don't touch mailto: links or hrefs which begin with a hash sign
                             if !anchor_attr_href_value.clone().starts_with('#')
@@ -1043,9 +1282,9 @@ pub fn walk_and_embed_assets(
                             set_node_attr(node, "integrity", None);
                         }
                     } else if !script_attr_src.clone().unwrap_or_default().is_empty() {
-                        retrieve_and_embed_asset(
-                            cache,
-                            client,
+                        let _ = retrieve_and_embed_asset(
+                            session,
+                            fetcher,
                             document_url,
                             node,
                             "src",
@@ -1063,16 +1302,18 @@ pub fn walk_and_embed_assets(
                         for child_node in node.children.borrow_mut().iter_mut() {
                             if let NodeData::Text { ref contents } = child_node.data {
                                 let mut tendril = contents.borrow_mut();
-                                let replacement = embed_css(
-                                    cache,
-                                    client,
+                                // Keep stylesheets which could not be processed as they are
+                                if let Ok(replacement) = embed_css(
+                                    session,
+                                    fetcher,
                                     &document_url,
                                     tendril.as_ref(),
                                     options,
                                     depth,
-                                );
-                                tendril.clear();
-                                tendril.push_slice(&replacement);
+                                ) {
+                                    tendril.clear();
+                                    tendril.push_slice(&replacement);
+                                }
                             }
                         }
                     }
@@ -1093,9 +1334,9 @@ pub fn walk_and_embed_assets(
                         } else {
                             // Ignore (i)frames with empty source (they cause infinite loops)
                             if !frame_attr_src_value.trim().is_empty() {
-                                retrieve_and_embed_asset(
-                                    cache,
-                                    client,
+                                let _ = retrieve_and_embed_asset(
+                                    session,
+                                    fetcher,
                                     &document_url,
                                     node,
                                     "src",
@@ -1113,9 +1354,9 @@ pub fn walk_and_embed_assets(
                         if options.no_audio {
                             set_node_attr(node, "src", None);
                         } else {
-                            retrieve_and_embed_asset(
-                                cache,
-                                client,
+                            let _ = retrieve_and_embed_asset(
+                                session,
+                                fetcher,
                                 document_url,
                                 node,
                                 "src",
@@ -1132,9 +1373,9 @@ pub fn walk_and_embed_assets(
                         if options.no_video {
                             set_node_attr(node, "src", None);
                         } else {
-                            retrieve_and_embed_asset(
-                                cache,
-                                client,
+                            let _ = retrieve_and_embed_asset(
+                                session,
+                                fetcher,
                                 document_url,
                                 node,
                                 "src",
@@ -1156,9 +1397,9 @@ pub fn walk_and_embed_assets(
                                     Some(EMPTY_IMAGE_DATA_URL.to_string()),
                                 );
                             } else {
-                                retrieve_and_embed_asset(
-                                    cache,
-                                    client,
+                                let _ = retrieve_and_embed_asset(
+                                    session,
+                                    fetcher,
                                     document_url,
                                     node,
                                     "poster",
@@ -1183,8 +1424,8 @@ pub fn walk_and_embed_assets(
                                 );
                                 // Embed assets of NOSCRIPT node contents
                                 walk_and_embed_assets(
-                                    cache,
-                                    client,
+                                    session,
+                                    fetcher,
                                     &document_url,
                                     &noscript_contents_dom.document,
                                     &options,
@@ -1219,15 +1460,16 @@ pub fn walk_and_embed_assets(
             } else {
                 // Embed URLs found within the style attribute of this node
                 if let Some(node_attr_style_value) = get_node_attr(node, "style") {
-                    let embedded_style = embed_css(
-                        cache,
-                        client,
+                    if let Ok(embedded_style) = embed_css(
+                        session,
+                        fetcher,
                         &document_url,
                         &node_attr_style_value,
                         options,
                         depth,
-                    );
-                    set_node_attr(node, "style", Some(embedded_style));
+                    ) {
+                        set_node_attr(node, "style", Some(embedded_style));
+                    }
                 }
             }
 
@@ -1249,7 +1491,7 @@ pub fn walk_and_embed_assets(
 
             // Dig deeper
             for child in node.children.borrow().iter() {
-                walk_and_embed_assets(cache, client, &document_url, child, options, depth);
+                walk_and_embed_assets(session, fetcher, &document_url, child, options, depth);
             }
         }
         _ => {
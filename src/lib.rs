@@ -1,7 +1,20 @@
+pub mod batch;
+pub mod cache;
 pub mod cookies;
+pub mod core;
 pub mod css;
+pub mod directory;
+pub mod fetcher;
+pub mod har;
 pub mod html;
 pub mod js;
+pub mod mhtml;
 pub mod opts;
+pub mod proxy;
+pub mod report;
+pub mod reporter;
+pub mod session;
+pub mod tls;
 pub mod url;
 pub mod utils;
+pub mod warc;
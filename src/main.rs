@@ -1,23 +1,39 @@
-use encoding_rs::Encoding;
-use html5ever::rcdom::RcDom;
-use reqwest::blocking::Client;
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
-use std::collections::HashMap;
+use chrono::prelude::*;
+use serde_json::Value;
+use std::collections::HashSet;
 use std::fs;
 use std::io::{self, prelude::*, Error, Write};
 use std::path::Path;
 use std::process;
-use std::time::Duration;
+use std::sync::Arc;
 use url::Url;
 
-use monolith::cookies::parse_cookies;
-use monolith::html::{
-    add_favicon, create_metadata_tag, get_base_url, get_charset, has_favicon, html_to_dom,
-    serialize_document, set_base_url, set_charset, walk_and_embed_assets,
+use monolith::batch::{
+    collect_targets, create_output_path, is_batch_input, BatchTarget, DEFAULT_OUTPUT_TEMPLATE,
 };
-use monolith::opts::Options;
-use monolith::url::{create_data_url, resolve_url};
-use monolith::utils::retrieve_asset;
+use monolith::cookies::{
+    detect_cookie_format, parse_cookies, parse_json_cookies, read_sqlite_cookies, CookieFormat,
+    CookieJar,
+};
+use monolith::core::{
+    create_http_client, resolve_target, Monolith, MonolithError, SavedDocument, SavedPage,
+};
+use monolith::directory::{save_to_directory, INDEX_FILE_NAME};
+use monolith::fetcher::AssetFetcher;
+use monolith::har::{HarLog, HarRecorder};
+use monolith::opts::{Options, OutputFormat};
+use monolith::report::{create_failed_target_report, create_page_report, create_report};
+use monolith::reporter::{create_reporter, Event, Reporter};
+use monolith::utils::AssetError;
+use monolith::warc::{WarcRecorder, WarcReplay, WarcWriter};
+
+// Exit codes (listed in README), scripts rely on them staying the same
+const EXIT_CODE_OTHER_ERROR: i32 = 1;
+const EXIT_CODE_USAGE_ERROR: i32 = 2;
+const EXIT_CODE_TARGET_UNREACHABLE: i32 = 3;
+const EXIT_CODE_TARGET_HTTP_ERROR: i32 = 4;
+const EXIT_CODE_ASSET_FAILURE: i32 = 5;
+const EXIT_CODE_WRITE_ERROR: i32 = 6;
 
 enum Output {
     Stdout(io::Stdout),
@@ -68,290 +84,492 @@ fn main() {
     let mut options = Options::from_args();
 
     // Check if target was provided
-    if options.target.len() == 0 {
+    if options.target.len() == 0 && options.input_file.is_none() {
         if !options.silent {
             eprintln!("No target specified");
         }
-        process::exit(1);
+        process::exit(EXIT_CODE_USAGE_ERROR);
+    }
+
+    // Directories can't be written into STDOUT
+    if options.output_format == OutputFormat::Dir
+        && options.input_file.is_none()
+        && (options.output.is_empty() || options.output == "-")
+    {
+        if !options.silent {
+            eprintln!("Output directory must be specified using -o when saving as dir");
+        }
+        process::exit(EXIT_CODE_USAGE_ERROR);
     }
 
-    // Check if custom charset is valid
-    if let Some(custom_charset) = options.charset.clone() {
-        if !Encoding::for_label_no_replacement(custom_charset.as_bytes()).is_some() {
-            eprintln!("Unknown encoding: {}", &custom_charset);
-            process::exit(1);
+    // Linked pages get saved next to the target's file
+    if options.crawl_depth > 0
+        && options.target != "-"
+        && options.input_file.is_none()
+        && (options.output.is_empty() || options.output == "-")
+    {
+        if !options.silent {
+            eprintln!("Output file must be specified using -o when saving linked pages");
         }
+        process::exit(EXIT_CODE_USAGE_ERROR);
     }
 
     if let Some(ref path) = options.cookie_file {
-        match std::fs::read_to_string(path) {
-            Ok(ref contents) => match parse_cookies(contents) {
-                Ok(cookies) => {
-                    options.__cookies = cookies;
-                }
-                Err(_) => {
-                    eprintln!("Could not parse specified cookie file");
-                    process::exit(1);
+        match fs::read(path) {
+            Ok(ref data) => {
+                let result = match detect_cookie_format(data) {
+                    CookieFormat::Json => parse_json_cookies(&String::from_utf8_lossy(data)),
+                    CookieFormat::Netscape => parse_cookies(&String::from_utf8_lossy(data)),
+                    CookieFormat::Sqlite => read_sqlite_cookies(Path::new(path)),
+                };
+                match result {
+                    Ok(cookies) => {
+                        options.__cookies = Arc::new(CookieJar::new(cookies));
+                    }
+                    Err(error) => {
+                        eprintln!("Could not parse specified cookie file ({})", error);
+                        process::exit(EXIT_CODE_USAGE_ERROR);
+                    }
                 }
-            },
+            }
             Err(_) => {
                 eprintln!("Could not read specified cookie file");
-                process::exit(1);
+                process::exit(EXIT_CODE_USAGE_ERROR);
             }
         }
     }
 
-    let mut use_stdin: bool = false;
-
-    let target_url = match options.target.as_str() {
-        "-" => {
-            // Read from pipe (stdin)
-            use_stdin = true;
-            // Set default target URL to an empty data URL; the user can set it via --base-url
-            Url::parse("data:text/html,").unwrap()
-        }
-        target => match Url::parse(&target) {
-            Ok(url) => match url.scheme() {
-                "data" | "file" | "http" | "https" => url,
-                unsupported_scheme => {
-                    if !options.silent {
-                        eprintln!("Unsupported target URL type: {}", unsupported_scheme);
-                    }
-                    process::exit(1)
-                }
-            },
-            Err(_) => {
-                // Failed to parse given base URL (perhaps it's a filesystem path?)
-                let path: &Path = Path::new(&target);
-                match path.exists() {
-                    true => match path.is_file() {
-                        true => {
-                            let canonical_path = fs::canonicalize(&path).unwrap();
-                            match Url::from_file_path(canonical_path) {
-                                Ok(url) => url,
-                                Err(_) => {
-                                    if !options.silent {
-                                        eprintln!(
-                                            "Could not generate file URL out of given path: {}",
-                                            &target
-                                        );
-                                    }
-                                    process::exit(1);
-                                }
-                            }
-                        }
-                        false => {
-                            if !options.silent {
-                                eprintln!("Local target is not a file: {}", &target);
-                            }
-                            process::exit(1);
-                        }
-                    },
-                    false => {
-                        // It is not a FS path, now we do what browsers do:
-                        // prepend "http://" and hope it points to a website
-                        Url::parse(&format!("http://{hopefully_url}", hopefully_url = &target))
-                            .unwrap()
-                    }
-                }
+    let reporter: Option<Box<dyn Reporter>> = match &options.log_format {
+        Some(log_format) => match create_reporter(log_format, options.no_color) {
+            Some(reporter) => Some(reporter),
+            None => {
+                eprintln!("Unknown log format: {}", log_format);
+                process::exit(EXIT_CODE_USAGE_ERROR);
             }
         },
+        None => None,
     };
 
-    // Initialize client
-    let mut cache = HashMap::new();
-    let mut header_map = HeaderMap::new();
-    if let Some(user_agent) = &options.user_agent {
-        header_map.insert(
-            USER_AGENT,
-            HeaderValue::from_str(&user_agent).expect("Invalid User-Agent header specified"),
-        );
-    }
-    let client = if options.timeout > 0 {
-        Client::builder().timeout(Duration::from_secs(options.timeout))
-    } else {
-        // No timeout is default
-        Client::builder()
+    let input_file: Option<String> = options.input_file.clone();
+    let target: String = options.target.clone();
+    let output_path: String = options.output.clone();
+    let silent: bool = options.silent;
+    let strict: bool = options.strict;
+    let report_path: Option<String> = options.report.clone();
+    let mut page_reports: Vec<Value> = vec![];
+    let output_format: OutputFormat = options.output_format;
+    let is_crawling: bool = options.crawl_depth > 0 && target != "-";
+
+    // Replay previously recorded responses instead of accessing the network
+    let mut fetcher: Option<Box<dyn AssetFetcher>> = None;
+    if let Some(warc_path) = &options.from_warc {
+        match WarcReplay::open(warc_path) {
+            Ok(replay) => {
+                fetcher = Some(Box::new(replay));
+            }
+            Err(_) => {
+                eprintln!("Could not read WARC file");
+                process::exit(EXIT_CODE_USAGE_ERROR);
+            }
+        }
     }
-    .danger_accept_invalid_certs(options.insecure)
-    .default_headers(header_map)
-    .build()
-    .expect("Failed to initialize HTTP client");
-
-    // At first we assume that base URL is the same as target URL
-    let mut base_url: Url = target_url.clone();
-
-    let data: Vec<u8>;
-    let mut document_encoding: String = "".to_string();
-    let mut dom: RcDom;
-
-    // Retrieve target document
-    if use_stdin {
-        data = read_stdin();
-    } else if target_url.scheme() == "file"
-        || (target_url.scheme() == "http" || target_url.scheme() == "https")
-        || target_url.scheme() == "data"
-    {
-        match retrieve_asset(&mut cache, &client, &target_url, &target_url, &options, 0) {
-            Ok((retrieved_data, final_url, media_type, charset)) => {
-                // Provide output as text without processing it, the way browsers do
-                if !media_type.eq_ignore_ascii_case("text/html")
-                    && !media_type.eq_ignore_ascii_case("application/xhtml+xml")
-                {
-                    // Define output
-                    let mut output =
-                        Output::new(&options.output).expect("Could not prepare output");
-
-                    // Write retrieved data into STDOUT or file
-                    output
-                        .write(&retrieved_data)
-                        .expect("Could not write output");
-
-                    // Nothing else to do past this point
-                    process::exit(0);
-                }
-
-                if options
-                    .base_url
-                    .clone()
-                    .unwrap_or("".to_string())
-                    .is_empty()
-                {
-                    base_url = final_url;
-                }
 
-                data = retrieved_data;
-                document_encoding = charset;
+    // Record raw HTTP exchanges if asked to
+    let mut warc_writer: Option<Arc<WarcWriter>> = None;
+    if let Some(warc_path) = &options.warc {
+        match WarcWriter::create(warc_path) {
+            Ok(writer) => {
+                warc_writer = Some(Arc::new(writer));
             }
             Err(_) => {
-                if !options.silent {
-                    eprintln!("Could not retrieve target document");
-                }
-                process::exit(1);
+                eprintln!("Could not create WARC file");
+                process::exit(EXIT_CODE_WRITE_ERROR);
             }
         }
-    } else {
-        process::exit(1);
     }
 
-    // Initial parse
-    dom = html_to_dom(&data, document_encoding.clone());
+    if let Some(writer) = &warc_writer {
+        fetcher = Some(Box::new(WarcRecorder::new(
+            take_fetcher(&mut fetcher, &options),
+            writer.clone(),
+        )));
+    }
+
+    // Log requests made while saving, for HAR viewers
+    let har_path: Option<String> = options.har.clone();
+    let mut har_log: Option<Arc<HarLog>> = None;
+    if har_path.is_some() {
+        let log: Arc<HarLog> = Arc::new(HarLog::new());
+        fetcher = Some(Box::new(HarRecorder::new(
+            take_fetcher(&mut fetcher, &options),
+            log.clone(),
+        )));
+        har_log = Some(log);
+    }
 
-    // TODO: investigate if charset from filesystem/data URL/HTTP headers
-    //       has say over what's specified in HTML
+    let mut monolith = match fetcher {
+        Some(fetcher) => Monolith::with_fetcher(options, fetcher),
+        None => Monolith::new(options),
+    };
+    if let Some(reporter) = reporter {
+        monolith.set_reporter(reporter);
+    }
 
-    // Attempt to determine document's charset
-    if let Some(html_charset) = get_charset(&dom.document) {
-        if !html_charset.is_empty() {
-            // Check if the charset specified inside HTML is valid
-            if let Some(encoding) = Encoding::for_label_no_replacement(html_charset.as_bytes()) {
-                document_encoding = html_charset;
-                dom = html_to_dom(&data, encoding.name().to_string());
+    // Target itself can be bookmarks or a sitemap, those get saved the same way as --input-file
+    let mut target_error: Option<MonolithError> = None;
+    let batch_input: Option<Vec<u8>> = if let Some(input_file) = &input_file {
+        // List of targets can be a local file, STDIN, or a remote one (e.g. sitemap.xml)
+        match read_input(&mut monolith, input_file) {
+            Some(input_data) => Some(input_data),
+            None => {
+                eprintln!("Could not read input file");
+                process::exit(EXIT_CODE_USAGE_ERROR);
             }
         }
-    }
-
-    // Use custom base URL if specified, read and use what's in the DOM otherwise
-    let custom_base_url: String = options.base_url.clone().unwrap_or("".to_string());
-    if custom_base_url.is_empty() {
-        // No custom base URL is specified
-        // Try to see if document has BASE element
-        if let Some(existing_base_url) = get_base_url(&dom.document) {
-            base_url = resolve_url(&target_url, &existing_base_url);
+    } else if target != "-" {
+        // Document retrieved here gets reused when saving the target, it isn't requested twice
+        match read_target(&mut monolith, &target) {
+            Ok(Some(target_data)) if is_batch_input(&target_data) => Some(target_data),
+            Ok(_) => None,
+            Err(error) => {
+                target_error = Some(error);
+                None
+            }
         }
     } else {
-        // Custom base URL provided
-        match Url::parse(&custom_base_url) {
-            Ok(parsed_url) => {
-                if parsed_url.scheme() == "file" {
-                    // File base URLs can only work with
-                    // documents saved from filesystem
-                    if target_url.scheme() == "file" {
-                        base_url = parsed_url;
+        None
+    };
+
+    if let Some(input_data) = batch_input {
+        let targets: Vec<BatchTarget> = collect_targets(&mut monolith, &input_data);
+
+        let template: String = if output_path.is_empty() {
+            let extension: &str = match output_format {
+                OutputFormat::Html => ".html",
+                OutputFormat::Mhtml => ".mhtml",
+                OutputFormat::Dir => "",
+            };
+            format!("{}{}", DEFAULT_OUTPUT_TEMPLATE, extension)
+        } else {
+            output_path.clone()
+        };
+        let date: NaiveDate = Local::now().date_naive();
+        let mut used_output_paths: HashSet<String> = HashSet::new();
+        let mut saved: usize = 0;
+        let mut failed: Vec<(String, String)> = vec![];
+        // Exit code is determined by the first target that failed
+        let mut exit_code: i32 = 0;
+
+        // Keep going past failures, they get listed once all targets are processed
+        for batch_target in &targets {
+            let target: &String = &batch_target.url;
+            let target_url: Url = match resolve_target(target) {
+                Ok(target_url) => target_url,
+                Err(error) => {
+                    page_reports.push(create_failed_target_report(target, &error.to_string()));
+                    failed.push((target.clone(), error.to_string()));
+                    if exit_code == 0 {
+                        exit_code = get_exit_code(&error);
                     }
-                } else {
-                    base_url = parsed_url;
+                    continue;
                 }
-            }
-            Err(_) => {
-                // Failed to parse given base URL, perhaps it's a filesystem path?
-                if target_url.scheme() == "file" {
-                    // Relative paths could work for documents saved from filesystem
-                    let path: &Path = Path::new(&custom_base_url);
-                    if path.exists() {
-                        match Url::from_file_path(fs::canonicalize(&path).unwrap()) {
-                            Ok(file_url) => {
-                                base_url = file_url;
-                            }
-                            Err(_) => {
-                                if !options.silent {
-                                    eprintln!(
-                                        "Could not map given path to base URL: {}",
-                                        custom_base_url
-                                    );
-                                }
-                                process::exit(1);
-                            }
+            };
+            let target_output_path: String = make_unique_path(
+                create_output_path(&template, &batch_target.section, &target_url, date),
+                &mut used_output_paths,
+            );
+            let file_name: String = get_file_name(&target_output_path, output_format);
+
+            let result = if is_crawling {
+                monolith.crawl(target, &file_name)
+            } else {
+                monolith.fetch(target).map(|document| {
+                    vec![SavedPage {
+                        file_name,
+                        document,
+                    }]
+                })
+            };
+
+            let (reason, target_exit_code) = match result {
+                Ok(saved_pages) => {
+                    let failed_assets: usize = count_failed_assets(&saved_pages);
+                    page_reports.extend(saved_pages.iter().map(create_page_report));
+                    match save_pages(saved_pages, &target_output_path, output_format, is_crawling) {
+                        Ok(()) if strict && failed_assets > 0 => (
+                            format!("Assets that could not be embedded: {}", failed_assets),
+                            EXIT_CODE_ASSET_FAILURE,
+                        ),
+                        Ok(()) => {
+                            saved += 1;
+                            continue;
                         }
+                        Err(_) => ("Could not write output".to_string(), EXIT_CODE_WRITE_ERROR),
                     }
                 }
+                Err(error) => {
+                    page_reports.push(create_failed_target_report(target, &error.to_string()));
+                    (error.to_string(), get_exit_code(&error))
+                }
+            };
+            failed.push((target.clone(), reason));
+            if exit_code == 0 {
+                exit_code = target_exit_code;
             }
         }
+
+        finish_warc(&warc_writer);
+        write_har(&har_path, &har_log);
+        save_cookies(monolith.options());
+        write_report(&report_path, page_reports);
+
+        monolith.report(Event::BatchFinished {
+            saved,
+            failed: &failed,
+        });
+
+        if exit_code != 0 {
+            process::exit(exit_code);
+        }
+        return;
     }
 
-    // Traverse through the document and embed remote assets
-    walk_and_embed_assets(&mut cache, &client, &base_url, &dom.document, &options, 0);
+    // Name of the target's file, pages it links to get saved alongside it
+    let file_name: String = get_file_name(&output_path, output_format);
+
+    let result = if let Some(error) = target_error {
+        Err(error)
+    } else if target == "-" {
+        // Read from pipe (stdin)
+        // Set default target URL to an empty data URL; the user can set it via --base-url
+        monolith
+            .from_data(read_stdin(), &Url::parse("data:text/html,").unwrap())
+            .map(|document| {
+                vec![SavedPage {
+                    file_name,
+                    document,
+                }]
+            })
+    } else if is_crawling {
+        monolith.crawl(&target, &file_name)
+    } else {
+        monolith.fetch(&target).map(|document| {
+            vec![SavedPage {
+                file_name,
+                document,
+            }]
+        })
+    };
 
-    // Update or add new BASE element to reroute network requests and hash-links
-    if let Some(new_base_url) = options.base_url.clone() {
-        dom = set_base_url(&dom.document, new_base_url);
+    finish_warc(&warc_writer);
+    write_har(&har_path, &har_log);
+    save_cookies(monolith.options());
+
+    match result {
+        Ok(saved_pages) => {
+            let failed_assets: usize = count_failed_assets(&saved_pages);
+            page_reports.extend(saved_pages.iter().map(create_page_report));
+            if save_pages(saved_pages, &output_path, output_format, is_crawling).is_err() {
+                eprintln!("Could not write output");
+                process::exit(EXIT_CODE_WRITE_ERROR);
+            }
+            write_report(&report_path, page_reports);
+            // Output is kept, it's up to the caller what to do with it
+            if strict && failed_assets > 0 {
+                if !silent {
+                    eprintln!("Assets that could not be embedded: {}", failed_assets);
+                }
+                process::exit(EXIT_CODE_ASSET_FAILURE);
+            }
+        }
+        Err(error) => {
+            if !silent {
+                eprintln!("{}", error);
+            }
+            process::exit(get_exit_code(&error));
+        }
     }
+}
 
-    // Request and embed /favicon.ico (unless it's already linked in the document)
-    if !options.no_images
-        && (target_url.scheme() == "http" || target_url.scheme() == "https")
-        && !has_favicon(&dom.document)
-    {
-        let favicon_ico_url: Url = resolve_url(&base_url, "/favicon.ico");
-
-        match retrieve_asset(
-            &mut cache,
-            &client,
-            &target_url,
-            &favicon_ico_url,
-            &options,
-            0,
-        ) {
-            Ok((data, final_url, media_type, charset)) => {
-                let favicon_data_url: Url =
-                    create_data_url(&media_type, &charset, &data, &final_url);
-                dom = add_favicon(&dom.document, favicon_data_url.to_string());
+fn read_input(monolith: &mut Monolith, input_file: &str) -> Option<Vec<u8>> {
+    if input_file == "-" {
+        Some(read_stdin())
+    } else if Path::new(input_file).is_file() {
+        fs::read(input_file).ok()
+    } else {
+        match Url::parse(input_file) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {
+                monolith.retrieve(input_file).ok()
             }
-            Err(_) => {
-                // Failed to retrieve /favicon.ico
+            _ => None,
+        }
+    }
+}
+
+// Contents of the target as they are, nothing is known about other kinds of targets upfront
+fn read_target(monolith: &mut Monolith, target: &str) -> Result<Option<Vec<u8>>, MonolithError> {
+    if Path::new(target).is_file() {
+        Ok(fs::read(target).ok())
+    } else {
+        match Url::parse(target) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {
+                monolith.retrieve(target).map(Some)
             }
+            _ => Ok(None),
+        }
+    }
+}
+
+fn get_exit_code(error: &MonolithError) -> i32 {
+    match error {
+        MonolithError::TargetUnreachable(AssetError::HttpStatus(_)) => EXIT_CODE_TARGET_HTTP_ERROR,
+        MonolithError::TargetUnreachable(_) | MonolithError::InvalidMhtml(_) => {
+            EXIT_CODE_TARGET_UNREACHABLE
+        }
+        MonolithError::HttpClient(_) => EXIT_CODE_OTHER_ERROR,
+        // Everything else is caused by options and arguments given
+        _ => EXIT_CODE_USAGE_ERROR,
+    }
+}
+
+fn count_failed_assets(saved_pages: &[SavedPage]) -> usize {
+    saved_pages
+        .iter()
+        .map(|saved_page| saved_page.document.failed_assets.len())
+        .sum()
+}
+
+fn write_report(report_path: &Option<String>, pages: Vec<Value>) {
+    if let Some(path) = report_path {
+        if fs::write(path, create_report(pages)).is_err() {
+            eprintln!("Could not write report file");
+            process::exit(EXIT_CODE_WRITE_ERROR);
+        }
+    }
+}
+
+fn finish_warc(warc_writer: &Option<Arc<WarcWriter>>) {
+    if let Some(writer) = warc_writer {
+        if writer.finish().is_err() {
+            eprintln!("Could not write WARC file");
+            process::exit(EXIT_CODE_WRITE_ERROR);
         }
     }
+}
 
-    // Save using specified charset, if given
-    if let Some(custom_charset) = options.charset.clone() {
-        document_encoding = custom_charset;
-        dom = set_charset(dom, document_encoding.clone());
+fn write_har(har_path: &Option<String>, har_log: &Option<Arc<HarLog>>) {
+    if let (Some(path), Some(log)) = (har_path, har_log) {
+        if log.write(path).is_err() {
+            eprintln!("Could not write HAR file");
+            process::exit(EXIT_CODE_WRITE_ERROR);
+        }
     }
+}
 
-    // Serialize DOM tree
-    let mut result: Vec<u8> = serialize_document(dom, document_encoding, &options);
+// Fetcher to be wrapped by a recorder, HTTP client is used unless some other one is set up
+fn take_fetcher(
+    fetcher: &mut Option<Box<dyn AssetFetcher>>,
+    options: &Options,
+) -> Box<dyn AssetFetcher> {
+    match fetcher.take() {
+        Some(fetcher) => fetcher,
+        None => match create_http_client(options) {
+            Ok(client) => Box::new(client),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(get_exit_code(&error));
+            }
+        },
+    }
+}
 
-    // Prepend metadata comment tag
-    if !options.no_metadata {
-        let mut metadata_comment: String = create_metadata_tag(&target_url);
-        metadata_comment += "\n";
-        result.splice(0..0, metadata_comment.as_bytes().to_vec());
+// Cookies set by servers get written along with the ones loaded from file
+fn save_cookies(options: &Options) {
+    if let Some(path) = &options.save_cookies {
+        if fs::write(path, options.__cookies.to_netscape()).is_err() {
+            eprintln!("Could not write cookie file");
+            process::exit(EXIT_CODE_WRITE_ERROR);
+        }
     }
+}
 
-    // Define output
-    let mut output = Output::new(&options.output).expect("Could not prepare output");
+// Name of the file (within output directory, when saving as dir) the target is saved as
+fn get_file_name(output_path: &str, output_format: OutputFormat) -> String {
+    if output_format == OutputFormat::Dir {
+        INDEX_FILE_NAME.to_string()
+    } else {
+        Path::new(output_path)
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+// Targets may end up with the same output path, e.g. when listed more than once
+fn make_unique_path(path: String, used_paths: &mut HashSet<String>) -> String {
+    let mut unique_path: String = path.clone();
+    let mut n: usize = 2;
+    while !used_paths.insert(unique_path.clone()) {
+        let original_path: &Path = Path::new(&path);
+        unique_path = match (original_path.file_stem(), original_path.extension()) {
+            (Some(stem), Some(extension)) => original_path
+                .with_file_name(format!(
+                    "{}-{}.{}",
+                    stem.to_string_lossy(),
+                    n,
+                    extension.to_string_lossy()
+                ))
+                .to_string_lossy()
+                .to_string(),
+            _ => format!("{}-{}", path, n),
+        };
+        n += 1;
+    }
+    unique_path
+}
+
+fn save_pages(
+    saved_pages: Vec<SavedPage>,
+    output_path: &str,
+    output_format: OutputFormat,
+    is_crawling: bool,
+) -> Result<(), Error> {
+    for saved_page in saved_pages {
+        let saved_document: SavedDocument = saved_page.document;
+
+        // Non-HTML targets are saved as they are, without creating a directory
+        if output_format == OutputFormat::Dir && saved_document.is_html() {
+            save_to_directory(
+                Path::new(output_path),
+                &saved_page.file_name,
+                &saved_document.data,
+                &saved_document.assets,
+            )?;
+            continue;
+        }
+
+        let page_output_path: String = if is_crawling {
+            Path::new(output_path)
+                .with_file_name(&saved_page.file_name)
+                .to_string_lossy()
+                .to_string()
+        } else {
+            output_path.to_string()
+        };
+
+        // Create directories the file is supposed to be in
+        if let Some(parent_path) = Path::new(&page_output_path).parent() {
+            if !parent_path.as_os_str().is_empty() {
+                fs::create_dir_all(parent_path)?;
+            }
+        }
+
+        // Define output
+        let mut output = Output::new(&page_output_path)?;
+
+        // Write result into STDOUT or file
+        output.write(&saved_document.data)?;
+    }
 
-    // Write result into STDOUT or file
-    output.write(&result).expect("Could not write output");
+    Ok(())
 }
@@ -0,0 +1,434 @@
+use chrono::prelude::*;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use url::Url;
+
+use crate::fetcher::{fetch_data_url, AssetFetcher, AssetRequest, FetchedAsset};
+use crate::opts::Options;
+use crate::url::clean_url;
+use crate::utils::{is_plaintext_media_type, parse_content_type, AssetError, Resource};
+
+const MAX_LINE_LENGTH: usize = 76;
+
+// Wraps document and its assets into a multipart/related (MHTML) archive
+pub fn create_mhtml(
+    document: &[u8],
+    document_charset: &str,
+    document_url: &Url,
+    resources: &[Resource],
+) -> Vec<u8> {
+    let boundary: String = format!(
+        "----MultipartBoundary--{}----",
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect::<String>()
+    );
+
+    let mut mhtml: String = format!(
+        "From: <Saved by {name}>\r\n\
+        Snapshot-Content-Location: {url}\r\n\
+        Date: {date}\r\n\
+        MIME-Version: 1.0\r\n\
+        Content-Type: multipart/related;\r\n\
+        \ttype=\"text/html\";\r\n\
+        \tboundary=\"{boundary}\"\r\n\r\n",
+        name = env!("CARGO_PKG_NAME"),
+        url = document_url,
+        date = Utc::now().to_rfc2822(),
+        boundary = boundary,
+    );
+
+    mhtml += &create_part(
+        &boundary,
+        "text/html",
+        document_charset,
+        document,
+        document_url,
+    );
+    for resource in resources {
+        mhtml += &create_part(
+            &boundary,
+            &resource.media_type,
+            &resource.charset,
+            &resource.data,
+            &resource.url,
+        );
+    }
+    mhtml += &format!("--{}--\r\n", boundary);
+
+    mhtml.into_bytes()
+}
+
+// Document and assets of an MHTML archive, parts are looked up by their Content-Location
+// (or Content-ID, for cid: URLs) in place of retrieving them from the network
+pub struct MhtmlArchive {
+    document: Resource,
+    parts: HashMap<String, Resource>,
+}
+
+impl MhtmlArchive {
+    pub fn open(file_path: &str) -> io::Result<MhtmlArchive> {
+        MhtmlArchive::parse(&fs::read(file_path)?)
+    }
+
+    pub fn parse(data: &[u8]) -> io::Result<MhtmlArchive> {
+        let (headers, body) = split_part(data);
+        let content_type: String = find_header(&headers, "Content-Type").unwrap_or_default();
+        let media_type: String = content_type
+            .split(';')
+            .next()
+            .unwrap()
+            .trim()
+            .to_lowercase();
+        if !media_type.starts_with("multipart/") {
+            return Err(invalid_data(format!(
+                "expected a multipart archive, found {}",
+                if media_type.is_empty() {
+                    "no Content-Type"
+                } else {
+                    &media_type
+                }
+            )));
+        }
+        let boundary: String = find_parameter(&content_type, "boundary")
+            .filter(|boundary| !boundary.is_empty())
+            .ok_or_else(|| invalid_data("multipart boundary is missing".to_string()))?;
+        // Root part is either referred to by its Content-ID, or comes first
+        let start: Option<String> =
+            find_parameter(&content_type, "start").map(|start| strip_angle_brackets(&start));
+        let snapshot_location: Option<Url> = find_header(&headers, "Snapshot-Content-Location")
+            .and_then(|location| Url::parse(&location).ok());
+
+        let mut document: Option<Resource> = None;
+        let mut parts: HashMap<String, Resource> = HashMap::new();
+        for part in split_multipart(body, &boundary) {
+            let (headers, body) = split_part(part);
+            let content_id: Option<String> =
+                find_header(&headers, "Content-ID").map(|id| strip_angle_brackets(&id));
+            let content_location: Option<Url> = find_header(&headers, "Content-Location")
+                .and_then(|location| Url::parse(&location).ok());
+            let (media_type, charset, _is_base64) =
+                parse_content_type(&find_header(&headers, "Content-Type").unwrap_or_default());
+            let data: Vec<u8> = decode_body(
+                body,
+                &find_header(&headers, "Content-Transfer-Encoding").unwrap_or_default(),
+            );
+
+            let cid_url: Option<Url> = content_id
+                .as_ref()
+                .and_then(|id| Url::parse(&format!("cid:{}", id)).ok());
+            let is_root: bool = document.is_none()
+                && match &start {
+                    Some(start) => content_id.as_ref() == Some(start),
+                    None => parts.is_empty(),
+                };
+            let url: Url = match (&content_location, is_root) {
+                (Some(location), _) => location.clone(),
+                (None, true) if snapshot_location.is_some() => snapshot_location.clone().unwrap(),
+                _ => match &cid_url {
+                    Some(cid_url) => cid_url.clone(),
+                    // Parts that can't be referred to are of no use
+                    None if !is_root => continue,
+                    None => {
+                        return Err(invalid_data("document has no location".to_string()));
+                    }
+                },
+            };
+
+            let resource: Resource = Resource {
+                url: clean_url(url),
+                media_type: unquote(&media_type),
+                charset: unquote(&charset),
+                data,
+            };
+            // Earlier parts take precedence over later ones sharing the same location
+            for key in [
+                Some(&resource.url),
+                content_location.as_ref(),
+                cid_url.as_ref(),
+            ]
+            .iter()
+            .flatten()
+            {
+                parts
+                    .entry(clean_url((*key).clone()).to_string())
+                    .or_insert_with(|| resource.clone());
+            }
+            if is_root {
+                document = Some(resource);
+            }
+        }
+
+        match document {
+            Some(document) => Ok(MhtmlArchive { document, parts }),
+            None => Err(invalid_data("document part is missing".to_string())),
+        }
+    }
+
+    // Main part of the archive, usually an HTML document
+    pub fn document(&self) -> &Resource {
+        &self.document
+    }
+
+    pub fn contains(&self, url: &Url) -> bool {
+        self.parts.contains_key(clean_url(url.clone()).as_str())
+    }
+}
+
+impl AssetFetcher for MhtmlArchive {
+    fn fetch(
+        &self,
+        request: &AssetRequest,
+        _options: &Options,
+    ) -> Result<FetchedAsset, AssetError> {
+        if let Some(part) = self.parts.get(clean_url(request.url.clone()).as_str()) {
+            return Ok(FetchedAsset::new(
+                part.data.clone(),
+                part.url.clone(),
+                part.media_type.clone(),
+                part.charset.clone(),
+            ));
+        }
+
+        match request.url.scheme() {
+            "data" => Ok(fetch_data_url(request.url)),
+            _ => Err(AssetError::NotFound),
+        }
+    }
+}
+
+pub fn decode_quoted_printable(data: &[u8]) -> Vec<u8> {
+    let mut result: Vec<u8> = Vec::with_capacity(data.len());
+
+    let mut i: usize = 0;
+    while i < data.len() {
+        if data[i] == b'=' {
+            // Soft line break
+            if data.get(i + 1) == Some(&b'\n') {
+                i += 2;
+                continue;
+            }
+            if data.get(i + 1) == Some(&b'\r') && data.get(i + 2) == Some(&b'\n') {
+                i += 3;
+                continue;
+            }
+            // Encoded octet, malformed sequences are kept as they are
+            if let Some(hex) = data.get(i + 1..i + 3) {
+                if hex.iter().all(u8::is_ascii_hexdigit) {
+                    let hex: &str = std::str::from_utf8(hex).unwrap();
+                    result.push(u8::from_str_radix(hex, 16).unwrap());
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+
+        result.push(data[i]);
+        i += 1;
+    }
+
+    result
+}
+
+pub fn encode_quoted_printable(data: &[u8]) -> String {
+    let mut result: String = String::new();
+    let mut line_length: usize = 0;
+
+    let mut i: usize = 0;
+    while i < data.len() {
+        let byte: u8 = data[i];
+
+        // Hard line breaks are kept as they are
+        if byte == b'\n' || (byte == b'\r' && data.get(i + 1) == Some(&b'\n')) {
+            if byte == b'\r' {
+                i += 1;
+            }
+            result += "\r\n";
+            line_length = 0;
+            i += 1;
+            continue;
+        }
+
+        let is_line_end: bool = i + 1 == data.len() || data[i + 1] == b'\n' || data[i + 1] == b'\r';
+        let is_literal: bool = ((byte == b' ' || byte == b'\t') && !is_line_end)
+            || ((33..=126).contains(&byte) && byte != b'=');
+        let encoded: String = if is_literal {
+            (byte as char).to_string()
+        } else {
+            format!("={:02X}", byte)
+        };
+
+        // Soft line break, leaving room for the trailing equals sign
+        if line_length + encoded.len() > MAX_LINE_LENGTH - 1 {
+            result += "=\r\n";
+            line_length = 0;
+        }
+
+        result += &encoded;
+        line_length += encoded.len();
+        i += 1;
+    }
+
+    result
+}
+
+fn create_part(boundary: &str, media_type: &str, charset: &str, data: &[u8], url: &Url) -> String {
+    let is_text: bool = media_type.starts_with("text/") || is_plaintext_media_type(media_type);
+
+    let mut content_type: String = if media_type.is_empty() {
+        "application/octet-stream".to_string()
+    } else {
+        media_type.to_string()
+    };
+    // Same as with data URLs, US-ASCII is implied
+    if is_text && !charset.trim().is_empty() && !charset.trim().eq_ignore_ascii_case("US-ASCII") {
+        content_type += &format!("; charset={}", charset);
+    }
+
+    let (transfer_encoding, body): (&str, String) = if is_text {
+        ("quoted-printable", encode_quoted_printable(data))
+    } else {
+        (
+            "base64",
+            base64::encode(data)
+                .as_bytes()
+                .chunks(MAX_LINE_LENGTH)
+                .map(|chunk| String::from_utf8_lossy(chunk).to_string())
+                .collect::<Vec<String>>()
+                .join("\r\n"),
+        )
+    };
+
+    format!(
+        "--{}\r\n\
+        Content-Type: {}\r\n\
+        Content-Transfer-Encoding: {}\r\n\
+        Content-Location: {}\r\n\r\n\
+        {}\r\n",
+        boundary, content_type, transfer_encoding, url, body
+    )
+}
+
+fn decode_body(body: &[u8], transfer_encoding: &str) -> Vec<u8> {
+    match transfer_encoding.trim().to_lowercase().as_str() {
+        "base64" => {
+            let encoded: Vec<u8> = body
+                .iter()
+                .filter(|byte| !byte.is_ascii_whitespace())
+                .cloned()
+                .collect();
+            base64::decode(encoded).unwrap_or_default()
+        }
+        "quoted-printable" => decode_quoted_printable(body),
+        // 7bit, 8bit and binary parts aren't encoded
+        _ => body.to_vec(),
+    }
+}
+
+// Header names are case-insensitive
+fn find_header(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.clone())
+}
+
+fn find_parameter(header_value: &str, name: &str) -> Option<String> {
+    header_value.split(';').skip(1).find_map(|parameter| {
+        let (parameter_name, value) = parameter.split_once('=')?;
+        if parameter_name.trim().eq_ignore_ascii_case(name) {
+            Some(unquote(value))
+        } else {
+            None
+        }
+    })
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Splits multipart body into parts, ignoring preamble and epilogue
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter: String = format!("--{}", boundary);
+    let mut parts: Vec<&[u8]> = vec![];
+    let mut part_start: Option<usize> = None;
+
+    let mut offset: usize = 0;
+    for line in body.split_inclusive(|byte| *byte == b'\n') {
+        let line_start: usize = offset;
+        offset += line.len();
+
+        let line: &[u8] = trim_line_end(line);
+        if !line.starts_with(delimiter.as_bytes()) {
+            continue;
+        }
+        let rest: &str = std::str::from_utf8(&line[delimiter.len()..]).unwrap_or("-");
+        let is_last: bool = rest.trim_end() == "--";
+        if !is_last && !rest.trim_end().is_empty() {
+            continue;
+        }
+
+        // Line break preceding the delimiter belongs to the delimiter
+        if let Some(start) = part_start {
+            parts.push(trim_line_end(&body[start..line_start]));
+        }
+        if is_last {
+            return parts;
+        }
+        part_start = Some(offset);
+    }
+
+    // Archive got cut short, keep whatever is there
+    if let Some(start) = part_start {
+        parts.push(&body[start..]);
+    }
+
+    parts
+}
+
+// Separates headers from the body, unfolding headers which span multiple lines
+fn split_part(part: &[u8]) -> (Vec<(String, String)>, &[u8]) {
+    let mut headers: Vec<(String, String)> = vec![];
+
+    let mut offset: usize = 0;
+    for line in part.split_inclusive(|byte| *byte == b'\n') {
+        offset += line.len();
+
+        let line: String = String::from_utf8_lossy(trim_line_end(line)).to_string();
+        if line.is_empty() {
+            return (headers, &part[offset..]);
+        }
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some((_, value)) = headers.last_mut() {
+                *value = format!("{} {}", value, line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    (headers, &[])
+}
+
+fn strip_angle_brackets(value: &str) -> String {
+    value
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string()
+}
+
+fn trim_line_end(line: &[u8]) -> &[u8] {
+    let line: &[u8] = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
@@ -1,30 +1,66 @@
-use crate::cookies::Cookie;
+use crate::cookies::CookieJar;
+use crate::fetcher::{parse_request_header, RequestHeader};
 use clap::{App, Arg, ArgAction};
 use std::env;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    // Single HTML file with assets embedded as data URLs
+    #[default]
+    Html,
+    // MIME multipart/related archive with assets as separate parts
+    Mhtml,
+    // Directory with index.html and assets stored as separate files next to it
+    Dir,
+}
 
 #[derive(Default)]
 pub struct Options {
     pub no_audio: bool,
+    pub accept_status: Option<String>,
     pub base_url: Option<String>,
     pub blacklist_domains: bool,
+    pub cache_dir: Option<String>,
+    pub cacert: Option<String>,
+    pub cert: Option<String>,
     pub no_css: bool,
     pub charset: Option<String>,
     pub domains: Option<Vec<String>>,
+    pub crawl_depth: u32,
     pub ignore_errors: bool,
+    pub keep_error_urls: bool,
     pub no_frames: bool,
     pub no_fonts: bool,
+    pub fail_on_status: Option<String>,
+    pub output_format: OutputFormat,
+    pub from_warc: Option<String>,
+    pub har: Option<String>,
+    pub headers: Vec<RequestHeader>,
     pub no_images: bool,
     pub isolate: bool,
+    pub jobs: usize,
     pub no_js: bool,
     pub insecure: bool,
+    pub key: Option<String>,
+    pub input_file: Option<String>,
     pub cookie_file: Option<String>,
-    pub __cookies: Vec<Cookie>,
+    pub __cookies: Arc<CookieJar>,
+    pub save_cookies: Option<String>,
+    pub log_format: Option<String>,
     pub no_metadata: bool,
+    pub no_proxy: Option<String>,
     pub output: String,
+    pub proxy: Option<String>,
+    pub report: Option<String>,
+    pub referrer_policy: Option<String>,
+    pub retries: u32,
     pub silent: bool,
+    pub strict: bool,
     pub timeout: u64,
     pub user_agent: Option<String>,
     pub no_video: bool,
+    pub warc: Option<String>,
     pub target: String,
     pub no_color: bool,
     pub unwrap_noscript: bool,
@@ -39,6 +75,7 @@ const ASCII: &'static str = " \
 |   | \\___/ |          | \\                      |   |   |   |   |  |   |
 |___|       |__________|  \\_____________________|   |___|   |___|  |___|
 ";
+const DEFAULT_JOBS: usize = 1;
 const DEFAULT_NETWORK_TIMEOUT: u64 = 120;
 const DEFAULT_USER_AGENT: &'static str =
     "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:73.0) Gecko/20100101 Firefox/73.0";
@@ -52,11 +89,23 @@ impl Options {
             .author(format!("\n{}\n\n", env!("CARGO_PKG_AUTHORS").replace(':', "\n")).as_str())
             .about(format!("{}\n{}", ASCII, env!("CARGO_PKG_DESCRIPTION")).as_str())
             .args_from_usage("-a, --no-audio 'Removes audio sources'")
+            .args_from_usage(
+                "    --accept-status=[404,410] 'Accepts assets with listed status codes (or classes, e.g. 4xx)'",
+            )
             .args_from_usage("-b, --base-url=[http://localhost/] 'Sets custom base URL'")
             .args_from_usage(
                 "-B, --blacklist-domains 'Treat list of specified domains as blacklist'",
             )
             .args_from_usage("-c, --no-css 'Removes CSS'")
+            .args_from_usage(
+                "    --cache-dir=[cache] 'Keeps retrieved assets in <dir> for reuse'",
+            )
+            .args_from_usage(
+                "    --cacert=[bundle.pem] 'Trusts CA certificates from <file> in addition to system ones'",
+            )
+            .args_from_usage(
+                "    --cert=[client.pem] 'Presents client certificate from <file> (PEM, may include key)'",
+            )
             .args_from_usage("-C, --charset=[UTF-8] 'Enforces custom encoding'")
             .arg(
                 Arg::with_name("domains")
@@ -67,28 +116,109 @@ impl Options {
                     .action(ArgAction::Append)
                     .help("Specify domains to use for white/black-listing"),
             )
+            .arg(
+                Arg::with_name("depth")
+                    .long("depth")
+                    .takes_value(true)
+                    .value_name("0")
+                    .value_parser(parse_number)
+                    .help("Also saves pages linked from the target, up to <N> links away"),
+            )
             .args_from_usage("-e, --ignore-errors 'Ignore network errors'")
+            .args_from_usage(
+                "    --keep-error-urls 'Keeps remote URLs of assets responding with 4xx or 5xx instead of embedding error pages'",
+            )
             .args_from_usage("-f, --no-frames 'Removes frames and iframes'")
             .args_from_usage("-F, --no-fonts 'Removes fonts'")
+            .args_from_usage(
+                "    --fail-on-status=[3xx] 'Rejects assets with listed status codes (or classes), even with -e'",
+            )
+            .arg(
+                Arg::with_name("format")
+                    .long("format")
+                    .takes_value(true)
+                    .value_name("html")
+                    .possible_values(["html", "mhtml", "dir"])
+                    .help("Sets output format"),
+            )
+            .args_from_usage(
+                "    --from-warc=[archive.warc.gz] 'Retrieves assets from WARC <file> instead of the network'",
+            )
+            .args_from_usage(
+                "    --har=[capture.har] 'Saves log of HTTP requests and responses into HAR <file>'",
+            )
+            .arg(
+                Arg::with_name("header")
+                    .short('H')
+                    .long("header")
+                    .takes_value(true)
+                    .value_name("Name: value")
+                    .action(ArgAction::Append)
+                    .value_parser(parse_header)
+                    .help("Adds header to requests, prefix with <domains>= to only send it there"),
+            )
             .args_from_usage("-i, --no-images 'Removes images'")
             .args_from_usage("-I, --isolate 'Cuts off document from the Internet'")
             .args_from_usage("-j, --no-js 'Removes JavaScript'")
+            .arg(
+                Arg::with_name("jobs")
+                    .long("jobs")
+                    .takes_value(true)
+                    .value_name("4")
+                    .value_parser(parse_positive_number)
+                    .help("Retrieves up to <N> assets in parallel"),
+            )
+            .args_from_usage(
+                "    --input-file=[urls.txt] 'Saves every target listed in <file> (list, bookmarks, or sitemap), use - for STDIN'",
+            )
             .args_from_usage("-k, --insecure 'Allows invalid X.509 (TLS) certificates'")
-            .args_from_usage("-L, --load-cookies=[cookies.txt] 'Sets cookies for requests'")
+            .args_from_usage(
+                "    --key=[client.key] 'Uses private key from <file> for client certificate (PKCS#8 PEM)'",
+            )
+            .args_from_usage("-L, --load-cookies=[cookies.txt] 'Sets cookies for requests (cookies.txt, JSON, or Firefox cookies.sqlite)'")
+            .args_from_usage("    --log-format=[tree] 'Sets progress output format: tree or json'")
             .args_from_usage("-M, --no-metadata 'Excludes timestamp and source information'")
             .args_from_usage(
                 "-n, --unwrap-noscript 'Replaces NOSCRIPT elements with their contents'",
             )
+            .args_from_usage(
+                "    --no-proxy=[localhost,.example.com] 'Contacts given hosts directly, bypassing proxy'",
+            )
             .args_from_usage(
                 "-o, --output=[document.html] 'Writes output to <file>, use - for STDOUT'",
             )
+            .args_from_usage(
+                "    --proxy=[socks5h://localhost:9050] 'Sends requests through proxy (http, https, socks5, or socks5h)'",
+            )
+            .args_from_usage(
+                "    --referrer-policy=[strict-origin-when-cross-origin] 'Limits how much of document's URL gets sent as Referer'",
+            )
+            .args_from_usage(
+                "    --report=[report.json] 'Writes list of assets and what happened to them into <file>'",
+            )
+            .arg(
+                Arg::with_name("retries")
+                    .long("retries")
+                    .takes_value(true)
+                    .value_name("3")
+                    .value_parser(parse_number)
+                    .help("Retries requests failing due to network or server errors up to <N> times"),
+            )
+            .args_from_usage(
+                "    --save-cookies=[cookies.txt] 'Writes cookies, including ones set by servers, into <file>'",
+            )
             .args_from_usage("-s, --silent 'Suppresses verbosity'")
+            .args_from_usage(
+                "    --strict 'Fails with exit code 5 if any asset could not be embedded'",
+            )
             .args_from_usage("-t, --timeout=[60] 'Adjusts network request timeout'")
             .args_from_usage("-u, --user-agent=[Firefox] 'Sets custom User-Agent string'")
             .args_from_usage("-v, --no-video 'Removes video sources'")
+            .args_from_usage("    --warc=[out.warc.gz] 'Saves raw HTTP exchanges into WARC <file>'")
             .arg(
                 Arg::with_name("target")
-                    .required(true)
+                    .required_unless_present("input-file")
+                    .conflicts_with("input-file")
                     .takes_value(true)
                     .index(1)
                     .help("URL or file path, use - for STDIN"),
@@ -97,15 +227,16 @@ impl Options {
         let mut options: Options = Options::default();
 
         // Process the command
-        options.target = app
-            .value_of("target")
-            .expect("please set target")
-            .to_string();
+        options.target = app.value_of("target").unwrap_or_default().to_string();
         options.no_audio = app.is_present("no-audio");
+        options.accept_status = app.value_of("accept-status").map(|s| s.to_string());
         if let Some(base_url) = app.value_of("base-url") {
             options.base_url = Some(base_url.to_string());
         }
         options.blacklist_domains = app.is_present("blacklist-domains");
+        options.cache_dir = app.value_of("cache-dir").map(|s| s.to_string());
+        options.cacert = app.value_of("cacert").map(|s| s.to_string());
+        options.cert = app.value_of("cert").map(|s| s.to_string());
         options.no_css = app.is_present("no-css");
         if let Some(charset) = app.value_of("charset") {
             options.charset = Some(charset.to_string());
@@ -114,17 +245,46 @@ impl Options {
             let list_of_domains: Vec<String> = domains.map(|v| v.clone()).collect::<Vec<_>>();
             options.domains = Some(list_of_domains);
         }
+        options.crawl_depth = app.get_one::<u32>("depth").copied().unwrap_or(0);
         options.ignore_errors = app.is_present("ignore-errors");
+        options.keep_error_urls = app.is_present("keep-error-urls");
         options.no_frames = app.is_present("no-frames");
         options.no_fonts = app.is_present("no-fonts");
+        options.fail_on_status = app.value_of("fail-on-status").map(|s| s.to_string());
+        options.output_format = match app.value_of("format") {
+            Some("mhtml") => OutputFormat::Mhtml,
+            Some("dir") => OutputFormat::Dir,
+            _ => OutputFormat::Html,
+        };
+        options.from_warc = app.value_of("from-warc").map(|s| s.to_string());
+        options.har = app.value_of("har").map(|s| s.to_string());
+        // Invalid headers are rejected by the parser, with usage error
+        if let Some(headers) = app.get_many::<RequestHeader>("header") {
+            options.headers = headers.cloned().collect();
+        }
         options.no_images = app.is_present("no-images");
         options.isolate = app.is_present("isolate");
+        // Invalid and zero values are rejected by the parser, with usage error
+        options.jobs = app
+            .get_one::<usize>("jobs")
+            .copied()
+            .unwrap_or(DEFAULT_JOBS);
         options.no_js = app.is_present("no-js");
         options.insecure = app.is_present("insecure");
+        options.key = app.value_of("key").map(|s| s.to_string());
+        options.input_file = app.value_of("input-file").map(|s| s.to_string());
         options.no_metadata = app.is_present("no-metadata");
         options.cookie_file = app.value_of("load-cookies").map(|s| s.to_string());
+        options.save_cookies = app.value_of("save-cookies").map(|s| s.to_string());
+        options.log_format = app.value_of("log-format").map(|s| s.to_string());
+        options.no_proxy = app.value_of("no-proxy").map(|s| s.to_string());
         options.output = app.value_of("output").unwrap_or("").to_string();
+        options.proxy = app.value_of("proxy").map(|s| s.to_string());
+        options.report = app.value_of("report").map(|s| s.to_string());
+        options.referrer_policy = app.value_of("referrer-policy").map(|s| s.to_string());
+        options.retries = app.get_one::<u32>("retries").copied().unwrap_or(0);
         options.silent = app.is_present("silent");
+        options.strict = app.is_present("strict");
         options.timeout = app
             .value_of("timeout")
             .unwrap_or(&DEFAULT_NETWORK_TIMEOUT.to_string())
@@ -137,6 +297,7 @@ impl Options {
         }
         options.unwrap_noscript = app.is_present("unwrap-noscript");
         options.no_video = app.is_present("no-video");
+        options.warc = app.value_of("warc").map(|s| s.to_string());
 
         options.no_color =
             env::var_os(ENV_VAR_NO_COLOR).is_some() || atty::isnt(atty::Stream::Stderr);
@@ -149,3 +310,21 @@ impl Options {
         options
     }
 }
+
+fn parse_header(value: &str) -> Result<RequestHeader, String> {
+    parse_request_header(value)
+        .ok_or_else(|| "must be \"Name: value\", optionally prefixed with \"domains=\"".to_string())
+}
+
+fn parse_number(value: &str) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .map_err(|_| "must be a number".to_string())
+}
+
+fn parse_positive_number(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err("must be a positive number".to_string()),
+    }
+}
@@ -0,0 +1,121 @@
+use std::env;
+use std::net::IpAddr;
+use url::Url;
+
+use crate::core::MonolithError;
+use crate::opts::Options;
+
+// Proxies to send HTTP and HTTPS requests through, and hosts to contact directly
+#[derive(Clone, Debug, Default)]
+pub struct ProxyConfig {
+    pub http: Option<Url>,
+    pub https: Option<Url>,
+    pub no_proxy: Vec<String>,
+}
+
+impl ProxyConfig {
+    // Proxy given explicitly takes precedence over HTTP_PROXY, HTTPS_PROXY and ALL_PROXY,
+    // list of hosts given explicitly takes precedence over NO_PROXY
+    pub fn new<F>(
+        proxy: Option<&str>,
+        no_proxy: Option<&str>,
+        get_env_var: F,
+    ) -> Result<ProxyConfig, MonolithError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        // Both upper and lower case variants are in use
+        let env_var = |name: &str| {
+            get_env_var(name)
+                .or_else(|| get_env_var(&name.to_lowercase()))
+                .filter(|value| !value.trim().is_empty())
+        };
+
+        let (http, https) = match proxy {
+            Some(proxy) => {
+                let proxy: Url = parse_proxy_url(proxy)?;
+                (Some(proxy.clone()), Some(proxy))
+            }
+            None => {
+                let all_proxy: Option<String> = env_var("ALL_PROXY");
+                let http: Option<String> = env_var("HTTP_PROXY").or_else(|| all_proxy.clone());
+                let https: Option<String> = env_var("HTTPS_PROXY").or(all_proxy);
+                (
+                    http.map(|proxy| parse_proxy_url(&proxy)).transpose()?,
+                    https.map(|proxy| parse_proxy_url(&proxy)).transpose()?,
+                )
+            }
+        };
+
+        let no_proxy: Vec<String> = no_proxy
+            .map(|no_proxy| no_proxy.to_string())
+            .or_else(|| env_var("NO_PROXY"))
+            .unwrap_or_default()
+            .split(',')
+            .map(|host| host.trim().trim_start_matches('.').to_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect();
+
+        Ok(ProxyConfig {
+            http,
+            https,
+            no_proxy,
+        })
+    }
+
+    pub fn from_options(options: &Options) -> Result<ProxyConfig, MonolithError> {
+        ProxyConfig::new(
+            options.proxy.as_deref(),
+            options.no_proxy.as_deref(),
+            |name| env::var(name).ok(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.http.is_none() && self.https.is_none()
+    }
+
+    pub fn proxy_for(&self, url: &Url) -> Option<&Url> {
+        if self.bypasses(url.host_str().unwrap_or_default()) {
+            return None;
+        }
+
+        match url.scheme() {
+            "http" => self.http.as_ref(),
+            "https" => self.https.as_ref(),
+            _ => None,
+        }
+    }
+
+    // Hosts are matched along with their subdomains, "*" matches everything
+    pub fn bypasses(&self, host: &str) -> bool {
+        let host: String = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_lowercase();
+
+        self.no_proxy.iter().any(|no_proxy_host| {
+            if no_proxy_host == "*" || *no_proxy_host == host {
+                true
+            } else {
+                host.parse::<IpAddr>().is_err() && host.ends_with(&format!(".{}", no_proxy_host))
+            }
+        })
+    }
+}
+
+// Proxies without scheme are assumed to be HTTP ones, the way curl does it
+fn parse_proxy_url(proxy: &str) -> Result<Url, MonolithError> {
+    let proxy: &str = proxy.trim();
+    let proxy_url: Url = if proxy.contains("://") {
+        Url::parse(proxy)
+    } else {
+        Url::parse(&format!("http://{}", proxy))
+    }
+    .map_err(|_| MonolithError::InvalidProxy(proxy.to_string()))?;
+
+    match proxy_url.scheme() {
+        "http" | "https" | "socks5" | "socks5h" if proxy_url.host_str().is_some() => Ok(proxy_url),
+        _ => Err(MonolithError::InvalidProxy(proxy.to_string())),
+    }
+}
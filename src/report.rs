@@ -0,0 +1,127 @@
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::core::SavedPage;
+use crate::opts::Options;
+use crate::session::Session;
+use crate::utils::AssetError;
+
+// Asset the document refers to, along with what happened when it got retrieved
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AssetRecord {
+    // Element and attribute the asset is referred to by, CSS references have no element
+    // and use property name (or @import) as attribute
+    pub element: Option<String>,
+    pub attribute: String,
+    // Reference as it appears in the document
+    pub value: String,
+    // Document or stylesheet the reference is in
+    pub parent_url: String,
+    pub url: String,
+    pub final_url: Option<String>,
+    pub media_type: Option<String>,
+    pub bytes: Option<usize>,
+    pub from_cache: Option<bool>,
+    // Result of integrity check, if the element has integrity attribute
+    pub integrity: Option<bool>,
+    pub error: Option<String>,
+}
+
+impl AssetRecord {
+    pub fn new(
+        element: Option<&str>,
+        attribute: &str,
+        value: &str,
+        parent_url: &Url,
+        url: &Url,
+    ) -> AssetRecord {
+        AssetRecord {
+            element: element.map(|element| element.to_string()),
+            attribute: attribute.to_string(),
+            value: value.to_string(),
+            parent_url: parent_url.to_string(),
+            url: url.to_string(),
+            ..AssetRecord::default()
+        }
+    }
+
+    pub fn succeeded(
+        mut self,
+        final_url: &Url,
+        media_type: &str,
+        bytes: usize,
+        session: &Session,
+    ) -> AssetRecord {
+        self.final_url = Some(final_url.to_string());
+        self.media_type = Some(media_type.to_string());
+        self.bytes = Some(bytes);
+        self.from_cache = session.document.cache_hits.get(&self.url).copied();
+        self
+    }
+
+    pub fn failed(mut self, error: &AssetError) -> AssetRecord {
+        self.error = Some(error.to_string());
+        self
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "element": self.element,
+            "attribute": self.attribute,
+            "value": self.value,
+            "parent_url": self.parent_url,
+            "url": self.url,
+            "final_url": self.final_url,
+            "media_type": self.media_type,
+            "bytes": self.bytes,
+            "from_cache": self.from_cache,
+            "integrity": self.integrity.map(|passed| if passed { "passed" } else { "failed" }),
+            "error": self.error,
+        })
+    }
+}
+
+// Records are only kept when a report is going to be written
+pub fn record_asset(session: &mut Session, options: &Options, record: AssetRecord) {
+    if options.report.is_some() {
+        session.document.asset_records.push(record);
+    }
+}
+
+// Whether the asset was taken from cache is decided by the first time it got retrieved
+pub fn record_cache_hit(session: &mut Session, options: &Options, url: &Url, from_cache: bool) {
+    if options.report.is_some() {
+        session
+            .document
+            .cache_hits
+            .entry(url.to_string())
+            .or_insert(from_cache);
+    }
+}
+
+pub fn create_page_report(saved_page: &SavedPage) -> Value {
+    json!({
+        "url": saved_page.document.final_url.as_str(),
+        "file_name": saved_page.file_name,
+        "media_type": saved_page.document.media_type,
+        "bytes": saved_page.document.data.len(),
+        "assets": saved_page
+            .document
+            .asset_records
+            .iter()
+            .map(|record| record.to_json())
+            .collect::<Vec<Value>>(),
+    })
+}
+
+// Targets of a batch that could not be saved are listed along with the reason
+pub fn create_failed_target_report(target: &str, reason: &str) -> Value {
+    json!({
+        "url": target,
+        "error": reason,
+    })
+}
+
+pub fn create_report(pages: Vec<Value>) -> String {
+    serde_json::to_string_pretty(&json!({ "pages": pages })).unwrap()
+}
@@ -0,0 +1,232 @@
+use reqwest::StatusCode;
+use serde_json::json;
+use std::time::Duration;
+use url::Url;
+
+use crate::opts::Options;
+use crate::session::Session;
+use crate::utils::{indent, AssetError};
+
+const ANSI_COLOR_RED: &'static str = "\x1b[31m";
+const ANSI_COLOR_RESET: &'static str = "\x1b[0m";
+
+pub enum Event<'a> {
+    // Asset is about to be retrieved
+    AssetStarted {
+        url: &'a Url,
+        depth: u32,
+    },
+    // Asset got retrieved (or taken from cache)
+    AssetFinished {
+        url: &'a Url,
+        final_url: &'a Url,
+        bytes: usize,
+        from_cache: bool,
+        depth: u32,
+    },
+    // Asset is going to be requested again after a transient failure
+    AssetRetrying {
+        url: &'a Url,
+        error: &'a AssetError,
+        // Number of the upcoming retry, out of retries allowed
+        attempt: u32,
+        retries: u32,
+        delay: Duration,
+        depth: u32,
+    },
+    // Asset could not be retrieved or used
+    AssetFailed {
+        url: &'a Url,
+        error: &'a AssetError,
+        depth: u32,
+    },
+    // Document got saved along with all of its assets
+    DocumentFinished {
+        url: &'a Url,
+        bytes: usize,
+    },
+    // All targets of a batch got processed, failed ones are listed along with the reason
+    BatchFinished {
+        saved: usize,
+        failed: &'a [(String, String)],
+    },
+}
+
+pub trait Reporter: Send + Sync {
+    fn report(&self, event: &Event);
+}
+
+// Indented tree of retrieved assets, failures are highlighted in red
+pub struct TreeReporter {
+    pub no_color: bool,
+}
+
+impl Reporter for TreeReporter {
+    fn report(&self, event: &Event) {
+        match event {
+            Event::AssetFinished {
+                url,
+                final_url,
+                from_cache,
+                depth,
+                ..
+            } => {
+                if *from_cache {
+                    eprintln!("{}{} (from cache)", indent(*depth).as_str(), url);
+                } else if url.as_str() == final_url.as_str() {
+                    eprintln!("{}{}", indent(*depth).as_str(), url);
+                } else {
+                    eprintln!("{}{} -> {}", indent(*depth).as_str(), url, final_url);
+                }
+            }
+            Event::AssetRetrying {
+                url,
+                error,
+                attempt,
+                retries,
+                delay,
+                depth,
+            } => {
+                eprintln!(
+                    "{}{} ({}, retry {} of {} in {:.1}s)",
+                    indent(*depth).as_str(),
+                    url,
+                    describe_error(error),
+                    attempt,
+                    retries,
+                    delay.as_secs_f64(),
+                );
+            }
+            Event::AssetFailed { url, error, depth } => {
+                // Blocked and mismatching assets are skipped without a word
+                if matches!(error, AssetError::Blocked | AssetError::Integrity) {
+                    return;
+                }
+
+                eprintln!(
+                    "{}{}{} ({}){}",
+                    indent(*depth).as_str(),
+                    if self.no_color { "" } else { ANSI_COLOR_RED },
+                    url,
+                    describe_error(error),
+                    if self.no_color { "" } else { ANSI_COLOR_RESET },
+                );
+            }
+            Event::BatchFinished { saved, failed } => {
+                eprintln!("Saved {} of {} targets", saved, saved + failed.len());
+                for (target, reason) in failed.iter() {
+                    eprintln!(
+                        "{}{} ({}){}",
+                        if self.no_color { "" } else { ANSI_COLOR_RED },
+                        target,
+                        reason,
+                        if self.no_color { "" } else { ANSI_COLOR_RESET },
+                    );
+                }
+            }
+            Event::AssetStarted { .. } | Event::DocumentFinished { .. } => {}
+        }
+    }
+}
+
+fn describe_error(error: &AssetError) -> String {
+    match error {
+        AssetError::HttpStatus(status) => match StatusCode::from_u16(*status) {
+            Ok(status_code) => status_code.to_string(),
+            Err(_) => status.to_string(),
+        },
+        AssetError::Network(network_error) => network_error.to_string(),
+        AssetError::Io(io_error) => io_error.to_string(),
+        AssetError::SecurityPolicy => "Security Error".to_string(),
+        _ => error.to_string(),
+    }
+}
+
+// One JSON object per line, meant to be consumed by other programs
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&self, event: &Event) {
+        let line = match event {
+            Event::AssetStarted { url, depth } => json!({
+                "event": "asset-started",
+                "url": url.as_str(),
+                "depth": depth,
+            }),
+            Event::AssetFinished {
+                url,
+                final_url,
+                bytes,
+                from_cache,
+                depth,
+            } => json!({
+                "event": "asset-finished",
+                "url": url.as_str(),
+                "final_url": final_url.as_str(),
+                "bytes": bytes,
+                "from_cache": from_cache,
+                "depth": depth,
+            }),
+            Event::AssetRetrying {
+                url,
+                error,
+                attempt,
+                retries,
+                delay,
+                depth,
+            } => json!({
+                "event": "asset-retrying",
+                "url": url.as_str(),
+                "reason": error.to_string(),
+                "attempt": attempt,
+                "retries": retries,
+                "delay_ms": delay.as_millis() as u64,
+                "depth": depth,
+            }),
+            Event::AssetFailed { url, error, depth } => json!({
+                "event": "asset-failed",
+                "url": url.as_str(),
+                "reason": error.to_string(),
+                "depth": depth,
+            }),
+            Event::DocumentFinished { url, bytes } => json!({
+                "event": "document-finished",
+                "url": url.as_str(),
+                "bytes": bytes,
+            }),
+            Event::BatchFinished { saved, failed } => json!({
+                "event": "batch-finished",
+                "saved": saved,
+                "failed": failed
+                    .iter()
+                    .map(|(target, reason)| json!({ "target": target, "reason": reason }))
+                    .collect::<Vec<_>>(),
+            }),
+        };
+
+        eprintln!("{}", line);
+    }
+}
+
+pub fn create_reporter(log_format: &str, no_color: bool) -> Option<Box<dyn Reporter>> {
+    match log_format {
+        "tree" => Some(Box::new(TreeReporter { no_color })),
+        "json" => Some(Box::new(JsonReporter)),
+        _ => None,
+    }
+}
+
+// Passes the event to the reporter set for the session, falls back to printing the tree
+pub fn report(session: &Session, options: &Options, event: Event) {
+    if options.silent {
+        return;
+    }
+
+    match &session.reporter {
+        Some(reporter) => reporter.report(&event),
+        None => TreeReporter {
+            no_color: options.no_color,
+        }
+        .report(&event),
+    }
+}
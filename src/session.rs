@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use url::Url;
+
+use crate::cache::CacheEntry;
+use crate::report::AssetRecord;
+use crate::reporter::Reporter;
+use crate::utils::{AssetError, Resource};
+
+// What happened to assets of the document being saved, handed over along with the document
+#[derive(Default)]
+pub struct DocumentState {
+    // Assets stored alongside the document instead of being embedded into it
+    pub resources: Vec<Resource>,
+    // Assets which could not be embedded, along with why
+    pub failed_assets: Vec<(Url, AssetError)>,
+    // Every asset the document refers to and what happened to it, kept only for the report
+    pub asset_records: Vec<AssetRecord>,
+    pub cache_hits: HashMap<String, bool>,
+    // Why assets could not be retrieved, keyed the same way as the cache;
+    // such assets don't get requested (or reported) again while saving the document
+    pub errors: HashMap<String, AssetError>,
+}
+
+// Everything that changes while documents get saved, options stay plain configuration
+#[derive(Default)]
+pub struct Session {
+    // Assets retrieved so far, shared by all documents saved by the same Monolith
+    pub cache: HashMap<String, CacheEntry>,
+    // Local file names of pages saved during the current crawl, keyed by their URLs
+    pub page_files: HashMap<String, String>,
+    // Receives progress events, the tree gets printed when it's not set
+    pub reporter: Option<Arc<dyn Reporter>>,
+    pub document: DocumentState,
+    // URLs of assets asked for while only looking for them, nothing gets retrieved when it's set
+    pub collected_urls: Option<Vec<Url>>,
+}
+
+impl Session {
+    // Session for walking through documents to find out which assets they need
+    pub fn collecting() -> Session {
+        Session {
+            collected_urls: Some(vec![]),
+            ..Session::default()
+        }
+    }
+
+    // Separate session for retrieving assets on another thread, results get merged back
+    pub fn fork(&self) -> Session {
+        Session {
+            reporter: self.reporter.clone(),
+            ..Session::default()
+        }
+    }
+
+    pub fn merge(&mut self, other: Session) {
+        self.cache.extend(other.cache);
+        for (url, from_cache) in other.document.cache_hits {
+            self.document.cache_hits.entry(url).or_insert(from_cache);
+        }
+        self.document.errors.extend(other.document.errors);
+    }
+}
@@ -0,0 +1,97 @@
+use reqwest::{Certificate, Identity};
+use std::fs;
+
+use crate::core::MonolithError;
+use crate::opts::Options;
+
+// Certificates to trust in addition to system ones, and certificate to present to servers
+#[derive(Default)]
+pub struct TlsConfig {
+    pub root_certificates: Vec<Certificate>,
+    pub identity: Option<Identity>,
+}
+
+impl TlsConfig {
+    pub fn from_options(options: &Options) -> Result<TlsConfig, MonolithError> {
+        let mut tls_config: TlsConfig = TlsConfig::default();
+
+        if let Some(cacert) = &options.cacert {
+            let pem: String = fs::read_to_string(cacert)
+                .map_err(|_| MonolithError::InvalidCaCert(cacert.clone()))?;
+            let certificates: Vec<String> = find_pem_blocks(&pem, "CERTIFICATE");
+            if certificates.is_empty() {
+                return Err(MonolithError::InvalidCaCert(cacert.clone()));
+            }
+            for certificate in certificates {
+                tls_config.root_certificates.push(
+                    Certificate::from_pem(certificate.as_bytes())
+                        .map_err(|_| MonolithError::InvalidCaCert(cacert.clone()))?,
+                );
+            }
+        }
+
+        match (&options.cert, &options.key) {
+            (Some(cert), key) => {
+                let cert_pem: String = fs::read_to_string(cert)
+                    .map_err(|_| MonolithError::InvalidClientCert(cert.clone()))?;
+                // Key may be stored in the same file as the certificate
+                let key_pem: String = match key {
+                    Some(key) => fs::read_to_string(key)
+                        .map_err(|_| MonolithError::InvalidClientKey(key.clone()))?,
+                    None => cert_pem.clone(),
+                };
+                tls_config.identity = Some(create_identity(
+                    &cert_pem,
+                    &key_pem,
+                    cert,
+                    key.as_ref().unwrap_or(cert),
+                )?);
+            }
+            (None, Some(_)) => return Err(MonolithError::NoClientCert),
+            (None, None) => {}
+        }
+
+        Ok(tls_config)
+    }
+}
+
+fn create_identity(
+    cert_pem: &str,
+    key_pem: &str,
+    cert_path: &str,
+    key_path: &str,
+) -> Result<Identity, MonolithError> {
+    let certificates: Vec<String> = find_pem_blocks(cert_pem, "CERTIFICATE");
+    if certificates.is_empty() {
+        return Err(MonolithError::InvalidClientCert(cert_path.to_string()));
+    }
+
+    // Only unencrypted PKCS#8 keys can be used
+    let key: String = find_pem_blocks(key_pem, "PRIVATE KEY")
+        .into_iter()
+        .next()
+        .ok_or_else(|| MonolithError::InvalidClientKey(key_path.to_string()))?;
+
+    Identity::from_pkcs8_pem(certificates.join("").as_bytes(), key.as_bytes())
+        .map_err(|_| MonolithError::InvalidClientCert(cert_path.to_string()))
+}
+
+// Picks PEM blocks of given type (e.g. CERTIFICATE) out of a file that may contain other things
+pub fn find_pem_blocks(pem: &str, label: &str) -> Vec<String> {
+    let begin: String = format!("-----BEGIN {}-----", label);
+    let end: String = format!("-----END {}-----", label);
+    let mut blocks: Vec<String> = vec![];
+    let mut rest: &str = pem;
+
+    while let Some(start) = rest.find(&begin) {
+        rest = &rest[start..];
+        let block_end: usize = match rest.find(&end) {
+            Some(block_end) => block_end + end.len(),
+            None => break,
+        };
+        blocks.push(format!("{}\n", &rest[..block_end]));
+        rest = &rest[block_end..];
+    }
+
+    blocks
+}
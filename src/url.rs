@@ -2,11 +2,16 @@ use base64;
 use percent_encoding::percent_decode_str;
 use url::Url;
 
-use crate::utils::{detect_media_type, parse_content_type};
+use crate::directory::{create_asset_file_name, ASSETS_DIRECTORY};
+use crate::opts::{Options, OutputFormat};
+use crate::session::Session;
+use crate::utils::{detect_media_type, parse_content_type, Resource};
 
 pub const EMPTY_IMAGE_DATA_URL: &'static str = "data:image/png;base64,\
 iVBORw0KGgoAAAANSUhEUgAAAA0AAAANCAQAAADY4iz3AAAAEUlEQVR42mNkwAkYR6UolgIACvgADsuK6xYAAAAASUVORK5CYII=";
 
+const MAX_SLUG_LENGTH: usize = 100;
+
 pub fn clean_url(url: Url) -> Url {
     let mut url = url.clone();
 
@@ -38,6 +43,97 @@ pub fn create_data_url(media_type: &str, charset: &str, data: &[u8], final_asset
     data_url
 }
 
+// Turns path and query of given URL into something that can be used as a file name
+pub fn create_path_slug(url: &Url) -> String {
+    let mut path: String = url.path().to_string();
+    for extension in [".html", ".htm"] {
+        if path.to_lowercase().ends_with(extension) {
+            path.truncate(path.len() - extension.len());
+        }
+    }
+    if let Some(query) = url.query() {
+        path = format!("{}-{}", path, query);
+    }
+
+    let slug: String = create_slug(&percent_decode_str(&path).decode_utf8_lossy());
+
+    if slug.is_empty() {
+        "index".to_string()
+    } else {
+        slug
+    }
+}
+
+// Joins words of given text with dashes, leaving out everything else
+pub fn create_slug(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+        .chars()
+        .take(MAX_SLUG_LENGTH)
+        .collect()
+}
+
+// Makes asset available to the document, returns what it should be referred to as;
+// depth tells whether it's referred to by the document itself or by one of its assets
+pub fn embed_asset(
+    session: &mut Session,
+    media_type: &str,
+    charset: &str,
+    data: &[u8],
+    final_asset_url: &Url,
+    fragment: Option<&str>,
+    options: &Options,
+    depth: u32,
+) -> String {
+    if options.output_format == OutputFormat::Html || final_asset_url.scheme() == "data" {
+        let mut data_url: Url = create_data_url(media_type, charset, data, final_asset_url);
+        data_url.set_fragment(fragment);
+        return data_url.to_string();
+    }
+
+    // Store asset as a separate part and refer to it by its URL
+    let location: Url = clean_url(final_asset_url.clone());
+    let resources: &mut Vec<Resource> = &mut session.document.resources;
+    if !resources.iter().any(|resource| resource.url == location) {
+        resources.push(Resource {
+            url: location.clone(),
+            media_type: if media_type.is_empty() {
+                detect_media_type(data, final_asset_url)
+            } else {
+                media_type.to_string()
+            },
+            charset: charset.to_string(),
+            data: data.to_vec(),
+        });
+    }
+
+    // Assets saved into a directory are referred to by relative paths,
+    // ones referred to by other assets are stored alongside them
+    if options.output_format == OutputFormat::Dir {
+        let resource: &Resource = resources
+            .iter()
+            .find(|resource| resource.url == location)
+            .unwrap();
+        let file_name: String =
+            create_asset_file_name(&resource.media_type, &resource.data, &resource.url);
+        let mut reference: String = if depth == 0 {
+            format!("{}/{}", ASSETS_DIRECTORY, file_name)
+        } else {
+            file_name
+        };
+        if let Some(fragment) = fragment {
+            reference = format!("{}#{}", reference, fragment);
+        }
+        return reference;
+    }
+
+    let mut reference: Url = location;
+    reference.set_fragment(fragment);
+    reference.to_string()
+}
+
 pub fn is_url_and_has_protocol(input: &str) -> bool {
     match Url::parse(&input) {
         Ok(parsed_url) => {
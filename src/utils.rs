@@ -1,15 +1,21 @@
-use reqwest::blocking::Client;
-use reqwest::header::{CONTENT_TYPE, COOKIE};
-use std::collections::HashMap;
-use std::fs;
-use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 use url::Url;
 
+use crate::cache::{parse_freshness, read_cache_entry, write_cache_entry, CacheEntry, Freshness};
+use crate::fetcher::{AssetFetcher, AssetRequest, FetchedAsset};
 use crate::opts::Options;
-use crate::url::{clean_url, parse_data_url};
+use crate::report::record_cache_hit;
+use crate::reporter::{report, Event};
+use crate::session::Session;
+use crate::url::clean_url;
 
-const ANSI_COLOR_RED: &'static str = "\x1b[31m";
-const ANSI_COLOR_RESET: &'static str = "\x1b[0m";
 const MAGIC: [[&[u8]; 2]; 18] = [
     // Image
     [b"GIF87a", b"image/gif"],
@@ -33,12 +39,99 @@ const MAGIC: [[&[u8]; 2]; 18] = [
     [b"....moov", b"video/quicktime"],
     [b"\x1A\x45\xDF\xA3", b"video/webm"],
 ];
+// Delay before the first retry, doubled with each subsequent one
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+// Neither backoff nor Retry-After can make monolith wait longer than this
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
 const PLAINTEXT_MEDIA_TYPES: &[&str] = &[
     "application/javascript",
     "application/json",
     "image/svg+xml",
 ];
 
+// Clonable so that failures can be remembered along with why they happened
+#[derive(Clone, Debug)]
+pub enum AssetError {
+    // Asset's domain is excluded from being retrieved
+    Blocked,
+    // Local asset does not exist
+    NotFound,
+    // Server responded with unacceptable status code
+    HttpStatus(u16),
+    // Request could not be completed
+    Network(Arc<reqwest::Error>),
+    // Asset does not match its integrity attribute
+    Integrity,
+    // Local asset could not be read
+    Io(Arc<io::Error>),
+    // Local asset was requested by non-local document
+    SecurityPolicy,
+    // Stylesheet could not be parsed
+    InvalidCss,
+}
+
+// HTTP status code (404) or class of them (4xx), used for deciding which responses to accept
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusPattern {
+    Code(u16),
+    Class(u16),
+}
+
+impl StatusPattern {
+    pub fn matches(&self, status: u16) -> bool {
+        match self {
+            StatusPattern::Code(code) => status == *code,
+            StatusPattern::Class(class) => status / 100 == *class,
+        }
+    }
+}
+
+// Asset stored alongside the document instead of being embedded into it
+#[derive(Clone, Debug)]
+pub struct Resource {
+    pub url: Url,
+    pub media_type: String,
+    pub charset: String,
+    pub data: Vec<u8>,
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetError::Blocked => write!(f, "blocked domain"),
+            AssetError::NotFound => write!(f, "not found"),
+            AssetError::HttpStatus(status) => write!(f, "HTTP status {}", status),
+            AssetError::Network(error) => write!(f, "network error: {}", error),
+            AssetError::Integrity => write!(f, "integrity check failed"),
+            AssetError::Io(error) => write!(f, "I/O error: {}", error),
+            AssetError::SecurityPolicy => write!(f, "security error"),
+            AssetError::InvalidCss => write!(f, "invalid CSS"),
+        }
+    }
+}
+
+impl Error for AssetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AssetError::Network(error) => Some(error.as_ref()),
+            AssetError::Io(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AssetError {
+    fn from(error: io::Error) -> Self {
+        AssetError::Io(Arc::new(error))
+    }
+}
+
+impl From<reqwest::Error> for AssetError {
+    fn from(error: reqwest::Error) -> Self {
+        AssetError::Network(Arc::new(error))
+    }
+}
+
 pub fn detect_media_type(data: &[u8], url: &Url) -> String {
     // At first attempt to read file's header
     for magic_item in MAGIC.iter() {
@@ -69,6 +162,7 @@ pub fn detect_media_type_by_file_name(filename: &str) -> String {
             "jpeg" | "jpg" => "image/jpeg",
             "js" => "application/javascript",
             "json" => "application/json",
+            "mht" | "mhtml" => "multipart/related",
             "mp3" => "audio/mpeg",
             "mp4" | "m4v" => "video/mp4",
             "ogg" => "audio/ogg",
@@ -193,218 +287,498 @@ pub fn parse_content_type(content_type: &str) -> (String, String, bool) {
 }
 
 pub fn retrieve_asset(
-    cache: &mut HashMap<String, Vec<u8>>,
-    client: &Client,
+    session: &mut Session,
+    fetcher: &dyn AssetFetcher,
     parent_url: &Url,
     url: &Url,
     options: &Options,
     depth: u32,
-) -> Result<(Vec<u8>, Url, String, String), reqwest::Error> {
+) -> Result<(Vec<u8>, Url, String, String), AssetError> {
+    // Assets are only being looked for, they get retrieved later
+    if let Some(collected_urls) = &mut session.collected_urls {
+        collected_urls.push(url.clone());
+        return Err(AssetError::Blocked);
+    }
+
     if url.scheme() == "data" {
-        let (media_type, charset, data) = parse_data_url(url);
-        Ok((data, url.clone(), media_type, charset))
+        let asset: FetchedAsset = fetcher.fetch(&AssetRequest::new(parent_url, url), options)?;
+        Ok((asset.data, asset.final_url, asset.media_type, asset.charset))
     } else if url.scheme() == "file" {
         // Check if parent_url is also a file: URL (if not, then we don't embed the asset)
         if parent_url.scheme() != "file" {
-            if !options.silent {
-                eprintln!(
-                    "{}{}{} ({}){}",
-                    indent(depth).as_str(),
-                    if options.no_color { "" } else { ANSI_COLOR_RED },
-                    &url,
-                    "Security Error",
-                    if options.no_color {
-                        ""
-                    } else {
-                        ANSI_COLOR_RESET
-                    },
-                );
-            }
-            // Provoke error
-            client.get("").send()?;
+            return Err(report_failure(
+                session,
+                url,
+                AssetError::SecurityPolicy,
+                options,
+                depth,
+            ));
         }
 
-        let path_buf: PathBuf = url.to_file_path().unwrap().clone();
-        let path: &Path = path_buf.as_path();
-        if path.exists() {
-            if path.is_dir() {
-                if !options.silent {
-                    eprintln!(
-                        "{}{}{} (is a directory){}",
-                        indent(depth).as_str(),
-                        if options.no_color { "" } else { ANSI_COLOR_RED },
-                        &url,
-                        if options.no_color {
-                            ""
-                        } else {
-                            ANSI_COLOR_RESET
-                        },
-                    );
-                }
-
-                // Provoke error
-                Err(client.get("").send().unwrap_err())
-            } else {
-                if !options.silent {
-                    eprintln!("{}{}", indent(depth).as_str(), &url);
-                }
-
-                let file_blob: Vec<u8> = fs::read(&path).expect("Unable to read file");
-
-                Ok((
-                    file_blob.clone(),
-                    url.clone(),
-                    detect_media_type(&file_blob, url),
-                    "".to_string(),
-                ))
-            }
-        } else {
-            if !options.silent {
-                eprintln!(
-                    "{}{}{} (not found){}",
-                    indent(depth).as_str(),
-                    if options.no_color { "" } else { ANSI_COLOR_RED },
-                    &url,
-                    if options.no_color {
-                        ""
-                    } else {
-                        ANSI_COLOR_RESET
-                    },
+        report(session, options, Event::AssetStarted { url, depth });
+
+        match fetcher.fetch(&AssetRequest::new(parent_url, url), options) {
+            Ok(asset) => {
+                report_success(
+                    session,
+                    url,
+                    &asset.final_url,
+                    asset.data.len(),
+                    false,
+                    options,
+                    depth,
                 );
+                Ok((asset.data, asset.final_url, asset.media_type, asset.charset))
             }
-
-            // Provoke error
-            Err(client.get("").send().unwrap_err())
+            Err(error) => Err(report_failure(session, url, error, options, depth)),
         }
     } else {
         let cache_key: String = clean_url(url.clone()).as_str().to_string();
 
-        if cache.contains_key(&cache_key) {
+        if let Some(cache_entry) = session.cache.get(&cache_key).cloned() {
             // URL is in cache, we get and return it
-            if !options.silent {
-                eprintln!("{}{} (from cache)", indent(depth).as_str(), &url);
-            }
+            report_success(
+                session,
+                url,
+                &cache_entry.final_url,
+                cache_entry.data.len(),
+                true,
+                options,
+                depth,
+            );
 
             Ok((
-                cache.get(&cache_key).unwrap().to_vec(),
-                url.clone(),
-                "".to_string(),
-                "".to_string(),
+                cache_entry.data,
+                cache_entry.final_url,
+                cache_entry.media_type,
+                cache_entry.charset,
             ))
+        } else if let Some(error) = session.document.errors.get(&cache_key) {
+            // Already failed for this document (e.g. ahead of embedding it), has been reported
+            Err(error.clone())
         } else {
             if let Some(domains) = &options.domains {
-                let domain_matches = domains
-                    .iter()
-                    .any(|d| domain_is_within_domain(url.host_str().unwrap(), &d.trim()));
+                let domain_matches = domains.iter().any(|d| {
+                    domain_is_within_domain(url.host_str().unwrap_or_default(), &d.trim())
+                });
                 if (options.blacklist_domains && domain_matches)
                     || (!options.blacklist_domains && !domain_matches)
                 {
-                    return Err(client.get("").send().unwrap_err());
+                    return Err(report_failure(
+                        session,
+                        url,
+                        AssetError::Blocked,
+                        options,
+                        depth,
+                    ));
                 }
             }
 
+            report(session, options, Event::AssetStarted { url, depth });
+
+            // Look for a copy of this asset saved to disk during one of the previous runs,
+            // unless archiving, as reused copies and 304 responses can't be recorded or replayed
+            let is_recording: bool = options.warc.is_some() || options.har.is_some();
+            let stored_cache_entry: Option<CacheEntry> = options
+                .cache_dir
+                .as_ref()
+                .filter(|_| !is_recording)
+                .and_then(|cache_dir| read_cache_entry(cache_dir, &cache_key))
+                .filter(|cache_entry| cache_entry.is_fresh() || cache_entry.can_be_revalidated());
+
+            // Fresh copies are used as they are, without asking the server
+            if let Some(cache_entry) = stored_cache_entry.clone().filter(CacheEntry::is_fresh) {
+                return Ok(reuse_stored_cache_entry(
+                    session,
+                    url,
+                    cache_key,
+                    cache_entry,
+                    options,
+                    depth,
+                ));
+            }
+
             // URL not in cache, we retrieve the file
-            let mut request = client.get(url.as_str());
-            if options.cookie_file.is_some() {
-                if let Some(cookie) = options
-                    .__cookies
-                    .iter()
-                    .find(|c| !c.is_expired() && c.matches_url(url.as_str()))
-                {
-                    request = request.header(COOKIE, cookie.encoded());
-                }
+            let mut request: AssetRequest = AssetRequest::new(parent_url, url);
+            if let Some(stored_cache_entry) = &stored_cache_entry {
+                request.etag = stored_cache_entry.etag.as_deref();
+                request.last_modified = stored_cache_entry.last_modified.as_deref();
             }
 
-            match request.send() {
-                Ok(response) => {
-                    if !options.ignore_errors && response.status() != reqwest::StatusCode::OK {
-                        if !options.silent {
-                            eprintln!(
-                                "{}{}{} ({}){}",
-                                indent(depth).as_str(),
-                                if options.no_color { "" } else { ANSI_COLOR_RED },
-                                &url,
-                                response.status(),
-                                if options.no_color {
-                                    ""
-                                } else {
-                                    ANSI_COLOR_RESET
-                                },
-                            );
+            match fetch_with_retries(session, fetcher, &request, options, depth) {
+                Ok(asset) => {
+                    if asset.is_not_modified() {
+                        if let Some(mut cache_entry) = stored_cache_entry {
+                            // Servers may extend freshness of the copy when revalidating it
+                            if let Freshness::FreshUntil(fresh_until) = parse_freshness(
+                                asset.cache_control.as_deref(),
+                                asset.expires.as_deref(),
+                            ) {
+                                cache_entry.fresh_until = Some(fresh_until);
+                                if let Some(cache_dir) = &options.cache_dir {
+                                    let _ = write_cache_entry(cache_dir, &cache_key, &cache_entry);
+                                }
+                            }
+
+                            return Ok(reuse_stored_cache_entry(
+                                session,
+                                url,
+                                cache_key,
+                                cache_entry,
+                                options,
+                                depth,
+                            ));
                         }
-                        // Provoke error
-                        return Err(client.get("").send().unwrap_err());
                     }
 
-                    let response_url: Url = response.url().clone();
+                    if !is_status_acceptable(asset.status, options) {
+                        let error: AssetError = AssetError::HttpStatus(asset.status);
+                        session.document.errors.insert(cache_key, error.clone());
+                        return Err(report_failure(session, url, error, options, depth));
+                    }
+
+                    report_success(
+                        session,
+                        url,
+                        &asset.final_url,
+                        asset.data.len(),
+                        false,
+                        options,
+                        depth,
+                    );
+
+                    let new_cache_key: String = clean_url(asset.final_url.clone()).to_string();
 
-                    if !options.silent {
-                        if url.as_str() == response_url.as_str() {
-                            eprintln!("{}{}", indent(depth).as_str(), &url);
-                        } else {
-                            eprintln!("{}{} -> {}", indent(depth).as_str(), &url, &response_url);
+                    // Add retrieved resource to cache
+                    let mut cache_entry = CacheEntry::new(
+                        asset.data.clone(),
+                        asset.final_url.clone(),
+                        asset.media_type.clone(),
+                        asset.charset.clone(),
+                    );
+                    cache_entry.etag = asset.etag;
+                    cache_entry.last_modified = asset.last_modified;
+                    let freshness: Freshness =
+                        parse_freshness(asset.cache_control.as_deref(), asset.expires.as_deref());
+                    if let Freshness::FreshUntil(fresh_until) = freshness {
+                        cache_entry.fresh_until = Some(fresh_until);
+                    }
+                    if let Some(cache_dir) = &options.cache_dir {
+                        let is_success: bool = (200..300).contains(&asset.status);
+                        // Copies which could be neither reused nor revalidated aren't worth keeping,
+                        // no-store only concerns the disk, memory cache is gone once saving is done
+                        if is_success
+                            && freshness != Freshness::NoStore
+                            && (cache_entry.is_fresh() || cache_entry.can_be_revalidated())
+                        {
+                            // The disk cache is a best-effort optimization, failing to write it is fine
+                            let _ = write_cache_entry(cache_dir, &cache_key, &cache_entry);
                         }
                     }
+                    if new_cache_key != cache_key {
+                        // Remember where redirected URLs lead to
+                        session.cache.insert(cache_key, cache_entry.clone());
+                    }
+                    session.cache.insert(new_cache_key, cache_entry);
 
-                    let new_cache_key: String = clean_url(response_url.clone()).to_string();
+                    // Return
+                    Ok((asset.data, asset.final_url, asset.media_type, asset.charset))
+                }
+                Err(error) => {
+                    session.document.errors.insert(cache_key, error.clone());
+                    Err(report_failure(session, url, error, options, depth))
+                }
+            }
+        }
+    }
+}
 
-                    // Attempt to obtain media type and charset by reading Content-Type header
-                    let content_type: &str = response
-                        .headers()
-                        .get(CONTENT_TYPE)
-                        .and_then(|header| header.to_str().ok())
-                        .unwrap_or("");
+// Comma-separated list of status codes and classes, e.g. "404,410,5xx"
+pub fn parse_status_patterns(list: &str) -> Option<Vec<StatusPattern>> {
+    list.split(',')
+        .map(|pattern| pattern.trim().to_lowercase())
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| match pattern.strip_suffix("xx") {
+            Some(class) => match class.parse::<u16>() {
+                Ok(class) if (1..=5).contains(&class) => Some(StatusPattern::Class(class)),
+                _ => None,
+            },
+            None => match pattern.parse::<u16>() {
+                Ok(code) if (100..=599).contains(&code) => Some(StatusPattern::Code(code)),
+                _ => None,
+            },
+        })
+        .collect()
+}
 
-                    let (media_type, charset, _is_base64) = parse_content_type(&content_type);
+// Statuses listed in --fail-on-status are never accepted, 2xx and ones listed in --accept-status always are
+pub fn is_status_acceptable(status: u16, options: &Options) -> bool {
+    let is_listed = |list: &Option<String>| {
+        list.as_deref()
+            .and_then(parse_status_patterns)
+            .unwrap_or_default()
+            .iter()
+            .any(|pattern| pattern.matches(status))
+    };
 
-                    // Convert response into a byte array
-                    let mut data: Vec<u8> = vec![];
-                    match response.bytes() {
-                        Ok(b) => {
-                            data = b.to_vec();
-                        }
-                        Err(error) => {
-                            if !options.silent {
-                                eprintln!(
-                                    "{}{}{}{}",
-                                    indent(depth).as_str(),
-                                    if options.no_color { "" } else { ANSI_COLOR_RED },
-                                    error,
-                                    if options.no_color {
-                                        ""
-                                    } else {
-                                        ANSI_COLOR_RESET
-                                    },
+    if is_listed(&options.fail_on_status) {
+        false
+    } else if (200..300).contains(&status) || is_listed(&options.accept_status) {
+        true
+    } else if options.keep_error_urls && (400..600).contains(&status) {
+        // Error pages are better left on the server than embedded in place of assets
+        false
+    } else {
+        options.ignore_errors
+    }
+}
+
+// Repeats requests that failed for reasons likely to go away, waiting longer every time
+fn fetch_with_retries(
+    session: &Session,
+    fetcher: &dyn AssetFetcher,
+    request: &AssetRequest,
+    options: &Options,
+    depth: u32,
+) -> Result<FetchedAsset, AssetError> {
+    let mut attempt: u32 = 0;
+
+    loop {
+        let result: Result<FetchedAsset, AssetError> = fetcher.fetch(request, options);
+        if attempt >= options.retries || !is_transient_failure(&result) {
+            return result;
+        }
+        attempt += 1;
+
+        let retry_after: Option<Duration> = result
+            .as_ref()
+            .ok()
+            .and_then(|asset| asset.http.as_ref())
+            .and_then(|http| {
+                http.response_headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case("retry-after"))
+            })
+            .and_then(|(_, value)| parse_retry_after(value, Utc::now()));
+        let delay: Duration = retry_delay(attempt, retry_after, rand::random::<f64>());
+        let error: AssetError = match result {
+            Ok(asset) => AssetError::HttpStatus(asset.status),
+            Err(error) => error,
+        };
+
+        report(
+            session,
+            options,
+            Event::AssetRetrying {
+                url: request.url,
+                error: &error,
+                attempt,
+                retries: options.retries,
+                delay,
+                depth,
+            },
+        );
+
+        thread::sleep(delay);
+    }
+}
+
+// Connection errors, timeouts, rate limiting, and server errors are worth another try
+pub fn is_transient_failure(result: &Result<FetchedAsset, AssetError>) -> bool {
+    match result {
+        Ok(asset) => asset.status == 429 || (500..600).contains(&asset.status),
+        Err(AssetError::Network(error)) => {
+            error.is_connect() || error.is_timeout() || error.is_request() || error.is_body()
+        }
+        Err(_) => false,
+    }
+}
+
+// Retry-After is either a number of seconds or an HTTP date
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value: &str = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date: DateTime<Utc> = DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .with_timezone(&Utc);
+    // Dates in the past mean it's fine to retry right away
+    Some((date - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+// Exponential backoff with jitter (0.0 to 1.0) spreading retries of parallel requests apart,
+// unless the server said when to come back
+pub fn retry_delay(attempt: u32, retry_after: Option<Duration>, jitter: f64) -> Duration {
+    if let Some(retry_after) = retry_after {
+        return retry_after.min(RETRY_MAX_DELAY);
+    }
+
+    let backoff: Duration = RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(RETRY_MAX_DELAY);
+
+    backoff / 2 + (backoff / 2).mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+// Assets left out on purpose (blocked domains, local files referred to by remote documents)
+// don't count as failures
+pub fn record_failed_asset(session: &mut Session, url: &Url, error: &AssetError) {
+    if matches!(error, AssetError::Blocked | AssetError::SecurityPolicy) {
+        return;
+    }
+
+    let failed_assets: &mut Vec<(Url, AssetError)> = &mut session.document.failed_assets;
+    if !failed_assets
+        .iter()
+        .any(|(failed_url, _)| failed_url == url)
+    {
+        failed_assets.push((url.clone(), error.clone()));
+    }
+}
+
+// Uses copy of the asset kept on disk, from now on it's also in memory
+fn reuse_stored_cache_entry(
+    session: &mut Session,
+    url: &Url,
+    cache_key: String,
+    cache_entry: CacheEntry,
+    options: &Options,
+    depth: u32,
+) -> (Vec<u8>, Url, String, String) {
+    report_success(
+        session,
+        url,
+        &cache_entry.final_url,
+        cache_entry.data.len(),
+        true,
+        options,
+        depth,
+    );
+
+    session.cache.insert(cache_key, cache_entry.clone());
+
+    (
+        cache_entry.data,
+        cache_entry.final_url,
+        cache_entry.media_type,
+        cache_entry.charset,
+    )
+}
+
+fn report_success(
+    session: &mut Session,
+    url: &Url,
+    final_url: &Url,
+    bytes: usize,
+    from_cache: bool,
+    options: &Options,
+    depth: u32,
+) {
+    record_cache_hit(session, options, url, from_cache);
+
+    report(
+        session,
+        options,
+        Event::AssetFinished {
+            url,
+            final_url,
+            bytes,
+            from_cache,
+            depth,
+        },
+    );
+}
+
+pub fn report_failure(
+    session: &Session,
+    url: &Url,
+    error: AssetError,
+    options: &Options,
+    depth: u32,
+) -> AssetError {
+    report(
+        session,
+        options,
+        Event::AssetFailed {
+            url,
+            error: &error,
+            depth,
+        },
+    );
+
+    error
+}
+
+pub fn retrieve_assets(
+    session: &mut Session,
+    fetcher: &dyn AssetFetcher,
+    assets: &[(Url, Url)],
+    options: &Options,
+    depth: u32,
+) {
+    // Only remote assets benefit from being retrieved in parallel
+    let mut seen_cache_keys: HashSet<String> = HashSet::new();
+    let mut queue: Vec<(Url, Url)> = vec![];
+    for (parent_url, url) in assets {
+        if url.scheme() != "http" && url.scheme() != "https" {
+            continue;
+        }
+
+        let cache_key: String = clean_url(url.clone()).as_str().to_string();
+        if !session.cache.contains_key(&cache_key)
+            && !session.document.errors.contains_key(&cache_key)
+            && seen_cache_keys.insert(cache_key)
+        {
+            queue.push((parent_url.clone(), url.clone()));
+        }
+    }
+
+    // Preserve document order, since workers take assets from the end of the queue
+    queue.reverse();
+
+    let jobs: usize = std::cmp::min(std::cmp::max(options.jobs, 1), queue.len());
+    let worker_sessions: Vec<Session> = (0..jobs).map(|_| session.fork()).collect();
+    let queue: Mutex<Vec<(Url, Url)>> = Mutex::new(queue);
+    let worker_sessions: Vec<Session> = thread::scope(|scope| {
+        let workers: Vec<_> = worker_sessions
+            .into_iter()
+            .map(|mut worker_session| {
+                let queue: &Mutex<Vec<(Url, Url)>> = &queue;
+                scope.spawn(move || {
+                    loop {
+                        let next_asset = queue.lock().unwrap().pop();
+                        match next_asset {
+                            Some((parent_url, url)) => {
+                                // Failures get reported and remembered here, assets which
+                                // failed don't get requested again when they're embedded
+                                let _ = retrieve_asset(
+                                    &mut worker_session,
+                                    fetcher,
+                                    &parent_url,
+                                    &url,
+                                    options,
+                                    depth,
                                 );
                             }
+                            None => break,
                         }
                     }
 
-                    // Add retrieved resource to cache
-                    cache.insert(new_cache_key, data.clone());
+                    worker_session
+                })
+            })
+            .collect();
 
-                    // Return
-                    Ok((data, response_url, media_type, charset))
-                }
-                Err(error) => {
-                    if !options.silent {
-                        eprintln!(
-                            "{}{}{} ({}){}",
-                            indent(depth).as_str(),
-                            if options.no_color { "" } else { ANSI_COLOR_RED },
-                            &url,
-                            error,
-                            if options.no_color {
-                                ""
-                            } else {
-                                ANSI_COLOR_RESET
-                            },
-                        );
-                    }
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("Asset retrieval worker panicked"))
+            .collect()
+    });
 
-                    Err(client.get("").send().unwrap_err())
-                }
-            }
-        }
+    for worker_session in worker_sessions {
+        session.merge(worker_session);
     }
 }
@@ -0,0 +1,563 @@
+use brotli_decompressor::Decompressor;
+use chrono::prelude::*;
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use reqwest::StatusCode;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+use crate::fetcher::{
+    fetch_data_url, fetch_local_file, AssetFetcher, AssetRequest, FetchedAsset, HttpExchange,
+    MAX_REDIRECTS,
+};
+use crate::opts::Options;
+use crate::utils::{parse_content_type, AssetError};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const WARC_VERSION: &str = "WARC/1.1";
+
+struct WarcOutput {
+    output: Box<dyn Write + Send>,
+    // First error encountered while writing, reported once the archive is finished
+    error: Option<io::Error>,
+}
+
+pub struct WarcWriter {
+    output: Mutex<WarcOutput>,
+    // Compress each record as a separate gzip member (.warc.gz)
+    compress: bool,
+}
+
+impl WarcWriter {
+    pub fn new(output: Box<dyn Write + Send>, compress: bool, filename: &str) -> WarcWriter {
+        let writer = WarcWriter {
+            output: Mutex::new(WarcOutput {
+                output,
+                error: None,
+            }),
+            compress,
+        };
+
+        let fields: String = format!(
+            "software: {}/{}\r\n\
+            format: WARC File Format 1.1\r\n\
+            conformsTo: http://iipc.github.io/warc-specifications/specifications/warc-format/warc-1.1/\r\n",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+        );
+        writer.write_records(&[create_record(
+            vec![
+                ("WARC-Type", "warcinfo".to_string()),
+                ("WARC-Record-ID", create_record_id()),
+                ("WARC-Date", create_warc_date()),
+                ("WARC-Filename", filename.to_string()),
+                ("Content-Type", "application/warc-fields".to_string()),
+            ],
+            fields.as_bytes(),
+        )]);
+
+        writer
+    }
+
+    pub fn create(file_path: &str) -> io::Result<WarcWriter> {
+        let file = fs::File::create(file_path)?;
+        let filename: String = file_path
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(file_path)
+            .to_string();
+
+        Ok(WarcWriter::new(
+            Box::new(BufWriter::new(file)),
+            file_path.ends_with(".gz"),
+            &filename,
+        ))
+    }
+
+    // Saves request and response records of every HTTP exchange it took to retrieve the asset
+    pub fn write_exchanges(&self, asset: &FetchedAsset) {
+        for redirect in &asset.redirects {
+            self.write_exchange(redirect);
+        }
+        self.write_exchange(asset);
+    }
+
+    // Saves request and response records of a single HTTP exchange
+    pub fn write_exchange(&self, asset: &FetchedAsset) {
+        let http: &HttpExchange = match &asset.http {
+            Some(http) => http,
+            None => return,
+        };
+
+        let response_id: String = create_record_id();
+        let date: String = create_warc_date();
+        let target_uri: String = asset.final_url.to_string();
+
+        let request_block: Vec<u8> = create_request_block(&asset.final_url, http);
+        let response_block: Vec<u8> = create_response_block(asset, http);
+
+        self.write_records(&[
+            create_record(
+                vec![
+                    ("WARC-Type", "response".to_string()),
+                    ("WARC-Record-ID", response_id.clone()),
+                    ("WARC-Date", date.clone()),
+                    ("WARC-Target-URI", target_uri.clone()),
+                    (
+                        "Content-Type",
+                        "application/http;msgtype=response".to_string(),
+                    ),
+                    ("WARC-Payload-Digest", create_digest(&asset.data)),
+                    ("WARC-Block-Digest", create_digest(&response_block)),
+                ],
+                &response_block,
+            ),
+            create_record(
+                vec![
+                    ("WARC-Type", "request".to_string()),
+                    ("WARC-Record-ID", create_record_id()),
+                    ("WARC-Date", date),
+                    ("WARC-Target-URI", target_uri),
+                    ("WARC-Concurrent-To", response_id),
+                    (
+                        "Content-Type",
+                        "application/http;msgtype=request".to_string(),
+                    ),
+                    ("WARC-Block-Digest", create_digest(&request_block)),
+                ],
+                &request_block,
+            ),
+        ]);
+    }
+
+    pub fn finish(&self) -> io::Result<()> {
+        let mut output = self.output.lock().unwrap();
+
+        if let Some(error) = output.error.take() {
+            return Err(error);
+        }
+
+        output.output.flush()
+    }
+
+    fn write_records(&self, records: &[Vec<u8>]) {
+        let mut output = self.output.lock().unwrap();
+
+        // Records of the same exchange are kept next to each other
+        for record in records {
+            if output.error.is_some() {
+                return;
+            }
+
+            let result: io::Result<()> = if self.compress {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder
+                    .write_all(record)
+                    .and_then(|_| encoder.finish())
+                    .and_then(|compressed| output.output.write_all(&compressed))
+            } else {
+                output.output.write_all(record)
+            };
+
+            if let Err(error) = result {
+                output.error = Some(error);
+            }
+        }
+    }
+}
+
+// Writes every HTTP exchange made by the wrapped fetcher into a WARC file
+pub struct WarcRecorder {
+    fetcher: Box<dyn AssetFetcher>,
+    writer: Arc<WarcWriter>,
+}
+
+impl WarcRecorder {
+    pub fn new(fetcher: Box<dyn AssetFetcher>, writer: Arc<WarcWriter>) -> WarcRecorder {
+        WarcRecorder { fetcher, writer }
+    }
+}
+
+impl AssetFetcher for WarcRecorder {
+    fn fetch(&self, request: &AssetRequest, options: &Options) -> Result<FetchedAsset, AssetError> {
+        let asset: FetchedAsset = self.fetcher.fetch(request, options)?;
+        self.writer.write_exchanges(&asset);
+        Ok(asset)
+    }
+}
+
+struct ReplayedResponse {
+    status: u16,
+    http_version: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl ReplayedResponse {
+    fn to_fetched_asset(&self, url: &Url) -> FetchedAsset {
+        let header = |name: &str| {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.clone())
+        };
+        let (media_type, charset, _is_base64) =
+            parse_content_type(&header("Content-Type").unwrap_or_default());
+
+        FetchedAsset {
+            data: self.body.clone(),
+            final_url: url.clone(),
+            media_type,
+            charset,
+            status: self.status,
+            etag: header("ETag"),
+            last_modified: header("Last-Modified"),
+            cache_control: header("Cache-Control"),
+            expires: header("Expires"),
+            http: Some(HttpExchange {
+                method: "GET".to_string(),
+                request_http_version: self.http_version.clone(),
+                request_headers: vec![],
+                http_version: self.http_version.clone(),
+                response_headers: self.headers.clone(),
+                wait: Duration::ZERO,
+                receive: Duration::ZERO,
+            }),
+            redirects: vec![],
+        }
+    }
+}
+
+// Answers asset lookups from response records of a WARC file instead of the network;
+// the whole archive gets read into memory (decoded bodies of responses), which limits
+// how large archives it can be used with
+pub struct WarcReplay {
+    responses: HashMap<String, ReplayedResponse>,
+}
+
+impl WarcReplay {
+    pub fn open(file_path: &str) -> io::Result<WarcReplay> {
+        WarcReplay::parse(&fs::read(file_path)?)
+    }
+
+    pub fn parse(data: &[u8]) -> io::Result<WarcReplay> {
+        let mut decompressed: Vec<u8> = vec![];
+        let data: &[u8] = if data.starts_with(&[0x1F, 0x8B]) {
+            MultiGzDecoder::new(data).read_to_end(&mut decompressed)?;
+            &decompressed
+        } else {
+            data
+        };
+
+        let mut responses: HashMap<String, ReplayedResponse> = HashMap::new();
+        let mut position: usize = 0;
+        loop {
+            // Skip line breaks which separate records
+            while position < data.len() && (data[position] == b'\r' || data[position] == b'\n') {
+                position += 1;
+            }
+            if position >= data.len() {
+                break;
+            }
+
+            let header_length: usize = find_header_end(&data[position..])
+                .filter(|_| data[position..].starts_with(b"WARC/"))
+                .ok_or_else(|| invalid_data(format!("no WARC record at offset {}", position)))?;
+            let fields: Vec<(String, String)> =
+                parse_header_lines(&data[position..position + header_length]);
+            let field = |name: &str| {
+                fields
+                    .iter()
+                    .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                    .map(|(_, v)| v.as_str())
+            };
+
+            let block_start: usize = position + header_length;
+            let block_end: usize = field("Content-Length")
+                .and_then(|value| value.parse::<usize>().ok())
+                .map(|length| block_start + length)
+                .filter(|block_end| *block_end <= data.len())
+                .ok_or_else(|| invalid_data(format!("bad record length at offset {}", position)))?;
+
+            let is_http_response: bool = field("WARC-Type") == Some("response")
+                && field("Content-Type")
+                    .map(|content_type| content_type.starts_with("application/http"))
+                    .unwrap_or(false);
+            if is_http_response {
+                let target_url: Option<Url> = field("WARC-Target-URI").and_then(|uri| {
+                    Url::parse(uri.trim_start_matches('<').trim_end_matches('>')).ok()
+                });
+                if let (Some(target_url), Some(response)) = (
+                    target_url,
+                    parse_http_response(&data[block_start..block_end]),
+                ) {
+                    // Later captures of the same URL take precedence
+                    responses.insert(replay_key(&target_url), response);
+                }
+            }
+
+            position = block_end;
+        }
+
+        Ok(WarcReplay { responses })
+    }
+
+    pub fn contains(&self, url: &Url) -> bool {
+        self.responses.contains_key(&replay_key(url))
+    }
+
+    fn replay(&self, url: &Url) -> Result<FetchedAsset, AssetError> {
+        let mut redirects: Vec<FetchedAsset> = vec![];
+        let mut current_url: Url = url.clone();
+        current_url.set_fragment(None);
+
+        // Follow redirects the way they got recorded
+        loop {
+            let mut asset: FetchedAsset = self
+                .responses
+                .get(&replay_key(&current_url))
+                .map(|response| response.to_fetched_asset(&current_url))
+                .ok_or(AssetError::NotFound)?;
+
+            match asset.redirect_location() {
+                Some(next_url) if redirects.len() < MAX_REDIRECTS => {
+                    current_url = next_url;
+                    redirects.push(asset);
+                }
+                _ => {
+                    asset.redirects = redirects;
+                    return Ok(asset);
+                }
+            }
+        }
+    }
+}
+
+impl AssetFetcher for WarcReplay {
+    fn fetch(
+        &self,
+        request: &AssetRequest,
+        _options: &Options,
+    ) -> Result<FetchedAsset, AssetError> {
+        match request.url.scheme() {
+            "data" => Ok(fetch_data_url(request.url)),
+            "file" => fetch_local_file(request.url),
+            _ => self.replay(request.url),
+        }
+    }
+}
+
+pub fn create_digest(data: &[u8]) -> String {
+    let digest = Sha1::digest(data);
+    let mut encoded: String = String::new();
+    let mut buffer: u64 = 0;
+    let mut bits: u32 = 0;
+
+    for byte in digest.iter() {
+        buffer = (buffer << 8) | *byte as u64;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1F) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize] as char);
+    }
+
+    format!("sha1:{}", encoded)
+}
+
+fn create_record(mut headers: Vec<(&str, String)>, block: &[u8]) -> Vec<u8> {
+    headers.push(("Content-Length", block.len().to_string()));
+
+    let mut record: Vec<u8> = format!("{}\r\n", WARC_VERSION).into_bytes();
+    for (name, value) in headers {
+        record.extend_from_slice(format!("{}: {}\r\n", name, value).as_bytes());
+    }
+    record.extend_from_slice(b"\r\n");
+    record.extend_from_slice(block);
+    record.extend_from_slice(b"\r\n\r\n");
+
+    record
+}
+
+fn create_record_id() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    // Version 4 (random) UUID
+    bytes[6] = (bytes[6] & 0x0F) | 0x40;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "<urn:uuid:{}-{}-{}-{}-{}>",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn create_request_block(url: &Url, http: &HttpExchange) -> Vec<u8> {
+    let mut path: String = url.path().to_string();
+    if let Some(query) = url.query() {
+        path = format!("{}?{}", path, query);
+    }
+
+    let mut block: String = format!("{} {} {}\r\n", http.method, path, http.request_http_version);
+    for (name, value) in &http.request_headers {
+        block += &format!("{}: {}\r\n", name, value);
+    }
+    block += "\r\n";
+
+    block.into_bytes()
+}
+
+fn create_response_block(asset: &FetchedAsset, http: &HttpExchange) -> Vec<u8> {
+    let reason: String = StatusCode::from_u16(asset.status)
+        .ok()
+        .and_then(|status_code| status_code.canonical_reason())
+        .unwrap_or("")
+        .to_string();
+
+    let mut head: String = format!("{} {} {}\r\n", http.http_version, asset.status, reason);
+    for (name, value) in &http.response_headers {
+        // The payload is stored decoded, framing headers have to describe it as such
+        if name.eq_ignore_ascii_case("content-encoding")
+            || name.eq_ignore_ascii_case("content-length")
+            || name.eq_ignore_ascii_case("transfer-encoding")
+        {
+            continue;
+        }
+        head += &format!("{}: {}\r\n", name, value);
+    }
+    head += &format!("content-length: {}\r\n\r\n", asset.data.len());
+
+    let mut block: Vec<u8> = head.into_bytes();
+    block.extend_from_slice(&asset.data);
+
+    block
+}
+
+fn decode_body(body: Vec<u8>, headers: &[(String, String)]) -> Vec<u8> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.trim().to_ascii_lowercase())
+            .unwrap_or_default()
+    };
+
+    let mut body: Vec<u8> = body;
+    if header("Transfer-Encoding").contains("chunked") {
+        body = decode_chunked(&body).unwrap_or(body);
+    }
+
+    let mut decoded: Vec<u8> = vec![];
+    let result: io::Result<usize> = match header("Content-Encoding").as_str() {
+        "gzip" | "x-gzip" => MultiGzDecoder::new(&body[..]).read_to_end(&mut decoded),
+        "deflate" => ZlibDecoder::new(&body[..])
+            .read_to_end(&mut decoded)
+            .or_else(|_| {
+                decoded.clear();
+                DeflateDecoder::new(&body[..]).read_to_end(&mut decoded)
+            }),
+        "br" => Decompressor::new(&body[..], 4096).read_to_end(&mut decoded),
+        _ => return body,
+    };
+
+    // Keep the payload as is if it can't be decoded
+    match result {
+        Ok(_) => decoded,
+        Err(_) => body,
+    }
+}
+
+fn decode_chunked(data: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded: Vec<u8> = vec![];
+    let mut position: usize = 0;
+
+    loop {
+        // Records may be cut off anywhere, including right after chunk data
+        let line_end: usize = position
+            + data
+                .get(position..)?
+                .windows(2)
+                .position(|w| w == b"\r\n")?;
+        let size_line: String = String::from_utf8_lossy(&data[position..line_end]).to_string();
+        let size: usize = usize::from_str_radix(size_line.split(';').next()?.trim(), 16).ok()?;
+        position = line_end + 2;
+
+        if size == 0 {
+            return Some(decoded);
+        }
+
+        decoded.extend_from_slice(data.get(position..position.checked_add(size)?)?);
+        position += size + 2;
+    }
+}
+
+fn find_header_end(data: &[u8]) -> Option<usize> {
+    data.windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map(|position| position + 4)
+        .or_else(|| {
+            data.windows(2)
+                .position(|w| w == b"\n\n")
+                .map(|position| position + 2)
+        })
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Status or request line is skipped, the rest are "name: value" pairs
+fn parse_header_lines(header: &[u8]) -> Vec<(String, String)> {
+    String::from_utf8_lossy(header)
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn parse_http_response(block: &[u8]) -> Option<ReplayedResponse> {
+    let header_length: usize = find_header_end(block)?;
+    let status_line: String = String::from_utf8_lossy(&block[..header_length])
+        .lines()
+        .next()?
+        .to_string();
+    let mut status_line_parts = status_line.split_whitespace();
+    let http_version: String = status_line_parts.next()?.to_string();
+    let status: u16 = status_line_parts.next()?.parse().ok()?;
+    let headers: Vec<(String, String)> = parse_header_lines(&block[..header_length]);
+    let body: Vec<u8> = decode_body(block[header_length..].to_vec(), &headers);
+
+    Some(ReplayedResponse {
+        status,
+        http_version,
+        headers,
+        body,
+    })
+}
+
+// Fragments never reach servers, so they're not a part of recorded URLs either
+fn replay_key(url: &Url) -> String {
+    let mut url: Url = url.clone();
+    url.set_fragment(None);
+    url.to_string()
+}
+
+fn create_warc_date() -> String {
+    Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
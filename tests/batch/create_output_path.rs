@@ -0,0 +1,68 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use chrono::NaiveDate;
+    use reqwest::Url;
+
+    use monolith::batch;
+
+    #[test]
+    fn default_template() {
+        let url: Url = Url::parse("https://site.local/blog/first-post.html?page=2").unwrap();
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+
+        assert_eq!(
+            batch::create_output_path(
+                &format!("{}.html", batch::DEFAULT_OUTPUT_TEMPLATE),
+                "",
+                &url,
+                date
+            ),
+            "site.local/blog-first-post-page-2-2024-03-09.html"
+        );
+    }
+
+    #[test]
+    fn section() {
+        let url: Url = Url::parse("https://site.local/").unwrap();
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+
+        assert_eq!(
+            batch::create_output_path(
+                &format!("{}.html", batch::DEFAULT_OUTPUT_TEMPLATE),
+                "work/docs",
+                &url,
+                date
+            ),
+            "work/docs/site.local/index-2024-03-09.html"
+        );
+    }
+
+    #[test]
+    fn local_file() {
+        let url: Url = Url::parse("file:///home/user/index.html").unwrap();
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+
+        assert_eq!(
+            batch::create_output_path("{host}_{date}/{path_slug}", "", &url, date),
+            "local_2024-03-09/home-user-index"
+        );
+    }
+
+    #[test]
+    fn no_placeholders() {
+        let url: Url = Url::parse("https://site.local/").unwrap();
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+
+        assert_eq!(
+            batch::create_output_path("page.html", "news", &url, date),
+            "page.html"
+        );
+    }
+}
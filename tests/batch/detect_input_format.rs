@@ -0,0 +1,68 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+
+    use monolith::batch::{self, InputFormat};
+
+    #[test]
+    fn list() {
+        assert_eq!(
+            batch::detect_input_format("# Reading list\nhttps://site.local/\n"),
+            InputFormat::List
+        );
+    }
+
+    #[test]
+    fn bookmarks() {
+        let contents: String = fs::read_to_string("tests/_data_/batch/bookmarks.html").unwrap();
+
+        assert_eq!(
+            batch::detect_input_format(&contents),
+            InputFormat::Bookmarks
+        );
+    }
+
+    #[test]
+    fn sitemap() {
+        let contents: String = fs::read_to_string("tests/_data_/batch/sitemap.xml").unwrap();
+
+        assert_eq!(batch::detect_input_format(&contents), InputFormat::Sitemap);
+    }
+
+    #[test]
+    fn sitemap_index() {
+        assert_eq!(
+            batch::detect_input_format(
+                "\u{feff}<?xml version=\"1.0\"?>\n<sitemapindex><sitemap><loc>https://site.local/sitemap-posts.xml</loc></sitemap></sitemapindex>"
+            ),
+            InputFormat::SitemapIndex
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::batch::{self, InputFormat};
+
+    #[test]
+    fn regular_html_document() {
+        assert_eq!(
+            batch::detect_input_format("<!DOCTYPE html><html><body>Not found</body></html>"),
+            InputFormat::List
+        );
+    }
+}
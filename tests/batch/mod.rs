@@ -0,0 +1,5 @@
+mod create_output_path;
+mod detect_input_format;
+mod parse_bookmarks;
+mod parse_sitemap;
+mod parse_targets;
@@ -0,0 +1,64 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+
+    use monolith::batch::{self, BatchTarget};
+
+    #[test]
+    fn folders_as_sections() {
+        let contents: String = fs::read_to_string("tests/_data_/batch/bookmarks.html").unwrap();
+
+        assert_eq!(
+            batch::parse_bookmarks(&contents),
+            vec![
+                BatchTarget {
+                    url: "https://docs.site.local/".to_string(),
+                    section: "bookmarks-toolbar".to_string(),
+                },
+                BatchTarget {
+                    url: "https://blog.site.local/post?id=1".to_string(),
+                    section: "bookmarks-toolbar/team-friends".to_string(),
+                },
+                BatchTarget {
+                    url: "http://site.local/unsorted.html".to_string(),
+                    section: "".to_string(),
+                },
+            ]
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::batch;
+
+    #[test]
+    fn no_bookmarks() {
+        assert!(batch::parse_bookmarks(
+            "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n<H1>Bookmarks</H1>\n<DL><p>\n</DL>\n"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn unsupported_schemes() {
+        assert!(batch::parse_bookmarks(
+            "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n<DL><p>\n<DT><A HREF=\"javascript:alert(1)\">Bookmarklet</A>\n<DT><A HREF=\"about:blank\">Blank</A>\n</DL>\n"
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+
+    use monolith::batch;
+
+    #[test]
+    fn sitemap() {
+        let contents: String = fs::read_to_string("tests/_data_/batch/sitemap.xml").unwrap();
+
+        assert_eq!(
+            batch::parse_sitemap(&contents),
+            vec![
+                "https://site.local/",
+                "https://site.local/search?q=a&page=2",
+                "https://site.local/about",
+            ]
+        );
+    }
+
+    #[test]
+    fn sitemap_index() {
+        assert_eq!(
+            batch::parse_sitemap(
+                "<sitemapindex>\
+                 <sitemap><loc>https://site.local/sitemap-posts.xml.gz</loc></sitemap>\
+                 <sitemap><loc>https://site.local/sitemap&#x2D;pages.xml</loc></sitemap>\
+                 </sitemapindex>"
+            ),
+            vec![
+                "https://site.local/sitemap-posts.xml.gz",
+                "https://site.local/sitemap-pages.xml",
+            ]
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::batch;
+
+    #[test]
+    fn unclosed_loc() {
+        assert_eq!(
+            batch::parse_sitemap(
+                "<urlset><url><loc>https://site.local/</loc></url><url><loc>https://site.local/a"
+            ),
+            vec!["https://site.local/"]
+        );
+    }
+
+    #[test]
+    fn unknown_entity() {
+        assert_eq!(
+            batch::parse_sitemap(
+                "<urlset><url><loc>https://site.local/?a=1&b=2&nbsp;</loc></url></urlset>"
+            ),
+            vec!["https://site.local/?a=1&b=2&nbsp;"]
+        );
+    }
+}
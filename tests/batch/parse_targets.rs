@@ -0,0 +1,34 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::batch;
+
+    #[test]
+    fn one_target_per_line() {
+        assert_eq!(
+            batch::parse_targets("https://site.local/\nhttps://other.local/page.html\n"),
+            vec!["https://site.local/", "https://other.local/page.html"]
+        );
+    }
+
+    #[test]
+    fn skip_blank_lines_and_comments() {
+        assert_eq!(
+            batch::parse_targets(
+                "# news\n\n  https://site.local/  \r\n   \n#https://other.local/\n"
+            ),
+            vec!["https://site.local/"]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(batch::parse_targets(""), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,3 @@
+mod parse_freshness;
+mod read_cache_entry;
+mod write_cache_entry;
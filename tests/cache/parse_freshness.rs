@@ -0,0 +1,92 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use monolith::cache::{parse_freshness, Freshness};
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn max_age() {
+        match parse_freshness(Some("public, max-age=3600"), None) {
+            Freshness::FreshUntil(fresh_until) => {
+                assert!(fresh_until >= now() + 3599 && fresh_until <= now() + 3600)
+            }
+            freshness => panic!("unexpected {:?}", freshness),
+        }
+    }
+
+    #[test]
+    fn max_age_over_expires() {
+        match parse_freshness(
+            Some("Max-Age=\"60\""),
+            Some("Wed, 21 Oct 2099 07:28:00 GMT"),
+        ) {
+            Freshness::FreshUntil(fresh_until) => assert!(fresh_until <= now() + 60),
+            freshness => panic!("unexpected {:?}", freshness),
+        }
+    }
+
+    #[test]
+    fn expires() {
+        assert_eq!(
+            parse_freshness(None, Some("Wed, 21 Oct 2099 07:28:00 GMT")),
+            Freshness::FreshUntil(4096250880)
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::cache::{parse_freshness, Freshness};
+
+    #[test]
+    fn no_store() {
+        assert_eq!(
+            parse_freshness(Some("max-age=3600, no-store"), None),
+            Freshness::NoStore
+        );
+    }
+
+    #[test]
+    fn no_cache() {
+        assert_eq!(
+            parse_freshness(Some("no-cache, max-age=3600"), None),
+            Freshness::Stale
+        );
+    }
+
+    #[test]
+    fn expired() {
+        assert_eq!(parse_freshness(Some("max-age=0"), None), Freshness::Stale);
+        assert_eq!(
+            parse_freshness(None, Some("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Freshness::Stale
+        );
+        assert_eq!(parse_freshness(None, Some("0")), Freshness::Stale);
+    }
+
+    #[test]
+    fn no_headers() {
+        assert_eq!(parse_freshness(None, None), Freshness::Stale);
+    }
+}
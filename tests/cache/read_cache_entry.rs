@@ -0,0 +1,85 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::env;
+
+    use monolith::cache::{self, CacheEntry};
+
+    #[test]
+    fn reads_back_written_entry() {
+        let cache_dir = env::temp_dir().join("monolith-test-read-cache-entry");
+        let cache_dir: &str = cache_dir.to_str().unwrap();
+
+        let mut cache_entry = CacheEntry::new(
+            b"\n\nbinary\x00data".to_vec(),
+            Url::parse("https://cdn.site.local/font.woff2").unwrap(),
+            "font/woff2".to_string(),
+            "".to_string(),
+        );
+        cache_entry.etag = Some("\"abc123\"".to_string());
+        cache_entry.last_modified = Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string());
+        cache_entry.fresh_until = Some(4096250880);
+        cache::write_cache_entry(cache_dir, "https://site.local/font.woff2", &cache_entry).unwrap();
+
+        let read_entry =
+            cache::read_cache_entry(cache_dir, "https://site.local/font.woff2").unwrap();
+        assert_eq!(read_entry.data, b"\n\nbinary\x00data");
+        assert_eq!(
+            read_entry.final_url.as_str(),
+            "https://cdn.site.local/font.woff2"
+        );
+        assert_eq!(read_entry.media_type, "font/woff2");
+        assert_eq!(read_entry.charset, "");
+        assert_eq!(read_entry.etag, Some("\"abc123\"".to_string()));
+        assert_eq!(
+            read_entry.last_modified,
+            Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string())
+        );
+        assert_eq!(read_entry.fresh_until, Some(4096250880));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::env;
+    use std::fs;
+
+    use monolith::cache;
+
+    #[test]
+    fn missing_entry() {
+        let cache_dir = env::temp_dir().join("monolith-test-read-cache-entry-missing");
+        let cache_dir: &str = cache_dir.to_str().unwrap();
+
+        assert!(cache::read_cache_entry(cache_dir, "https://site.local/missing.png").is_none());
+    }
+
+    #[test]
+    fn entry_saved_under_different_key() {
+        let cache_dir = env::temp_dir().join("monolith-test-read-cache-entry-mismatch");
+        fs::create_dir_all(&cache_dir).unwrap();
+        let cache_dir: &str = cache_dir.to_str().unwrap();
+
+        fs::write(
+            cache::cache_file_path(cache_dir, "https://site.local/a.png"),
+            "key: https://site.local/b.png\nfinal-url: https://site.local/b.png\n\nb",
+        )
+        .unwrap();
+
+        assert!(cache::read_cache_entry(cache_dir, "https://site.local/a.png").is_none());
+    }
+}
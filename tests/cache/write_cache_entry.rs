@@ -0,0 +1,33 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::env;
+    use std::fs;
+
+    use monolith::cache::{self, CacheEntry};
+
+    #[test]
+    fn creates_cache_directory() {
+        let cache_dir = env::temp_dir().join("monolith-test-write-cache-entry");
+        let _ = fs::remove_dir_all(&cache_dir);
+        let cache_dir: &str = cache_dir.to_str().unwrap();
+
+        let cache_entry = CacheEntry::new(
+            b"body{}".to_vec(),
+            Url::parse("https://site.local/style.css").unwrap(),
+            "text/css".to_string(),
+            "utf-8".to_string(),
+        );
+        cache::write_cache_entry(cache_dir, "https://site.local/style.css", &cache_entry).unwrap();
+
+        assert!(cache::cache_file_path(cache_dir, "https://site.local/style.css").is_file());
+        assert_eq!(fs::read_dir(cache_dir).unwrap().count(), 1);
+    }
+}
@@ -12,6 +12,8 @@ mod passing {
     use std::fs;
     use std::path::Path;
     use std::process::{Command, Stdio};
+    use std::thread;
+    use std::time::Duration;
     use url::Url;
 
     #[test]
@@ -109,6 +111,78 @@ mod passing {
         // Exit code should be 0
         out.assert().code(0);
     }
+
+    #[test]
+    fn same_output_regardless_of_jobs() {
+        let server = crate::server::start(|request| {
+            // Later assets respond sooner, making them arrive out of document order
+            let delay: u64 = match request.path.as_str() {
+                "/img0.png" | "/style.css" => 150,
+                "/img1.png" | "/font.woff" => 100,
+                "/img2.png" | "/more.css" => 50,
+                _ => 0,
+            };
+            thread::sleep(Duration::from_millis(delay));
+
+            match request.path.as_str() {
+                "/" => crate::server::Response::new(
+                    200,
+                    "text/html",
+                    b"<link rel=\"stylesheet\" href=\"style.css\">\
+                      <img src=\"img0.png\"><img src=\"img1.png\"><img src=\"img2.png\">\
+                      <img src=\"img3.png\"><img src=\"img1.png\"><img src=\"missing.png\">\
+                      <div style=\"background: url(img2.png)\"></div>",
+                ),
+                "/style.css" => crate::server::Response::new(
+                    200,
+                    "text/css",
+                    b"@import url(more.css);\
+                      @font-face{font-family:F;src:url(font.woff)}\
+                      body{background:url(img3.png)}",
+                ),
+                "/more.css" => {
+                    crate::server::Response::new(200, "text/css", b"p{background:url(img0.png)}")
+                }
+                "/font.woff" => crate::server::Response::new(200, "font/woff", b"wOFF"),
+                "/img0.png" | "/img1.png" | "/img2.png" | "/img3.png" => {
+                    crate::server::Response::new(200, "image/png", request.path.as_bytes())
+                }
+                _ => crate::server::Response::new(404, "text/plain", b"Not found"),
+            }
+        });
+
+        let outputs: Vec<Vec<u8>> = ["1", "4"]
+            .iter()
+            .map(|jobs| {
+                let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+                let out = cmd
+                    .arg("-s")
+                    .arg("-M")
+                    .arg("--jobs")
+                    .arg(jobs)
+                    .arg(server.url("/").as_str())
+                    .output()
+                    .unwrap();
+
+                // Exit code should be 0
+                assert_eq!(out.status.code(), Some(0));
+
+                out.stdout
+            })
+            .collect();
+
+        // Every asset that could be retrieved should be embedded
+        assert!(String::from_utf8_lossy(&outputs[0]).contains("href=\"data:text/css;base64,"));
+        assert_eq!(
+            String::from_utf8_lossy(&outputs[0])
+                .matches("data:image/png;base64,")
+                .count(),
+            6
+        );
+
+        // Saved document should be the same byte for byte
+        assert_eq!(outputs[0], outputs[1]);
+    }
 }
 
 //  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
@@ -138,7 +212,89 @@ mod failing {
         // STDOUT should be empty
         assert_eq!(String::from_utf8_lossy(&out.stdout), "");
 
-        // Exit code should be 1
-        out.assert().code(1);
+        // Exit code should be 2
+        out.assert().code(2);
+    }
+
+    #[test]
+    fn bad_input_jobs() {
+        for jobs in ["0", "abc"].iter() {
+            let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+            let out = cmd
+                .arg("--jobs")
+                .arg(jobs)
+                .arg("tests/_data_/basic/local-file.html")
+                .output()
+                .unwrap();
+
+            // STDERR should contain error description
+            assert!(String::from_utf8_lossy(&out.stderr).contains("must be a positive number"));
+
+            // STDOUT should be empty
+            assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+            // Exit code should be 2
+            out.assert().code(2);
+        }
+    }
+
+    #[test]
+    fn bad_input_depth() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--depth")
+            .arg("abc")
+            .arg("tests/_data_/basic/local-file.html")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert!(String::from_utf8_lossy(&out.stderr).contains("must be a number"));
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 2
+        out.assert().code(2);
+    }
+
+    #[test]
+    fn bad_input_header() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-H")
+            .arg("Accept-Language de")
+            .arg("tests/_data_/basic/local-file.html")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert!(String::from_utf8_lossy(&out.stderr).contains("must be \"Name: value\""));
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 2
+        out.assert().code(2);
+    }
+
+    #[test]
+    fn bad_input_retries() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--retries")
+            .arg("x")
+            .arg("tests/_data_/basic/local-file.html")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert!(String::from_utf8_lossy(&out.stderr).contains("must be a number"));
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 2
+        out.assert().code(2);
     }
 }
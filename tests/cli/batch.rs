@@ -0,0 +1,248 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    #[test]
+    fn save_every_listed_target() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let directory_path: PathBuf =
+            env::temp_dir().join(format!("monolith-batch-{}", std::process::id()));
+        fs::create_dir_all(&directory_path).unwrap();
+        let input_file_path: PathBuf = directory_path.join("urls.txt");
+        fs::write(
+            &input_file_path,
+            "# Local pages\n\
+             tests/_data_/basic/local-file.html\n\
+             \n\
+             tests/_data_/basic/local-file.html\n",
+        )
+        .unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--input-file")
+            .arg(&input_file_path)
+            .arg("-o")
+            .arg(directory_path.join("{host}/{path_slug}.html"))
+            .output()
+            .unwrap();
+
+        let saved_file_names: Vec<String> = fs::read_dir(directory_path.join("local"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_string())
+            .collect();
+
+        // Same target listed twice should not overwrite the first copy
+        assert_eq!(saved_file_names.len(), 2);
+        assert!(saved_file_names
+            .iter()
+            .any(|name| name.ends_with("-basic-local-file.html")));
+        assert!(saved_file_names
+            .iter()
+            .any(|name| name.ends_with("-basic-local-file-2.html")));
+
+        // STDERR should end with the summary
+        assert!(String::from_utf8_lossy(&out.stderr).ends_with("Saved 2 of 2 targets\n"));
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&directory_path).unwrap();
+    }
+
+    #[test]
+    fn save_bookmarks_given_as_target() {
+        let server = crate::server::start(|request| match request.path.as_str() {
+            "/first" => crate::server::Response::new(200, "text/html", b"<title>First</title>"),
+            "/second" => crate::server::Response::new(200, "text/html", b"<title>Second</title>"),
+            _ => crate::server::Response::new(404, "text/plain", b"Not found"),
+        });
+        let directory_path: PathBuf =
+            env::temp_dir().join(format!("monolith-batch-bookmarks-{}", std::process::id()));
+        fs::create_dir_all(&directory_path).unwrap();
+        let bookmarks_path: PathBuf = directory_path.join("bookmarks.html");
+        fs::write(
+            &bookmarks_path,
+            format!(
+                "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n\
+                 <DL><p>\n\
+                 <DT><H3>Reading</H3>\n\
+                 <DL><p>\n\
+                 <DT><A HREF=\"{}\">First</A>\n\
+                 <DT><A HREF=\"{}\">Second</A>\n\
+                 </DL><p>\n\
+                 </DL><p>\n",
+                server.url("/first"),
+                server.url("/second")
+            ),
+        )
+        .unwrap();
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg(&bookmarks_path)
+            .arg("-o")
+            .arg(directory_path.join("{section}/{path_slug}.html"))
+            .output()
+            .unwrap();
+
+        // Every bookmark should be saved into its folder
+        assert!(
+            fs::read_to_string(directory_path.join("reading").join("first.html"))
+                .unwrap()
+                .contains("<title>First</title>")
+        );
+        assert!(
+            fs::read_to_string(directory_path.join("reading").join("second.html"))
+                .unwrap()
+                .contains("<title>Second</title>")
+        );
+
+        // STDERR should end with the summary
+        assert!(String::from_utf8_lossy(&out.stderr).ends_with("Saved 2 of 2 targets\n"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&directory_path).unwrap();
+    }
+
+    #[test]
+    fn save_sitemap_given_as_target() {
+        let server = crate::server::start(|request| match request.path.as_str() {
+            "/sitemap.xml" => crate::server::Response::new(
+                200,
+                "application/xml",
+                format!(
+                    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                     <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+                     <url><loc>http://{}/page</loc></url>\n\
+                     </urlset>\n",
+                    request.header("Host").unwrap()
+                )
+                .as_bytes(),
+            ),
+            "/page" => crate::server::Response::new(200, "text/html", b"<title>Page</title>"),
+            _ => crate::server::Response::new(404, "text/plain", b"Not found"),
+        });
+        let directory_path: PathBuf =
+            env::temp_dir().join(format!("monolith-batch-sitemap-{}", std::process::id()));
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg(server.url("/sitemap.xml").as_str())
+            .arg("-o")
+            .arg(directory_path.join("{path_slug}.html"))
+            .output()
+            .unwrap();
+
+        // Page listed in the sitemap should be saved instead of the sitemap itself
+        assert!(fs::read_to_string(directory_path.join("page.html"))
+            .unwrap()
+            .contains("<title>Page</title>"));
+        assert!(!directory_path.join("sitemap-xml.html").exists());
+
+        // STDERR should end with the summary
+        assert!(String::from_utf8_lossy(&out.stderr).ends_with("Saved 1 of 1 targets\n"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&directory_path).unwrap();
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    #[test]
+    fn keep_going_past_failed_targets() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let directory_path: PathBuf =
+            env::temp_dir().join(format!("monolith-batch-failed-{}", std::process::id()));
+        fs::create_dir_all(&directory_path).unwrap();
+        let input_file_path: PathBuf = directory_path.join("urls.txt");
+        fs::write(
+            &input_file_path,
+            "file:///missing/page.html\ntests/_data_/basic/local-file.html\n",
+        )
+        .unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--input-file")
+            .arg(&input_file_path)
+            .arg("-o")
+            .arg(directory_path.join("{path_slug}.html"))
+            .output()
+            .unwrap();
+
+        let stderr: String = String::from_utf8_lossy(&out.stderr).to_string();
+
+        // Target listed after the failed one should still be saved
+        assert_eq!(
+            fs::read_dir(&directory_path)
+                .unwrap()
+                .filter(|entry| entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_str()
+                    .unwrap()
+                    .ends_with("local-file.html"))
+                .count(),
+            1
+        );
+
+        // Failed target should be listed in the summary
+        assert!(stderr.contains("Saved 1 of 2 targets\nfile:///missing/page.html ("));
+
+        // Exit code should be 3
+        out.assert().code(3);
+
+        fs::remove_dir_all(&directory_path).unwrap();
+    }
+
+    #[test]
+    fn missing_input_file() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--input-file")
+            .arg("tests/_data_/missing-urls.txt")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            "Could not read input file\n"
+        );
+
+        // Exit code should be 2
+        out.assert().code(2);
+    }
+}
@@ -0,0 +1,94 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    #[test]
+    fn save_into_directory() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let directory_path: PathBuf =
+            env::temp_dir().join(format!("monolith-dir-format-{}", std::process::id()));
+        let out = cmd
+            .arg("-M")
+            .arg("--format")
+            .arg("dir")
+            .arg("-o")
+            .arg(&directory_path)
+            .arg("tests/_data_/basic/local-file.html")
+            .output()
+            .unwrap();
+
+        let index: String =
+            String::from_utf8(fs::read(directory_path.join("index.html")).unwrap()).unwrap();
+        let asset_file_names: Vec<String> = fs::read_dir(directory_path.join("assets"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_string())
+            .collect();
+
+        // Stylesheet and script should be saved as separate files
+        assert_eq!(asset_file_names.len(), 2);
+        assert!(asset_file_names.iter().any(|name| name.ends_with(".css")));
+        assert!(asset_file_names.iter().any(|name| name.ends_with(".js")));
+
+        // Document should refer to them using relative paths
+        for file_name in &asset_file_names {
+            assert!(index.contains(&format!("\"assets/{}\"", file_name)));
+        }
+        assert!(!index.contains("data:"));
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&directory_path).unwrap();
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::process::Command;
+
+    #[test]
+    fn no_output_directory() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--format")
+            .arg("dir")
+            .arg("tests/_data_/basic/local-file.html")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error message
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            "Output directory must be specified using -o when saving as dir\n"
+        );
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 2
+        out.assert().code(2);
+    }
+}
@@ -0,0 +1,129 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn missing_asset_without_strict() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("-s")
+            .arg("tests/_data_/strict/missing-image.html")
+            .output()
+            .unwrap();
+
+        // STDOUT should contain the document
+        assert!(String::from_utf8_lossy(&out.stdout).contains("<title>Strict</title>"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::fs;
+    use std::process::Command;
+
+    #[test]
+    fn missing_asset_with_strict() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--strict")
+            .arg("tests/_data_/strict/missing-image.html")
+            .output()
+            .unwrap();
+
+        // STDERR should mention how many assets could not be embedded
+        assert!(String::from_utf8_lossy(&out.stderr)
+            .ends_with("Assets that could not be embedded: 1\n"));
+
+        // STDOUT should still contain the document
+        assert!(String::from_utf8_lossy(&out.stdout).contains("<title>Strict</title>"));
+
+        // Exit code should be 5
+        out.assert().code(5);
+    }
+
+    #[test]
+    fn missing_asset_with_strict_and_warc() {
+        let warc_path = env::temp_dir().join("monolith-test-exit-codes-strict.warc");
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-s")
+            .arg("--strict")
+            .arg("--warc")
+            .arg(&warc_path)
+            .arg("tests/_data_/strict/missing-image.html")
+            .output()
+            .unwrap();
+        let _ = fs::remove_file(&warc_path);
+
+        // Exit code should be 5
+        out.assert().code(5);
+    }
+
+    #[test]
+    fn target_unreachable() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-s")
+            .arg("tests/_data_/strict/missing-page.html")
+            .output()
+            .unwrap();
+
+        // Exit code should be 3
+        out.assert().code(3);
+    }
+
+    #[test]
+    fn target_http_error() {
+        let server = crate::server::start(|_| {
+            crate::server::Response::new(404, "text/html", b"<h1>Not Found</h1>")
+        });
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-s")
+            .arg(server.url("missing.html").as_str())
+            .output()
+            .unwrap();
+
+        // Exit code should be 4
+        out.assert().code(4);
+    }
+
+    #[test]
+    fn output_write_error() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-o")
+            .arg("tests/_data_/strict")
+            .arg("tests/_data_/basic/local-file.html")
+            .output()
+            .unwrap();
+
+        // STDERR should end with error message
+        assert!(String::from_utf8_lossy(&out.stderr).ends_with("Could not write output\n"));
+
+        // Exit code should be 6
+        out.assert().code(6);
+    }
+}
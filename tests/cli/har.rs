@@ -0,0 +1,134 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use serde_json::Value;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    #[test]
+    fn record_every_request() {
+        let server = crate::server::start(|request| match request.path.as_str() {
+            "/" => crate::server::Response::new(
+                200,
+                "text/html",
+                b"<link rel=\"stylesheet\" href=\"style.css\">",
+            ),
+            "/style.css" => {
+                crate::server::Response::new(200, "text/css", b"p{background:url(bg.png)}")
+            }
+            "/bg.png" => crate::server::Response::new(200, "image/png", b"PNG"),
+            _ => crate::server::Response::new(404, "text/plain", b"Not found"),
+        });
+        let har_path: PathBuf =
+            env::temp_dir().join(format!("monolith-har-{}.har", std::process::id()));
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-s")
+            .arg("--har")
+            .arg(&har_path)
+            .arg(server.url("/").as_str())
+            .output()
+            .unwrap();
+
+        let har: Value = serde_json::from_str(&fs::read_to_string(&har_path).unwrap()).unwrap();
+        fs::remove_file(&har_path).unwrap();
+
+        // Target, stylesheet, image found in it, and favicon probe
+        let entries: Vec<(String, u64)> = har["log"]["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                (
+                    entry["request"]["url"].as_str().unwrap().to_string(),
+                    entry["response"]["status"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (server.url("/").to_string(), 200),
+                (server.url("/style.css").to_string(), 200),
+                (server.url("/bg.png").to_string(), 200),
+                (server.url("/favicon.ico").to_string(), 404),
+            ]
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use serde_json::Value;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    #[test]
+    fn unreachable_target_gets_recorded() {
+        let har_path: PathBuf = env::temp_dir().join(format!(
+            "monolith-har-unreachable-{}.har",
+            std::process::id()
+        ));
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-s")
+            .arg("--har")
+            .arg(&har_path)
+            .arg("http://127.0.0.1:1/")
+            .output()
+            .unwrap();
+
+        let har: Value = serde_json::from_str(&fs::read_to_string(&har_path).unwrap()).unwrap();
+        fs::remove_file(&har_path).unwrap();
+
+        let entry: &Value = &har["log"]["entries"][0];
+        assert_eq!(entry["request"]["url"], "http://127.0.0.1:1/");
+        assert_eq!(entry["response"]["status"], 0);
+        assert!(entry["_error"].is_string());
+
+        // Exit code should indicate unreachable target
+        out.assert().code(3);
+    }
+
+    #[test]
+    fn unwritable_har_path() {
+        let server =
+            crate::server::start(|_| crate::server::Response::new(200, "text/html", b"<p>Hi</p>"));
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-s")
+            .arg("--har")
+            .arg("tests/_data_/no-such-directory/capture.har")
+            .arg(server.url("/").as_str())
+            .output()
+            .unwrap();
+
+        // STDERR should mention the HAR file
+        assert!(String::from_utf8_lossy(&out.stderr).contains("Could not write HAR file"));
+
+        // Exit code should indicate write error
+        out.assert().code(6);
+    }
+}
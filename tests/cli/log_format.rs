@@ -0,0 +1,115 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use serde_json::Value;
+    use std::env;
+    use std::process::Command;
+
+    #[test]
+    fn json_log_format() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let cwd_normalized: String = env::current_dir()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .replace("\\", "/");
+        let out = cmd
+            .arg("-M")
+            .arg("--log-format")
+            .arg("json")
+            .arg("tests/_data_/basic/local-file.html")
+            .output()
+            .unwrap();
+        let file_url_protocol: &str = if cfg!(windows) { "file:///" } else { "file://" };
+        let base: String = format!(
+            "{file}{cwd}/tests/_data_/basic/",
+            file = file_url_protocol,
+            cwd = cwd_normalized
+        );
+
+        // STDERR should contain one JSON object per line
+        let events: Vec<Value> = String::from_utf8_lossy(&out.stderr)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let summary: Vec<(&str, String)> = events
+            .iter()
+            .map(|event| {
+                (
+                    event["event"].as_str().unwrap(),
+                    event["url"].as_str().unwrap().replace(&base, ""),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("asset-started", "local-file.html".to_string()),
+                ("asset-finished", "local-file.html".to_string()),
+                ("asset-started", "local-style.css".to_string()),
+                ("asset-finished", "local-style.css".to_string()),
+                (
+                    "asset-started",
+                    "local-style-does-not-exist.css".to_string()
+                ),
+                ("asset-failed", "local-style-does-not-exist.css".to_string()),
+                ("asset-started", "monolith.png".to_string()),
+                ("asset-failed", "monolith.png".to_string()),
+                ("asset-started", "local-script.js".to_string()),
+                ("asset-finished", "local-script.js".to_string()),
+                ("document-finished", "local-file.html".to_string()),
+            ]
+        );
+        assert_eq!(events[3]["depth"], 1);
+        assert_eq!(events[3]["bytes"], 54);
+        assert_eq!(events[3]["from_cache"], false);
+        assert_eq!(events[5]["reason"], "not found");
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::process::Command;
+
+    #[test]
+    fn unknown_log_format() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--log-format")
+            .arg("xml")
+            .arg("tests/_data_/basic/local-file.html")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error message
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            "Unknown log format: xml\n"
+        );
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 2
+        out.assert().code(2);
+    }
+}
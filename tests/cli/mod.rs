@@ -1,6 +1,13 @@
 mod base_url;
 mod basic;
+mod batch;
 mod data_url;
+mod dir_format;
+mod exit_codes;
+mod har;
 mod local_files;
+mod log_format;
 mod noscript;
+mod report;
 mod unusual_encodings;
+mod warc;
@@ -0,0 +1,88 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use serde_json::Value;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    #[test]
+    fn write_report_next_to_document() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let report_path: PathBuf =
+            env::temp_dir().join(format!("monolith-report-{}.json", std::process::id()));
+        let out = cmd
+            .arg("-M")
+            .arg("-s")
+            .arg("--report")
+            .arg(&report_path)
+            .arg("tests/_data_/strict/missing-image.html")
+            .output()
+            .unwrap();
+
+        let report: Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+        fs::remove_file(&report_path).unwrap();
+
+        let page: &Value = &report["pages"][0];
+        assert!(page["url"]
+            .as_str()
+            .unwrap()
+            .ends_with("tests/_data_/strict/missing-image.html"));
+        assert_eq!(page["media_type"], "text/html");
+
+        let assets: &Vec<Value> = page["assets"].as_array().unwrap();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0]["element"], "img");
+        assert_eq!(assets[0]["attribute"], "src");
+        assert_eq!(assets[0]["value"], "missing.png");
+        assert_eq!(assets[0]["final_url"], Value::Null);
+        assert!(assets[0]["error"].is_string());
+
+        // STDOUT should contain the document
+        assert!(String::from_utf8_lossy(&out.stdout).contains("<title>Strict</title>"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn unwritable_report_path() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("-s")
+            .arg("--report")
+            .arg("tests/_data_/no-such-directory/report.json")
+            .arg("tests/_data_/strict/missing-image.html")
+            .output()
+            .unwrap();
+
+        // STDERR should mention the report file
+        assert!(String::from_utf8_lossy(&out.stderr).contains("Could not write report file"));
+
+        // Exit code should indicate write error
+        out.assert().code(6);
+    }
+}
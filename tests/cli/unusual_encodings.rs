@@ -176,8 +176,8 @@ mod passing {
         // STDOUT should be empty
         assert_eq!(String::from_utf8_lossy(&out.stdout).to_string(), "");
 
-        // Exit code should be 1
-        out.assert().code(1);
+        // Exit code should be 2
+        out.assert().code(2);
     }
 }
 
@@ -0,0 +1,175 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    #[test]
+    fn document_written_to_stdout() {
+        let server = crate::server::start(|request| match request.path.as_str() {
+            "/" => crate::server::Response::new(200, "text/html", b"<title>Archived</title>"),
+            _ => crate::server::Response::new(404, "text/plain", b"Not found"),
+        });
+        let warc_path: PathBuf =
+            env::temp_dir().join(format!("monolith-warc-stdout-{}.warc", std::process::id()));
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-s")
+            .arg("-M")
+            .arg("--warc")
+            .arg(&warc_path)
+            .arg(server.url("/").as_str())
+            .output()
+            .unwrap();
+
+        let warc: String = fs::read_to_string(&warc_path).unwrap();
+        fs::remove_file(&warc_path).unwrap();
+
+        // STDOUT should contain the document
+        assert!(String::from_utf8_lossy(&out.stdout).contains("<title>Archived</title>"));
+
+        // WARC file should contain the exchange as well
+        assert!(warc.contains("<title>Archived</title>"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+
+    #[test]
+    fn replay_recorded_redirect() {
+        let server = crate::server::start(|request| match request.path.as_str() {
+            "/" => crate::server::Response::new(
+                200,
+                "text/html",
+                b"<link rel=\"stylesheet\" href=\"old.css\">",
+            ),
+            "/old.css" => crate::server::Response::new(301, "text/html", b"Moved")
+                .header("Location", "/new.css"),
+            "/new.css" => crate::server::Response::new(200, "text/css", b"body{color:red}"),
+            _ => crate::server::Response::new(404, "text/plain", b"Not found"),
+        });
+        let warc_path: PathBuf = env::temp_dir().join(format!(
+            "monolith-warc-replay-{}.warc.gz",
+            std::process::id()
+        ));
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let recorded = cmd
+            .arg("-s")
+            .arg("-M")
+            .arg("--warc")
+            .arg(&warc_path)
+            .arg(server.url("/").as_str())
+            .output()
+            .unwrap();
+        let requests: usize = server.requests().len();
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let replayed = cmd
+            .arg("-s")
+            .arg("-M")
+            .arg("--from-warc")
+            .arg(&warc_path)
+            .arg(server.url("/").as_str())
+            .output()
+            .unwrap();
+        fs::remove_file(&warc_path).unwrap();
+
+        // Stylesheet should be embedded after following the redirect
+        assert!(String::from_utf8_lossy(&recorded.stdout)
+            .contains("data:text/css;base64,Ym9keXtjb2xvcjpyZWR9"));
+
+        // Replayed document should be the same, without making any requests
+        assert_eq!(
+            String::from_utf8_lossy(&replayed.stdout),
+            String::from_utf8_lossy(&recorded.stdout)
+        );
+        assert_eq!(server.requests().len(), requests);
+
+        // Exit code should be 0
+        replayed.assert().code(0);
+    }
+
+    #[test]
+    fn replay_assets_kept_in_cache_dir() {
+        let server = crate::server::start(|request| match request.path.as_str() {
+            "/" => crate::server::Response::new(
+                200,
+                "text/html",
+                b"<link rel=\"stylesheet\" href=\"style.css\">",
+            ),
+            "/style.css" => crate::server::Response::new(200, "text/css", b"body{color:red}")
+                .header("Cache-Control", "max-age=3600"),
+            _ => crate::server::Response::new(404, "text/plain", b"Not found"),
+        });
+        let cache_dir: PathBuf =
+            env::temp_dir().join(format!("monolith-warc-cache-dir-{}", std::process::id()));
+        let warc_path: PathBuf = env::temp_dir().join(format!(
+            "monolith-warc-cache-dir-{}.warc",
+            std::process::id()
+        ));
+
+        // Fill the disk cache with a fresh copy of the stylesheet
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("-s")
+            .arg("-M")
+            .arg("--cache-dir")
+            .arg(&cache_dir)
+            .arg(server.url("/").as_str())
+            .assert()
+            .code(0);
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let recorded = cmd
+            .arg("-s")
+            .arg("-M")
+            .arg("--cache-dir")
+            .arg(&cache_dir)
+            .arg("--warc")
+            .arg(&warc_path)
+            .arg(server.url("/").as_str())
+            .output()
+            .unwrap();
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let replayed = cmd
+            .arg("-s")
+            .arg("-M")
+            .arg("--from-warc")
+            .arg(&warc_path)
+            .arg(server.url("/").as_str())
+            .output()
+            .unwrap();
+        fs::remove_file(&warc_path).unwrap();
+        fs::remove_dir_all(&cache_dir).unwrap();
+
+        // Stylesheet should be requested again while recording
+        assert_eq!(
+            server
+                .requests()
+                .iter()
+                .filter(|request| request.path == "/style.css")
+                .count(),
+            2
+        );
+
+        // Replayed document should contain the stylesheet kept in cache
+        assert!(String::from_utf8_lossy(&replayed.stdout)
+            .contains("data:text/css;base64,Ym9keXtjb2xvcjpyZWR9"));
+        assert_eq!(
+            String::from_utf8_lossy(&replayed.stdout),
+            String::from_utf8_lossy(&recorded.stdout)
+        );
+
+        // Exit code should be 0
+        replayed.assert().code(0);
+    }
+}
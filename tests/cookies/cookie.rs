@@ -0,0 +1,106 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::Cookie;
+
+    fn cookie(domain: &str, tailmatch: bool, path: &str, secure: bool) -> Cookie {
+        Cookie {
+            domain: domain.to_string(),
+            tailmatch,
+            path: path.to_string(),
+            secure,
+            http_only: false,
+            expiration: 0,
+            name: "id".to_string(),
+            value: "1".to_string(),
+        }
+    }
+
+    #[test]
+    fn subdomain_of_tailmatching_domain() {
+        let cookie: Cookie = cookie(".example.com", true, "/", false);
+
+        assert!(cookie.matches_url("https://example.com/"));
+        assert!(cookie.matches_url("https://www.example.com/"));
+        assert!(cookie.matches_url("http://a.b.EXAMPLE.com/page"));
+    }
+
+    #[test]
+    fn path_prefix() {
+        let cookie: Cookie = cookie("example.com", false, "/docs", false);
+
+        assert!(cookie.matches_url("https://example.com/docs"));
+        assert!(cookie.matches_url("https://example.com/docs/"));
+        assert!(cookie.matches_url("https://example.com/docs/guide.html"));
+    }
+
+    #[test]
+    fn session_cookie_does_not_expire() {
+        assert!(!cookie("example.com", false, "/", false).is_expired());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::cookies::Cookie;
+
+    fn cookie(domain: &str, tailmatch: bool, path: &str, secure: bool) -> Cookie {
+        Cookie {
+            domain: domain.to_string(),
+            tailmatch,
+            path: path.to_string(),
+            secure,
+            http_only: false,
+            expiration: 0,
+            name: "id".to_string(),
+            value: "1".to_string(),
+        }
+    }
+
+    #[test]
+    fn subdomain_of_host_only_domain() {
+        assert!(!cookie("example.com", false, "/", false).matches_url("https://www.example.com/"));
+    }
+
+    #[test]
+    fn domain_merely_ending_with_same_name() {
+        assert!(!cookie(".example.com", true, "/", false).matches_url("https://badexample.com/"));
+    }
+
+    #[test]
+    fn path_without_separator() {
+        assert!(!cookie("example.com", false, "/docs", false)
+            .matches_url("https://example.com/documents"));
+    }
+
+    #[test]
+    fn secure_cookie_over_http() {
+        assert!(!cookie("example.com", false, "/", true).matches_url("http://example.com/"));
+    }
+
+    #[test]
+    fn not_http() {
+        assert!(!cookie("example.com", true, "/", false).matches_url("ftp://example.com/"));
+    }
+
+    #[test]
+    fn expired() {
+        let mut cookie: Cookie = cookie("example.com", false, "/", false);
+        cookie.expiration = 1;
+
+        assert!(cookie.is_expired());
+    }
+}
@@ -0,0 +1,78 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::cookies::{self, CookieJar};
+
+    #[test]
+    fn join_all_matching_cookies() {
+        let cookie_jar: CookieJar = CookieJar::new(
+            cookies::parse_cookies(
+                ".example.com\tTRUE\t/\tFALSE\t0\tsite\t\"1\"\n\
+                 www.example.com\tFALSE\t/docs\tFALSE\t0\tdocs\t\"2\"\n\
+                 other.com\tFALSE\t/\tFALSE\t0\tother\t\"3\"\n",
+            )
+            .unwrap(),
+        );
+        let url: Url = Url::parse("https://www.example.com/docs/index.html").unwrap();
+
+        // Cookies with longer paths go first
+        assert_eq!(
+            cookie_jar.cookie_header(&url),
+            Some("docs=2; site=1".to_string())
+        );
+    }
+
+    #[test]
+    fn replace_cookie_set_by_server() {
+        let cookie_jar: CookieJar = CookieJar::default();
+        let url: Url = Url::parse("https://example.com/").unwrap();
+
+        cookie_jar.set_cookie("id=1; Path=/", &url);
+        cookie_jar.set_cookie("theme=dark; Path=/", &url);
+        cookie_jar.set_cookie("id=2; Path=/", &url);
+
+        assert_eq!(
+            cookie_jar.cookie_header(&url),
+            Some("id=2; theme=dark".to_string())
+        );
+    }
+
+    #[test]
+    fn remove_cookie_expired_by_server() {
+        let cookie_jar: CookieJar = CookieJar::default();
+        let url: Url = Url::parse("https://example.com/").unwrap();
+
+        cookie_jar.set_cookie("id=1", &url);
+        cookie_jar.set_cookie("id=1; Max-Age=0", &url);
+
+        assert_eq!(cookie_jar.cookie_header(&url), None);
+        assert!(cookie_jar.cookies().is_empty());
+    }
+
+    #[test]
+    fn netscape_format() {
+        let cookie_jar: CookieJar = CookieJar::default();
+        let url: Url = Url::parse("https://www.example.com/account/login").unwrap();
+
+        cookie_jar.set_cookie("session=abc; HttpOnly; Secure", &url);
+        cookie_jar.set_cookie(
+            "lang=en; Domain=example.com; Path=/; Expires=Wed, 21 Oct 2099 07:28:00 GMT",
+            &url,
+        );
+
+        assert_eq!(
+            cookie_jar.to_netscape(),
+            "# Netscape HTTP Cookie File\n\
+             #HttpOnly_www.example.com\tFALSE\t/account\tTRUE\t0\tsession\tabc\n\
+             .example.com\tTRUE\t/\tFALSE\t4096250880\tlang\ten\n"
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::{self, CookieFormat};
+
+    #[test]
+    fn netscape() {
+        assert_eq!(
+            cookies::detect_cookie_format(b"# Netscape HTTP Cookie File\n"),
+            CookieFormat::Netscape
+        );
+        assert_eq!(
+            cookies::detect_cookie_format(b"#HttpOnly_.example.com\tTRUE\t/\tFALSE\t0\tid\t1\n"),
+            CookieFormat::Netscape
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            cookies::detect_cookie_format(b"\n  [{\"name\": \"id\"}]"),
+            CookieFormat::Json
+        );
+        assert_eq!(
+            cookies::detect_cookie_format(b"{\"cookies\": []}"),
+            CookieFormat::Json
+        );
+    }
+
+    #[test]
+    fn sqlite() {
+        assert_eq!(
+            cookies::detect_cookie_format(b"SQLite format 3\0\x10\0\x02\x02"),
+            CookieFormat::Sqlite
+        );
+    }
+}
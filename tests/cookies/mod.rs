@@ -0,0 +1,8 @@
+mod cookie;
+mod cookie_jar;
+mod detect_cookie_format;
+mod parse_cookies;
+mod parse_json_cookies;
+mod parse_set_cookie;
+#[cfg(feature = "firefox-cookies")]
+mod read_sqlite_cookies;
@@ -0,0 +1,115 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::{self, Cookie};
+
+    #[test]
+    fn quoted_and_unquoted_values() {
+        let cookies: Vec<Cookie> = cookies::parse_cookies(
+            "# Netscape HTTP Cookie File\n\
+             \n\
+             .example.com\tTRUE\t/\tTRUE\t4102444800\tquoted\t\"a b\"\r\n\
+             example.com\tFALSE\t/docs\tFALSE\t0\tunquoted\tabc\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            cookies,
+            vec![
+                Cookie {
+                    domain: ".example.com".to_string(),
+                    tailmatch: true,
+                    path: "/".to_string(),
+                    secure: true,
+                    http_only: false,
+                    expiration: 4102444800,
+                    name: "quoted".to_string(),
+                    value: "a b".to_string(),
+                },
+                Cookie {
+                    domain: "example.com".to_string(),
+                    tailmatch: false,
+                    path: "/docs".to_string(),
+                    secure: false,
+                    http_only: false,
+                    expiration: 0,
+                    name: "unquoted".to_string(),
+                    value: "abc".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn http_only_prefix() {
+        let cookies: Vec<Cookie> =
+            cookies::parse_cookies("#HttpOnly_.example.com\tTRUE\t/\tFALSE\t0\tsession\tabc\n")
+                .unwrap();
+
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].domain, ".example.com");
+        assert!(cookies[0].http_only);
+    }
+
+    #[test]
+    fn empty_value() {
+        let cookies: Vec<Cookie> = cookies::parse_cookies(
+            "example.com\tFALSE\t/\tFALSE\t0\tempty\t\n\
+             example.com\tFALSE\t/\tFALSE\t0\tnone\n\
+             example.com\tFALSE\t/\tFALSE\t0\tquote\t\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(cookies[0].value, "");
+        assert_eq!(cookies[1].value, "");
+        assert_eq!(cookies[2].value, "\"");
+    }
+
+    #[test]
+    fn fractional_expiration() {
+        let cookies: Vec<Cookie> =
+            cookies::parse_cookies("example.com\tFALSE\t/\tFALSE\t1700000000.5\tid\t1\n").unwrap();
+
+        assert_eq!(cookies[0].expiration, 1700000000);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::cookies::{self, ParseCookieError};
+
+    #[test]
+    fn non_numeric_expiration() {
+        let error: ParseCookieError = cookies::parse_cookies(
+            "# Netscape HTTP Cookie File\n\
+             example.com\tFALSE\t/\tFALSE\t0\tid\t1\n\
+             example.com\tFALSE\t/\tFALSE\tnever\tid\t1\n",
+        )
+        .unwrap_err();
+
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.to_string(), "line 3: invalid expiration date: never");
+    }
+
+    #[test]
+    fn missing_fields() {
+        let error: ParseCookieError =
+            cookies::parse_cookies("example.com FALSE / FALSE 0 id 1\n").unwrap_err();
+
+        assert_eq!(error.line, Some(1));
+        assert_eq!(error.message, "expected 7 tab-separated fields, found 1");
+    }
+}
@@ -0,0 +1,124 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::{self, Cookie};
+
+    #[test]
+    fn browser_extension_export() {
+        let cookies: Vec<Cookie> = cookies::parse_json_cookies(
+            r#"[
+                {
+                    "domain": ".example.com",
+                    "expirationDate": 4102444800.25,
+                    "hostOnly": false,
+                    "httpOnly": true,
+                    "name": "session",
+                    "path": "/",
+                    "sameSite": "lax",
+                    "secure": true,
+                    "session": false,
+                    "storeId": "0",
+                    "value": "abc"
+                },
+                {
+                    "domain": "www.example.com",
+                    "hostOnly": true,
+                    "httpOnly": false,
+                    "name": "theme",
+                    "path": "/docs",
+                    "secure": false,
+                    "session": true,
+                    "value": "dark"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cookies,
+            vec![
+                Cookie {
+                    domain: ".example.com".to_string(),
+                    tailmatch: true,
+                    path: "/".to_string(),
+                    secure: true,
+                    http_only: true,
+                    expiration: 4102444800,
+                    name: "session".to_string(),
+                    value: "abc".to_string(),
+                },
+                Cookie {
+                    domain: "www.example.com".to_string(),
+                    tailmatch: false,
+                    path: "/docs".to_string(),
+                    secure: false,
+                    http_only: false,
+                    expiration: 0,
+                    name: "theme".to_string(),
+                    value: "dark".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn storage_state() {
+        let cookies: Vec<Cookie> = cookies::parse_json_cookies(
+            r#"{
+                "cookies": [
+                    {"name": "id", "value": "1", "domain": ".example.com", "path": "/", "expires": -1, "httpOnly": false, "secure": false}
+                ],
+                "origins": []
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(cookies.len(), 1);
+        assert!(cookies[0].tailmatch);
+        assert_eq!(cookies[0].expiration, 0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::cookies::{self, ParseCookieError};
+
+    #[test]
+    fn syntax_error() {
+        let error: ParseCookieError =
+            cookies::parse_json_cookies("[\n  {\"name\": \"id\",\n  }\n]").unwrap_err();
+
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.to_string(), "line 3: trailing comma");
+    }
+
+    #[test]
+    fn no_name() {
+        let error: ParseCookieError =
+            cookies::parse_json_cookies(r#"[{"domain": "example.com", "value": "1"}]"#)
+                .unwrap_err();
+
+        assert_eq!(error.line, None);
+        assert_eq!(error.to_string(), "cookie 1 has no name");
+    }
+
+    #[test]
+    fn not_a_list() {
+        let error: ParseCookieError = cookies::parse_json_cookies("{}").unwrap_err();
+
+        assert_eq!(error.to_string(), "expected a list of cookies");
+    }
+}
@@ -0,0 +1,151 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::cookies::{self, Cookie};
+
+    #[test]
+    fn host_only() {
+        let url: Url = Url::parse("https://www.example.com/account/login").unwrap();
+
+        assert_eq!(
+            cookies::parse_set_cookie("session=abc123; HttpOnly; Secure", &url),
+            Some(Cookie {
+                domain: "www.example.com".to_string(),
+                tailmatch: false,
+                path: "/account".to_string(),
+                secure: true,
+                http_only: true,
+                expiration: 0,
+                name: "session".to_string(),
+                value: "abc123".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn domain_and_path() {
+        let url: Url = Url::parse("https://www.example.com/").unwrap();
+        let cookie: Cookie = cookies::parse_set_cookie(
+            "lang = en ; domain=.Example.COM; path=/docs; Expires=Wed, 21 Oct 2099 07:28:00 GMT",
+            &url,
+        )
+        .unwrap();
+
+        assert_eq!(cookie.name, "lang");
+        assert_eq!(cookie.value, "en");
+        assert_eq!(cookie.domain, "example.com");
+        assert!(cookie.tailmatch);
+        assert_eq!(cookie.path, "/docs");
+        assert_eq!(cookie.expiration, 4096250880);
+    }
+
+    #[test]
+    fn netscape_expiration_date() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+        let cookie: Cookie =
+            cookies::parse_set_cookie("id=1; expires=Thu, 01-Jan-1970 00:01:40 GMT", &url).unwrap();
+
+        assert_eq!(cookie.expiration, 100);
+        assert!(cookie.is_expired());
+    }
+
+    #[test]
+    fn max_age_over_expires() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+        let cookie: Cookie = cookies::parse_set_cookie(
+            "id=1; Max-Age=0; Expires=Wed, 21 Oct 2099 07:28:00 GMT",
+            &url,
+        )
+        .unwrap();
+
+        assert!(cookie.is_expired());
+    }
+
+    #[test]
+    fn empty_value() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+        let cookie: Cookie = cookies::parse_set_cookie("id=; Path=/", &url).unwrap();
+
+        assert_eq!(cookie.name, "id");
+        assert_eq!(cookie.value, "");
+    }
+
+    #[test]
+    fn public_suffix_of_host_itself() {
+        let url: Url = Url::parse("https://github.io/").unwrap();
+        let cookie: Cookie = cookies::parse_set_cookie("id=1; Domain=github.io", &url).unwrap();
+
+        assert_eq!(cookie.domain, "github.io");
+        assert!(!cookie.tailmatch);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::cookies;
+
+    #[test]
+    fn foreign_domain() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+
+        assert_eq!(
+            cookies::parse_set_cookie("id=1; Domain=other.com", &url),
+            None
+        );
+    }
+
+    #[test]
+    fn subdomain_of_host() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+
+        assert_eq!(
+            cookies::parse_set_cookie("id=1; Domain=www.example.com", &url),
+            None
+        );
+    }
+
+    #[test]
+    fn no_name() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+
+        assert_eq!(cookies::parse_set_cookie("=1", &url), None);
+        assert_eq!(cookies::parse_set_cookie("id", &url), None);
+    }
+
+    #[test]
+    fn public_suffix() {
+        let url: Url = Url::parse("https://www.example.co.uk/").unwrap();
+
+        assert_eq!(cookies::parse_set_cookie("id=1; Domain=co.uk", &url), None);
+        assert_eq!(cookies::parse_set_cookie("id=1; Domain=.uk", &url), None);
+        assert_eq!(
+            cookies::parse_set_cookie("id=1; Domain=example.co.uk", &url)
+                .unwrap()
+                .domain,
+            "example.co.uk"
+        );
+
+        let url: Url = Url::parse("https://alice.github.io/").unwrap();
+        assert_eq!(
+            cookies::parse_set_cookie("id=1; Domain=github.io", &url),
+            None
+        );
+    }
+}
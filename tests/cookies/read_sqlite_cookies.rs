@@ -0,0 +1,130 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use rusqlite::Connection;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use monolith::cookies::{self, Cookie};
+
+    #[test]
+    fn firefox_profile() {
+        let path: PathBuf =
+            env::temp_dir().join(format!("monolith-cookies-{}.sqlite", std::process::id()));
+        let connection: Connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE moz_cookies (id INTEGER PRIMARY KEY, originAttributes TEXT NOT NULL DEFAULT '', name TEXT, value TEXT, host TEXT, path TEXT, expiry INTEGER, lastAccessed INTEGER, creationTime INTEGER, isSecure INTEGER, isHttpOnly INTEGER);
+                INSERT INTO moz_cookies (name, value, host, path, expiry, lastAccessed, creationTime, isSecure, isHttpOnly) VALUES ('session', 'abc', '.example.com', '/', 4102444800, 0, 0, 1, 1);
+                INSERT INTO moz_cookies (name, value, host, path, expiry, lastAccessed, creationTime, isSecure, isHttpOnly) VALUES ('theme', 'dark', 'www.example.com', '/docs', 4102444800000, 0, 0, 0, 0);",
+            )
+            .unwrap();
+        drop(connection);
+
+        let cookies: Vec<Cookie> = cookies::read_sqlite_cookies(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            cookies,
+            vec![
+                Cookie {
+                    domain: ".example.com".to_string(),
+                    tailmatch: true,
+                    path: "/".to_string(),
+                    secure: true,
+                    http_only: true,
+                    expiration: 4102444800,
+                    name: "session".to_string(),
+                    value: "abc".to_string(),
+                },
+                Cookie {
+                    domain: "www.example.com".to_string(),
+                    tailmatch: false,
+                    path: "/docs".to_string(),
+                    secure: false,
+                    http_only: false,
+                    // Stored in milliseconds
+                    expiration: 4102444800,
+                    name: "theme".to_string(),
+                    value: "dark".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn profile_in_use() {
+        let path: PathBuf = env::temp_dir().join(format!(
+            "monolith-locked-cookies-{}.sqlite",
+            std::process::id()
+        ));
+        // Like Firefox does: recent changes stay in the write-ahead log,
+        // and the database remains locked for as long as it's open
+        let connection: Connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "PRAGMA journal_mode = WAL;
+                PRAGMA wal_autocheckpoint = 0;
+                CREATE TABLE moz_cookies (id INTEGER PRIMARY KEY, name TEXT, value TEXT, host TEXT, path TEXT, expiry INTEGER, isSecure INTEGER, isHttpOnly INTEGER);
+                INSERT INTO moz_cookies (name, value, host, path, expiry, isSecure, isHttpOnly) VALUES ('session', 'abc', 'example.com', '/', 4102444800, 0, 0);
+                PRAGMA locking_mode = EXCLUSIVE;
+                BEGIN EXCLUSIVE;",
+            )
+            .unwrap();
+
+        let cookies: Vec<Cookie> = cookies::read_sqlite_cookies(&path).unwrap();
+        drop(connection);
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(path.with_extension("sqlite-wal")).ok();
+        fs::remove_file(path.with_extension("sqlite-shm")).ok();
+
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].name, "session");
+        assert_eq!(cookies[0].value, "abc");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use rusqlite::Connection;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use monolith::cookies;
+
+    #[test]
+    fn not_firefox_profile() {
+        let path: PathBuf = env::temp_dir().join(format!(
+            "monolith-not-cookies-{}.sqlite",
+            std::process::id()
+        ));
+        let connection: Connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch("CREATE TABLE cookies (name TEXT, value TEXT);")
+            .unwrap();
+        drop(connection);
+
+        let result = cookies::read_sqlite_cookies(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "no such table: moz_cookies"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::core::is_within_crawl_scope;
+    use monolith::opts::Options;
+
+    #[test]
+    fn same_host() {
+        let target_url: Url = Url::parse("https://site.local/docs/").unwrap();
+        let options = Options::default();
+
+        assert!(is_within_crawl_scope(
+            &Url::parse("http://site.local/blog/").unwrap(),
+            &target_url,
+            &options
+        ));
+    }
+
+    #[test]
+    fn specified_domains() {
+        let target_url: Url = Url::parse("https://site.local/").unwrap();
+        let mut options = Options::default();
+        options.domains = Some(vec!["docs.site.local".to_string()]);
+
+        assert!(is_within_crawl_scope(
+            &Url::parse("https://docs.site.local/intro.html").unwrap(),
+            &target_url,
+            &options
+        ));
+        assert!(!is_within_crawl_scope(
+            &Url::parse("https://site.local/about.html").unwrap(),
+            &target_url,
+            &options
+        ));
+    }
+
+    #[test]
+    fn local_files_under_target_directory() {
+        let target_url: Url = Url::parse("file:///docs/index.html").unwrap();
+        let options = Options::default();
+
+        assert!(is_within_crawl_scope(
+            &Url::parse("file:///docs/guide/intro.html").unwrap(),
+            &target_url,
+            &options
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::core::is_within_crawl_scope;
+    use monolith::opts::Options;
+
+    #[test]
+    fn other_host() {
+        let target_url: Url = Url::parse("https://site.local/").unwrap();
+        let options = Options::default();
+
+        assert!(!is_within_crawl_scope(
+            &Url::parse("https://other.local/").unwrap(),
+            &target_url,
+            &options
+        ));
+    }
+
+    #[test]
+    fn blacklisted_domains() {
+        let target_url: Url = Url::parse("https://site.local/").unwrap();
+        let mut options = Options::default();
+        options.domains = Some(vec!["site.local".to_string()]);
+        options.blacklist_domains = true;
+
+        assert!(!is_within_crawl_scope(
+            &Url::parse("https://site.local/about.html").unwrap(),
+            &target_url,
+            &options
+        ));
+    }
+
+    #[test]
+    fn non_web_links() {
+        let target_url: Url = Url::parse("https://site.local/").unwrap();
+        let options = Options::default();
+
+        assert!(!is_within_crawl_scope(
+            &Url::parse("mailto:someone@site.local").unwrap(),
+            &target_url,
+            &options
+        ));
+        assert!(!is_within_crawl_scope(
+            &Url::parse("file:///etc/passwd").unwrap(),
+            &target_url,
+            &options
+        ));
+    }
+
+    #[test]
+    fn local_files_outside_target_directory() {
+        let target_url: Url = Url::parse("file:///docs/index.html").unwrap();
+        let options = Options::default();
+
+        assert!(!is_within_crawl_scope(
+            &Url::parse("file:///other/index.html").unwrap(),
+            &target_url,
+            &options
+        ));
+    }
+}
@@ -0,0 +1,3 @@
+mod is_within_crawl_scope;
+mod monolith;
+mod resolve_target;
@@ -0,0 +1,333 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::collections::HashMap;
+
+    use monolith::core::Monolith;
+    use monolith::fetcher::{AssetFetcher, AssetRequest, FetchedAsset};
+    use monolith::opts::Options;
+    use monolith::utils::AssetError;
+
+    struct FixtureFetcher {
+        assets: HashMap<String, (&'static str, &'static str)>,
+    }
+
+    impl AssetFetcher for FixtureFetcher {
+        fn fetch(
+            &self,
+            request: &AssetRequest,
+            _options: &Options,
+        ) -> Result<FetchedAsset, AssetError> {
+            match self.assets.get(request.url.as_str()) {
+                Some((media_type, data)) => Ok(FetchedAsset::new(
+                    data.as_bytes().to_vec(),
+                    request.url.clone(),
+                    media_type.to_string(),
+                    "".to_string(),
+                )),
+                None => Err(AssetError::NotFound),
+            }
+        }
+    }
+
+    #[test]
+    fn fetch_local_file() {
+        let mut options = Options::default();
+        options.no_metadata = true;
+        options.silent = true;
+
+        let saved_document = Monolith::new(options)
+            .fetch("tests/_data_/basic/local-file.html")
+            .unwrap();
+
+        assert!(saved_document.is_html());
+        assert_eq!(saved_document.encoding, "utf-8");
+        assert!(String::from_utf8_lossy(&saved_document.data).contains(
+            "<script src=\"data:application/javascript;base64,ZG9jdW1lbnQuYm9keS5zdHlsZS5iYWNrZ3JvdW5kQ29sb3IgPSAiZ3JlZW4iOwpkb2N1bWVudC5ib2R5LnN0eWxlLmNvbG9yID0gInJlZCI7Cg==\"></script>"
+        ));
+    }
+
+    #[test]
+    fn custom_fetcher() {
+        let mut options = Options::default();
+        options.no_metadata = true;
+        options.silent = true;
+
+        let mut assets = HashMap::new();
+        assets.insert(
+            "https://site.local/".to_string(),
+            (
+                "text/html",
+                "<link rel=\"stylesheet\" href=\"style.css\"><p>Hi</p>",
+            ),
+        );
+        assets.insert(
+            "https://site.local/style.css".to_string(),
+            ("text/css", "p{color:red}"),
+        );
+
+        let saved_document = Monolith::with_fetcher(options, Box::new(FixtureFetcher { assets }))
+            .fetch("https://site.local/")
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8_lossy(&saved_document.data),
+            "<html><head><link rel=\"stylesheet\" href=\"data:text/css;base64,cHtjb2xvcjpyZWR9\"></head><body><p>Hi</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn records_assets_for_report() {
+        let mut options = Options::default();
+        options.no_metadata = true;
+        options.silent = true;
+        options.report = Some("report.json".to_string());
+
+        let mut assets = HashMap::new();
+        assets.insert(
+            "https://site.local/".to_string(),
+            (
+                "text/html",
+                "<link rel=\"stylesheet\" href=\"style.css\">\
+                 <script src=\"app.js\" integrity=\"sha256-invalid\"></script>\
+                 <img srcset=\"a.png 2x\">",
+            ),
+        );
+        assets.insert(
+            "https://site.local/style.css".to_string(),
+            ("text/css", "p{background:url(bg.png)}"),
+        );
+        assets.insert(
+            "https://site.local/app.js".to_string(),
+            ("text/javascript", "1"),
+        );
+        assets.insert("https://site.local/a.png".to_string(), ("image/png", "PNG"));
+
+        let mut monolith = Monolith::with_fetcher(options, Box::new(FixtureFetcher { assets }));
+        let saved_document = monolith.fetch("https://site.local/").unwrap();
+        let records = &saved_document.asset_records;
+
+        let record = |url: &str| {
+            records
+                .iter()
+                .find(|record| record.url == url)
+                .unwrap()
+                .clone()
+        };
+
+        assert_eq!(records.len(), 4);
+
+        let stylesheet = record("https://site.local/style.css");
+        assert_eq!(stylesheet.element.as_deref(), Some("link"));
+        assert_eq!(stylesheet.attribute, "href");
+        assert_eq!(stylesheet.value, "style.css");
+        assert_eq!(stylesheet.media_type.as_deref(), Some("text/css"));
+        assert_eq!(stylesheet.bytes, Some(25));
+        assert_eq!(stylesheet.from_cache, Some(false));
+        assert_eq!(stylesheet.error, None);
+
+        let background = record("https://site.local/bg.png");
+        assert_eq!(background.element, None);
+        assert_eq!(background.attribute, "background");
+        assert_eq!(background.parent_url, "https://site.local/style.css");
+        assert_eq!(background.error.as_deref(), Some("not found"));
+
+        let script = record("https://site.local/app.js");
+        assert_eq!(script.integrity, Some(false));
+        assert_eq!(script.error.as_deref(), Some("integrity check failed"));
+
+        let image = record("https://site.local/a.png");
+        assert_eq!(image.element.as_deref(), Some("img"));
+        assert_eq!(image.attribute, "srcset");
+        assert_eq!(image.value, "a.png");
+
+        // Assets retrieved while saving the first document are cached
+        let saved_document = monolith.fetch("https://site.local/").unwrap();
+        let stylesheet = saved_document
+            .asset_records
+            .iter()
+            .find(|record| record.url == "https://site.local/style.css")
+            .unwrap();
+        assert_eq!(stylesheet.from_cache, Some(true));
+    }
+
+    #[test]
+    fn crawl_linked_pages() {
+        let mut options = Options::default();
+        options.no_metadata = true;
+        options.silent = true;
+        options.crawl_depth = 1;
+
+        let mut assets = HashMap::new();
+        assets.insert(
+            "https://site.local/".to_string(),
+            (
+                "text/html",
+                "<a href=\"docs/intro.html#usage\">Intro</a><a href=\"https://other.local/\">Other</a>",
+            ),
+        );
+        assets.insert(
+            "https://site.local/docs/intro.html".to_string(),
+            (
+                "text/html",
+                "<a href=\"/\">Home</a><a href=\"more.html\">More</a>",
+            ),
+        );
+
+        let saved_pages = Monolith::with_fetcher(options, Box::new(FixtureFetcher { assets }))
+            .crawl("https://site.local/", "site.html")
+            .unwrap();
+
+        assert_eq!(saved_pages.len(), 2);
+        assert_eq!(saved_pages[0].file_name, "site.html");
+        assert_eq!(
+            String::from_utf8_lossy(&saved_pages[0].document.data),
+            "<html><head></head><body><a href=\"docs-intro.html#usage\">Intro</a><a href=\"https://other.local/\">Other</a></body></html>"
+        );
+        // Pages beyond the depth limit are linked to remotely
+        assert_eq!(saved_pages[1].file_name, "docs-intro.html");
+        assert_eq!(
+            String::from_utf8_lossy(&saved_pages[1].document.data),
+            "<html><head></head><body><a href=\"site.html\">Home</a><a href=\"https://site.local/docs/more.html\">More</a></body></html>"
+        );
+    }
+
+    #[test]
+    fn from_data() {
+        let mut options = Options::default();
+        options.no_metadata = true;
+        options.silent = true;
+        options.charset = Some("iso-8859-1".to_string());
+
+        let saved_document = Monolith::new(options)
+            .from_data(
+                "<p>\u{e9}</p>".as_bytes().to_vec(),
+                &Url::parse("data:text/html,").unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(saved_document.encoding, "iso-8859-1");
+        assert_eq!(
+            saved_document.data,
+            b"<html><head><meta charset=\"iso-8859-1\"></meta></head><body><p>\xe9</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn fetch_mhtml_file() {
+        let mut options = Options::default();
+        options.no_metadata = true;
+        options.silent = true;
+
+        let saved_document = Monolith::new(options)
+            .fetch("tests/_data_/mhtml/page.mhtml")
+            .unwrap();
+
+        assert!(saved_document.is_html());
+        assert_eq!(saved_document.encoding, "utf-8");
+        assert_eq!(
+            saved_document.final_url.as_str(),
+            "https://site.local/page.html"
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&saved_document.data),
+            "<html><head><meta charset=\"utf-8\"><link rel=\"stylesheet\" href=\"data:text/css;base64,cCB7IGNvbG9yOiByZWQ7IH0=\"></head><body><p>Caf\u{e9}</p><img src=\"data:image/gif;base64,R0lGODlhAQABAAAAACw=\"></body></html>"
+        );
+    }
+
+    #[test]
+    fn passes_non_html_targets_through() {
+        let mut options = Options::default();
+        options.silent = true;
+
+        let saved_document = Monolith::new(options)
+            .fetch("tests/_data_/basic/local-style.css")
+            .unwrap();
+
+        assert!(!saved_document.is_html());
+        assert_eq!(saved_document.media_type, "text/css");
+        assert_eq!(
+            saved_document.data,
+            std::fs::read("tests/_data_/basic/local-style.css").unwrap()
+        );
+    }
+
+    #[test]
+    fn lists_failed_assets() {
+        let mut options = Options::default();
+        options.silent = true;
+
+        let mut monolith = Monolith::new(options);
+        let saved_document = monolith
+            .fetch("tests/_data_/strict/missing-image.html")
+            .unwrap();
+
+        assert_eq!(saved_document.failed_assets.len(), 1);
+        let (url, error) = &saved_document.failed_assets[0];
+        assert!(url.as_str().ends_with("/tests/_data_/strict/missing.png"));
+        assert!(matches!(error, AssetError::NotFound));
+
+        // Failures of documents saved earlier are forgotten
+        let saved_document = monolith
+            .fetch("tests/_data_/strict/missing-image.html")
+            .unwrap();
+
+        assert_eq!(saved_document.failed_assets.len(), 1);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::{Monolith, MonolithError};
+    use monolith::opts::Options;
+
+    #[test]
+    fn unknown_encoding() {
+        let mut options = Options::default();
+        options.silent = true;
+        options.charset = Some("utf-0".to_string());
+
+        assert!(matches!(
+            Monolith::new(options).fetch("tests/_data_/basic/local-file.html"),
+            Err(MonolithError::UnknownEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_referrer_policy() {
+        let mut options = Options::default();
+        options.silent = true;
+        options.referrer_policy = Some("origin-only".to_string());
+
+        assert!(matches!(
+            Monolith::new(options).fetch("tests/_data_/basic/local-file.html"),
+            Err(MonolithError::UnknownReferrerPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_status_list() {
+        let mut options = Options::default();
+        options.silent = true;
+        options.fail_on_status = Some("4xx,teapot".to_string());
+
+        assert!(matches!(
+            Monolith::new(options).fetch("tests/_data_/basic/local-file.html"),
+            Err(MonolithError::InvalidStatusList(_))
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::env;
+    use std::fs;
+
+    use monolith::core;
+
+    #[test]
+    fn http_url() {
+        assert_eq!(
+            core::resolve_target("https://site.local/page.html").unwrap(),
+            Url::parse("https://site.local/page.html").unwrap()
+        );
+    }
+
+    #[test]
+    fn data_url() {
+        assert_eq!(
+            core::resolve_target("data:text/html,Hello").unwrap(),
+            Url::parse("data:text/html,Hello").unwrap()
+        );
+    }
+
+    #[test]
+    fn local_file_path() {
+        let path: &str = "tests/_data_/basic/local-file.html";
+
+        assert_eq!(
+            core::resolve_target(path).unwrap(),
+            Url::from_file_path(fs::canonicalize(env::current_dir().unwrap().join(path)).unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn domain_name() {
+        assert_eq!(
+            core::resolve_target("site.local").unwrap(),
+            Url::parse("http://site.local/").unwrap()
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::{self, MonolithError};
+
+    #[test]
+    fn empty_target() {
+        assert!(matches!(
+            core::resolve_target(""),
+            Err(MonolithError::NoTarget)
+        ));
+    }
+
+    #[test]
+    fn unsupported_scheme() {
+        assert!(matches!(
+            core::resolve_target("ftp://site.local/file.html"),
+            Err(MonolithError::UnsupportedTargetScheme(scheme)) if scheme == "ftp"
+        ));
+    }
+
+    #[test]
+    fn directory() {
+        assert!(matches!(
+            core::resolve_target("tests/_data_/basic"),
+            Err(MonolithError::TargetIsNotAFile(_))
+        ));
+    }
+}
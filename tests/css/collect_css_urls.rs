@@ -0,0 +1,69 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::blocking::Client;
+    use reqwest::Url;
+
+    use monolith::css;
+    use monolith::opts::Options;
+
+    #[test]
+    fn empty_input() {
+        let document_url: Url = Url::parse("https://doc.local/").unwrap();
+        let options = Options::default();
+        let client = Client::new();
+
+        assert!(css::collect_css_urls(&client, &document_url, "", &options).is_empty());
+    }
+
+    #[test]
+    fn urls_and_imports() {
+        let document_url: Url = Url::parse("https://doc.local/css/").unwrap();
+        let options = Options::default();
+        let client = Client::new();
+
+        const CSS: &str = "\
+            @import 'base.css';\
+            @import url(\"print.css\") print;\
+            body { background-image: url(\"../img/bg.png\"); }\
+            div { cursor: url(pointer.cur), auto; }\
+            svg { fill: url(#gradient); }\
+        ";
+
+        assert_eq!(
+            css::collect_css_urls(&client, &document_url, CSS, &options),
+            vec![
+                Url::parse("https://doc.local/css/base.css").unwrap(),
+                Url::parse("https://doc.local/css/print.css").unwrap(),
+                Url::parse("https://doc.local/img/bg.png").unwrap(),
+                Url::parse("https://doc.local/css/pointer.cur").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn respects_exclusion_options() {
+        let document_url: Url = Url::parse("https://doc.local/").unwrap();
+        let client = Client::new();
+        let mut options = Options::default();
+        options.no_fonts = true;
+        options.no_images = true;
+
+        const CSS: &str = "\
+            @font-face { font-family: 'F'; src: url(font.woff2); }\
+            body { background: url(bg.png); }\
+            @import 'style.css';\
+        ";
+
+        assert_eq!(
+            css::collect_css_urls(&client, &document_url, CSS, &options),
+            vec![Url::parse("https://doc.local/style.css").unwrap()]
+        );
+    }
+}
@@ -1,2 +1,3 @@
+mod collect_css_urls;
 mod embed_css;
 mod is_image_url_prop;
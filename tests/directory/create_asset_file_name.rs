@@ -0,0 +1,67 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::directory;
+
+    #[test]
+    fn extension_from_file_header() {
+        let url: Url = Url::parse("https://site.local/image.php").unwrap();
+
+        assert_eq!(
+            directory::create_asset_file_name("text/html", b"GIF89a", &url),
+            "25c9b37ae36a0a08318d4dca7ca57ea98d776821.gif"
+        );
+    }
+
+    #[test]
+    fn extension_from_file_name() {
+        let url: Url = Url::parse("https://site.local/style.css?v=1").unwrap();
+
+        assert_eq!(
+            directory::create_asset_file_name("", b"p{}", &url),
+            "9e2d306947d70f879bf4bde5d1eb1f07e531c0e5.css"
+        );
+    }
+
+    #[test]
+    fn extension_from_media_type() {
+        let url: Url = Url::parse("https://site.local/css?family=Serif").unwrap();
+
+        assert_eq!(
+            directory::create_asset_file_name("text/css", b"p{}", &url),
+            "9e2d306947d70f879bf4bde5d1eb1f07e531c0e5.css"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::directory;
+
+    #[test]
+    fn unknown_media_type() {
+        let url: Url = Url::parse("https://site.local/data").unwrap();
+
+        assert_eq!(
+            directory::create_asset_file_name("application/x-unknown", b"hello", &url),
+            "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d"
+        );
+    }
+}
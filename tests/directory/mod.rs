@@ -0,0 +1,2 @@
+mod create_asset_file_name;
+mod save_to_directory;
@@ -0,0 +1,47 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use monolith::directory;
+    use monolith::utils::Resource;
+
+    #[test]
+    fn writes_document_and_assets() {
+        let directory_path: PathBuf =
+            env::temp_dir().join(format!("monolith-save-to-directory-{}", std::process::id()));
+        let assets: Vec<Resource> = vec![Resource {
+            url: Url::parse("https://site.local/style.css").unwrap(),
+            media_type: "text/css".to_string(),
+            charset: "".to_string(),
+            data: b"p{}".to_vec(),
+        }];
+
+        directory::save_to_directory(&directory_path, "index.html", b"<p>Hi</p>", &assets).unwrap();
+
+        assert_eq!(
+            fs::read(directory_path.join("index.html")).unwrap(),
+            b"<p>Hi</p>"
+        );
+        assert_eq!(
+            fs::read(
+                directory_path
+                    .join("assets")
+                    .join("9e2d306947d70f879bf4bde5d1eb1f07e531c0e5.css")
+            )
+            .unwrap(),
+            b"p{}"
+        );
+
+        fs::remove_dir_all(&directory_path).unwrap();
+    }
+}
@@ -0,0 +1,87 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::env;
+
+    use monolith::fetcher;
+
+    #[test]
+    fn read_local_file() {
+        let file_url_prefix: &str = if cfg!(windows) { "file:///" } else { "file://" };
+        let cwd = env::current_dir().unwrap();
+        let url: Url = Url::parse(&format!(
+            "{file}{cwd}/tests/_data_/basic/local-script.js",
+            file = file_url_prefix,
+            cwd = cwd.to_str().unwrap(),
+        ))
+        .unwrap();
+
+        let asset = fetcher::fetch_local_file(&url).unwrap();
+
+        assert_eq!(asset.status, 200);
+        assert_eq!(asset.final_url, url);
+        assert_eq!(asset.media_type, "application/javascript");
+        assert_eq!(asset.charset, "");
+        assert_eq!(
+            asset.data,
+            std::fs::read("tests/_data_/basic/local-script.js").unwrap()
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+    use std::env;
+
+    use monolith::fetcher;
+    use monolith::utils::AssetError;
+
+    #[test]
+    fn missing_file() {
+        let file_url_prefix: &str = if cfg!(windows) { "file:///" } else { "file://" };
+        let cwd = env::current_dir().unwrap();
+        let url: Url = Url::parse(&format!(
+            "{file}{cwd}/tests/_data_/basic/missing.js",
+            file = file_url_prefix,
+            cwd = cwd.to_str().unwrap(),
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            fetcher::fetch_local_file(&url),
+            Err(AssetError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn directory() {
+        let file_url_prefix: &str = if cfg!(windows) { "file:///" } else { "file://" };
+        let cwd = env::current_dir().unwrap();
+        let url: Url = Url::parse(&format!(
+            "{file}{cwd}/tests/_data_/basic/",
+            file = file_url_prefix,
+            cwd = cwd.to_str().unwrap(),
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            fetcher::fetch_local_file(&url),
+            Err(AssetError::Io(_))
+        ));
+    }
+}
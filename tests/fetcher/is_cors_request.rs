@@ -0,0 +1,59 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::fetcher::is_cors_request;
+
+    #[test]
+    fn cross_origin_font() {
+        assert!(is_cors_request(
+            &Url::parse("https://site.local/style.css").unwrap(),
+            &Url::parse("https://fonts.cdn.local/font.WOFF2?v=3").unwrap(),
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::fetcher::is_cors_request;
+
+    #[test]
+    fn same_origin_font() {
+        assert!(!is_cors_request(
+            &Url::parse("https://site.local/style.css").unwrap(),
+            &Url::parse("https://site.local/font.woff").unwrap(),
+        ));
+    }
+
+    #[test]
+    fn cross_origin_image() {
+        assert!(!is_cors_request(
+            &Url::parse("https://site.local/").unwrap(),
+            &Url::parse("https://images.cdn.local/logo.png").unwrap(),
+        ));
+    }
+
+    #[test]
+    fn local_document() {
+        assert!(!is_cors_request(
+            &Url::parse("file:///home/user/style.css").unwrap(),
+            &Url::parse("https://fonts.cdn.local/font.ttf").unwrap(),
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+mod fetch_local_file;
+mod is_cors_request;
+mod parse_request_header;
+mod referrer_policy;
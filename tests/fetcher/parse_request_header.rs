@@ -0,0 +1,91 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::fetcher::{self, RequestHeader};
+
+    #[test]
+    fn unscoped() {
+        let header: RequestHeader = fetcher::parse_request_header("Accept-Language: de").unwrap();
+
+        assert_eq!(header.name.as_str(), "accept-language");
+        assert_eq!(header.value, "de");
+        assert!(header.applies_to(&Url::parse("https://fonts.site.local/").unwrap()));
+    }
+
+    #[test]
+    fn value_with_colons_and_equal_signs() {
+        let header: RequestHeader = fetcher::parse_request_header("X-Token:a=b:c").unwrap();
+
+        assert_eq!(header.name.as_str(), "x-token");
+        assert_eq!(header.value, "a=b:c");
+        assert!(header.domains.is_empty());
+    }
+
+    #[test]
+    fn scoped_to_domains() {
+        let header: RequestHeader = fetcher::parse_request_header(
+            "wiki.site.local, .docs.site.local=Authorization: Bearer secret",
+        )
+        .unwrap();
+
+        assert_eq!(header.domains, vec!["wiki.site.local", ".docs.site.local"]);
+        assert_eq!(header.name.as_str(), "authorization");
+        assert_eq!(header.value, "Bearer secret");
+        assert!(header.applies_to(&Url::parse("https://wiki.site.local/page").unwrap()));
+        assert!(header.applies_to(&Url::parse("https://docs.site.local/").unwrap()));
+        assert!(header.applies_to(&Url::parse("https://v2.docs.site.local/").unwrap()));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::fetcher::{self, RequestHeader};
+
+    #[test]
+    fn other_domains() {
+        let header: RequestHeader =
+            fetcher::parse_request_header("wiki.site.local=Authorization: Bearer secret").unwrap();
+
+        assert!(!header.applies_to(&Url::parse("https://fonts.cdn.local/font.woff2").unwrap()));
+        assert!(!header.applies_to(&Url::parse("https://www.wiki.site.local/").unwrap()));
+        assert!(!header.applies_to(&Url::parse("file:///home/user/style.css").unwrap()));
+    }
+
+    #[test]
+    fn no_colon() {
+        assert!(fetcher::parse_request_header("Accept-Language de").is_none());
+    }
+
+    #[test]
+    fn invalid_name() {
+        assert!(fetcher::parse_request_header("Accept Language: de").is_none());
+        assert!(fetcher::parse_request_header(": de").is_none());
+    }
+
+    #[test]
+    fn empty_scope() {
+        assert!(fetcher::parse_request_header(" =Authorization: Bearer secret").is_none());
+    }
+
+    #[test]
+    fn invalid_value() {
+        assert!(fetcher::parse_request_header("X-Token: a\nb").is_none());
+    }
+}
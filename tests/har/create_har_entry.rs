@@ -0,0 +1,153 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use chrono::prelude::*;
+    use reqwest::Url;
+    use std::time::Duration;
+
+    use monolith::fetcher::{FetchedAsset, HttpExchange};
+    use monolith::har::create_har_entry;
+
+    fn started() -> DateTime<Utc> {
+        Utc.ymd(2024, 1, 2).and_hms_milli(3, 4, 5, 678)
+    }
+
+    #[test]
+    fn http_response() {
+        let url = Url::parse("https://site.local/style.css?v=2").unwrap();
+        let mut asset = FetchedAsset::new(
+            b"body{}".to_vec(),
+            Url::parse("https://cdn.site.local/style.css").unwrap(),
+            "text/css".to_string(),
+            "UTF-8".to_string(),
+        );
+        asset.http = Some(HttpExchange {
+            method: "GET".to_string(),
+            request_http_version: "HTTP/1.1".to_string(),
+            request_headers: vec![("host".to_string(), "site.local".to_string())],
+            http_version: "HTTP/1.1".to_string(),
+            response_headers: vec![(
+                "content-type".to_string(),
+                "text/css; charset=utf-8".to_string(),
+            )],
+            wait: Duration::from_millis(30),
+            receive: Duration::from_millis(10),
+        });
+
+        let entry = create_har_entry(&url, started(), Duration::from_millis(50), Ok(&asset));
+
+        assert_eq!(entry["startedDateTime"], "2024-01-02T03:04:05.678Z");
+        assert_eq!(entry["time"], 50.0);
+        assert_eq!(entry["timings"]["blocked"], 10.0);
+        assert_eq!(entry["timings"]["wait"], 30.0);
+        assert_eq!(entry["timings"]["receive"], 10.0);
+
+        assert_eq!(entry["request"]["url"], "https://site.local/style.css?v=2");
+        assert_eq!(entry["request"]["httpVersion"], "HTTP/1.1");
+        assert_eq!(entry["request"]["headers"][0]["name"], "host");
+        assert_eq!(entry["request"]["headers"][0]["value"], "site.local");
+        assert_eq!(entry["request"]["queryString"][0]["name"], "v");
+        assert_eq!(entry["request"]["queryString"][0]["value"], "2");
+
+        assert_eq!(entry["response"]["status"], 200);
+        assert_eq!(entry["response"]["statusText"], "OK");
+        assert_eq!(
+            entry["response"]["content"]["mimeType"],
+            "text/css; charset=utf-8"
+        );
+        assert_eq!(entry["response"]["content"]["size"], 6);
+        assert_eq!(entry["response"]["bodySize"], 6);
+        assert_eq!(entry["response"]["redirectURL"], "");
+        assert!(entry.get("_error").is_none());
+    }
+
+    #[test]
+    fn redirect_response() {
+        let url = Url::parse("https://site.local/old.css").unwrap();
+        let mut asset = FetchedAsset::new(
+            vec![],
+            url.clone(),
+            "text/plain".to_string(),
+            "".to_string(),
+        );
+        asset.status = 301;
+        asset.http = Some(HttpExchange {
+            method: "GET".to_string(),
+            request_http_version: "HTTP/1.1".to_string(),
+            request_headers: vec![],
+            http_version: "HTTP/1.1".to_string(),
+            response_headers: vec![("location".to_string(), "/new.css".to_string())],
+            wait: Duration::from_millis(20),
+            receive: Duration::ZERO,
+        });
+
+        let entry = create_har_entry(&url, started(), Duration::from_millis(20), Ok(&asset));
+
+        assert_eq!(entry["response"]["status"], 301);
+        assert_eq!(entry["response"]["statusText"], "Moved Permanently");
+        assert_eq!(entry["response"]["headers"][0]["name"], "location");
+        assert_eq!(
+            entry["response"]["redirectURL"],
+            "https://site.local/new.css"
+        );
+    }
+
+    #[test]
+    fn response_without_http_exchange() {
+        let url = Url::parse("https://site.local/a.png").unwrap();
+        let asset = FetchedAsset::new(
+            b"PNG".to_vec(),
+            url.clone(),
+            "image/png".to_string(),
+            "".to_string(),
+        );
+
+        let entry = create_har_entry(&url, started(), Duration::from_millis(5), Ok(&asset));
+
+        assert_eq!(entry["request"]["headers"].as_array().unwrap().len(), 0);
+        assert_eq!(entry["response"]["content"]["mimeType"], "image/png");
+        assert_eq!(entry["response"]["redirectURL"], "");
+        assert_eq!(entry["timings"]["wait"], 5.0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use chrono::prelude::*;
+    use reqwest::Url;
+    use std::time::Duration;
+
+    use monolith::har::create_har_entry;
+    use monolith::utils::AssetError;
+
+    #[test]
+    fn no_response() {
+        let url = Url::parse("https://site.local/").unwrap();
+
+        let entry = create_har_entry(
+            &url,
+            Utc.ymd(2024, 1, 2).and_hms(3, 4, 5),
+            Duration::from_millis(20),
+            Err(&AssetError::NotFound),
+        );
+
+        assert_eq!(entry["response"]["status"], 0);
+        assert_eq!(entry["response"]["bodySize"], -1);
+        assert_eq!(entry["_error"], "not found");
+        assert_eq!(entry["timings"]["wait"], 20.0);
+        assert_eq!(entry["timings"]["receive"], 0.0);
+    }
+}
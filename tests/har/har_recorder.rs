@@ -0,0 +1,77 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::sync::Arc;
+
+    use crate::server::{self, Response};
+    use monolith::core::create_http_client;
+    use monolith::har::{HarLog, HarRecorder};
+    use monolith::opts::Options;
+    use monolith::session::Session;
+    use monolith::utils;
+
+    #[test]
+    fn record_requests() {
+        let server = server::start(|request| match request.path.as_str() {
+            "/style.css" => Response::new(200, "text/css", b"body{color:red}"),
+            "/old.css" => Response::new(302, "text/plain", b"").header("Location", "/style.css"),
+            _ => Response::new(404, "text/plain", b"Not found"),
+        });
+        let mut options = Options::default();
+        options.silent = true;
+        let log = Arc::new(HarLog::new());
+        let client = create_http_client(&options).unwrap();
+        let recorder = HarRecorder::new(Box::new(client), log.clone());
+        let session = &mut Session::default();
+
+        let url = server.url("/old.css");
+        utils::retrieve_asset(session, &recorder, &url, &url, &options, 0).unwrap();
+        let missing_url = server.url("/missing.png");
+        assert!(
+            utils::retrieve_asset(session, &recorder, &url, &missing_url, &options, 0).is_err()
+        );
+        // Cached assets and data URLs don't result in new requests
+        utils::retrieve_asset(session, &recorder, &url, &url, &options, 0).unwrap();
+        let data_url = Url::parse("data:text/plain,Hi").unwrap();
+        utils::retrieve_asset(session, &recorder, &url, &data_url, &options, 0).unwrap();
+
+        let har = log.to_json();
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(har["log"]["creator"]["name"], "monolith");
+
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+
+        // Redirect and where it led to are logged as separate requests
+        assert_eq!(entries[0]["request"]["url"], url.as_str());
+        assert_eq!(entries[0]["response"]["status"], 302);
+        assert_eq!(
+            entries[0]["response"]["redirectURL"],
+            server.url("/style.css").as_str()
+        );
+        assert!(entries[0]["response"]["headers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|header| header["name"] == "location" && header["value"] == "/style.css"));
+
+        assert_eq!(
+            entries[1]["request"]["url"],
+            server.url("/style.css").as_str()
+        );
+        assert_eq!(entries[1]["response"]["status"], 200);
+        assert_eq!(entries[1]["response"]["redirectURL"], "");
+        assert_eq!(entries[1]["response"]["content"]["size"], 15);
+
+        assert_eq!(entries[2]["request"]["url"], missing_url.as_str());
+        assert_eq!(entries[2]["response"]["status"], 404);
+        assert_eq!(entries[2]["response"]["statusText"], "Not Found");
+    }
+}
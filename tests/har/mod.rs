@@ -0,0 +1,2 @@
+mod create_har_entry;
+mod har_recorder;
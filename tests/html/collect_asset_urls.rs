@@ -0,0 +1,101 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::blocking::Client;
+    use reqwest::Url;
+
+    use monolith::html;
+    use monolith::opts::Options;
+
+    #[test]
+    fn collects_assets_in_document_order() {
+        let html: &str = "\
+            <link rel=\"stylesheet\" href=\"style.css\">\
+            <link rel=\"icon\" href=\"/favicon.png\">\
+            <style>body { background: url(bg.png); }</style>\
+            <body background=\"body.png\">\
+            <img src=\"a.png\" srcset=\"b.png 1x, c.png 2x\">\
+            <img src=\"d.png\" data-src=\"e.png\">\
+            <picture><source srcset=\"f.png\"></picture>\
+            <video poster=\"poster.png\"><source src=\"movie.mp4\"></video>\
+            <iframe src=\"frame.html\"></iframe>\
+            <script src=\"script.js\"></script>\
+            <div style=\"background-image: url('div.png')\"></div>\
+            <a href=\"page.html\">link</a>\
+        ";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("https://doc.local/path/").unwrap();
+        let options = Options::default();
+        let client = Client::new();
+
+        assert_eq!(
+            html::collect_asset_urls(&client, &url, &dom.document, &options)
+                .iter()
+                .map(|u| u.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "https://doc.local/path/style.css",
+                "https://doc.local/favicon.png",
+                "https://doc.local/path/bg.png",
+                "https://doc.local/path/body.png",
+                "https://doc.local/path/a.png",
+                "https://doc.local/path/b.png",
+                "https://doc.local/path/c.png",
+                "https://doc.local/path/e.png",
+                "https://doc.local/path/f.png",
+                "https://doc.local/path/poster.png",
+                "https://doc.local/path/movie.mp4",
+                "https://doc.local/path/frame.html",
+                "https://doc.local/path/script.js",
+                "https://doc.local/path/div.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn respects_exclusion_options() {
+        let html: &str = "\
+            <link rel=\"stylesheet\" href=\"style.css\">\
+            <img src=\"a.png\">\
+            <iframe src=\"frame.html\"></iframe>\
+            <script src=\"script.js\"></script>\
+            <audio src=\"sound.ogg\"></audio>\
+        ";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("https://doc.local/").unwrap();
+        let client = Client::new();
+        let mut options = Options::default();
+        options.no_css = true;
+        options.no_images = true;
+        options.no_frames = true;
+        options.no_js = true;
+
+        assert_eq!(
+            html::collect_asset_urls(&client, &url, &dom.document, &options),
+            vec![Url::parse("https://doc.local/sound.ogg").unwrap()]
+        );
+    }
+
+    #[test]
+    fn leaves_document_intact() {
+        let html: &str = "<video><source src=\"movie.mp4\"></video>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("https://doc.local/").unwrap();
+        let options = Options::default();
+        let client = Client::new();
+
+        html::collect_asset_urls(&client, &url, &dom.document, &options);
+        html::collect_asset_urls(&client, &url, &dom.document, &options);
+
+        assert_eq!(
+            html::collect_asset_urls(&client, &url, &dom.document, &options),
+            vec![Url::parse("https://doc.local/movie.mp4").unwrap()]
+        );
+    }
+}
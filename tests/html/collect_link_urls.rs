@@ -0,0 +1,39 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::html;
+
+    #[test]
+    fn collects_anchors_and_areas() {
+        let html: &str = "\
+            <a href=\"page.html\">page</a>\
+            <map><area href=\"/map.html\"></map>\
+            <a href=\"#top\">top</a>\
+            <a href=\"javascript:void(0)\">script</a>\
+            <a>no href</a>\
+            <a href=\"https://other.local/\">other</a>\
+        ";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("https://doc.local/path/").unwrap();
+
+        assert_eq!(
+            html::collect_link_urls(&url, &dom.document)
+                .iter()
+                .map(|u| u.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "https://doc.local/path/page.html",
+                "https://doc.local/map.html",
+                "https://other.local/",
+            ]
+        );
+    }
+}
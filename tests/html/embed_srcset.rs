@@ -9,24 +9,25 @@
 mod passing {
     use reqwest::blocking::Client;
     use reqwest::Url;
-    use std::collections::HashMap;
 
     use monolith::html;
     use monolith::opts::Options;
+    use monolith::session::Session;
     use monolith::url::EMPTY_IMAGE_DATA_URL;
 
     #[test]
     fn small_medium_large() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
         let srcset_value = "small.png 1x, medium.png 1.5x, large.png 2x";
         let mut options = Options::default();
         options.no_images = true;
         options.silent = true;
         let embedded_css = html::embed_srcset(
-            cache,
+            session,
             &client,
             &Url::parse("data:,").unwrap(),
+            "img",
             &srcset_value,
             &options,
             0,
@@ -43,16 +44,17 @@ mod passing {
 
     #[test]
     fn small_medium_only_medium_has_scale() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
         let srcset_value = "small.png, medium.png 1.5x";
         let mut options = Options::default();
         options.no_images = true;
         options.silent = true;
         let embedded_css = html::embed_srcset(
-            cache,
+            session,
             &client,
             &Url::parse("data:,").unwrap(),
+            "img",
             &srcset_value,
             &options,
             0,
@@ -66,16 +68,17 @@ mod passing {
 
     #[test]
     fn commas_within_file_names() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
         let srcset_value = "small,s.png 1x, large,l.png 2x";
         let mut options = Options::default();
         options.no_images = true;
         options.silent = true;
         let embedded_css = html::embed_srcset(
-            cache,
+            session,
             &client,
             &Url::parse("data:,").unwrap(),
+            "img",
             &srcset_value,
             &options,
             0,
@@ -89,16 +92,17 @@ mod passing {
 
     #[test]
     fn tabs_and_newlines_after_commas() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
         let srcset_value = "small,s.png 1x,\nmedium,m.png 2x,\nlarge,l.png 3x";
         let mut options = Options::default();
         options.no_images = true;
         options.silent = true;
         let embedded_css = html::embed_srcset(
-            cache,
+            session,
             &client,
             &Url::parse("data:,").unwrap(),
+            "img",
             &srcset_value,
             &options,
             0,
@@ -125,24 +129,25 @@ mod passing {
 mod failing {
     use reqwest::blocking::Client;
     use reqwest::Url;
-    use std::collections::HashMap;
 
     use monolith::html;
     use monolith::opts::Options;
+    use monolith::session::Session;
     use monolith::url::EMPTY_IMAGE_DATA_URL;
 
     #[test]
     fn trailing_comma() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
         let srcset_value = "small.png 1x, large.png 2x,";
         let mut options = Options::default();
         options.no_images = true;
         options.silent = true;
         let embedded_css = html::embed_srcset(
-            cache,
+            session,
             &client,
             &Url::parse("data:,").unwrap(),
+            "img",
             &srcset_value,
             &options,
             0,
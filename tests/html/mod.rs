@@ -1,5 +1,7 @@
 mod add_favicon;
 mod check_integrity;
+mod collect_asset_urls;
+mod collect_link_urls;
 mod compose_csp;
 mod create_metadata_tag;
 mod embed_srcset;
@@ -9,6 +11,7 @@ mod get_node_attr;
 mod get_node_name;
 mod has_favicon;
 mod is_icon;
+mod retrieve_and_embed_asset;
 mod serialize_document;
 mod set_node_attr;
 mod walk_and_embed_assets;
@@ -0,0 +1,155 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use html5ever::serialize::{serialize, SerializeOpts};
+    use reqwest::blocking::Client;
+    use url::Url;
+
+    use monolith::html;
+    use monolith::opts::Options;
+    use monolith::session::Session;
+
+    #[test]
+    fn embeds_image_as_data_url() {
+        let session = &mut Session::default();
+        let client = Client::new();
+        let url: Url = Url::parse("data:text/html,").unwrap();
+        let dom = html::html_to_dom(&"<img>".as_bytes().to_vec(), "".to_string());
+        let img = html::get_child_node_by_name(
+            &html::get_child_node_by_name(
+                &html::get_child_node_by_name(&dom.document, "html").unwrap(),
+                "body",
+            )
+            .unwrap(),
+            "img",
+        )
+        .unwrap();
+
+        let mut options = Options::default();
+        options.silent = true;
+
+        assert!(html::retrieve_and_embed_asset(
+            session,
+            &client,
+            &url,
+            &img,
+            "src",
+            "data:image/svg+xml,<svg></svg>",
+            &options,
+            0,
+        )
+        .is_ok());
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&buf),
+            "<html><head></head><body><img src=\"data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=\"></body></html>"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::blocking::Client;
+    use url::Url;
+
+    use monolith::html;
+    use monolith::opts::Options;
+    use monolith::session::Session;
+    use monolith::utils::AssetError;
+
+    #[test]
+    fn integrity_mismatch() {
+        let session = &mut Session::default();
+        let client = Client::new();
+        let url: Url = Url::parse("data:text/html,").unwrap();
+        let dom = html::html_to_dom(
+            &"<script integrity=\"sha256-AAAA\"></script>"
+                .as_bytes()
+                .to_vec(),
+            "".to_string(),
+        );
+        let script = html::get_child_node_by_name(
+            &html::get_child_node_by_name(&dom.document, "html").unwrap(),
+            "head",
+        )
+        .and_then(|head| html::get_child_node_by_name(&head, "script"))
+        .unwrap();
+
+        let mut options = Options::default();
+        options.silent = true;
+
+        match html::retrieve_and_embed_asset(
+            session,
+            &client,
+            &url,
+            &script,
+            "src",
+            "data:application/javascript,alert(1)",
+            &options,
+            0,
+        ) {
+            Ok(()) => {
+                assert!(false);
+            }
+            Err(error) => {
+                assert!(matches!(error, AssetError::Integrity));
+            }
+        }
+    }
+
+    #[test]
+    fn missing_local_asset() {
+        let session = &mut Session::default();
+        let client = Client::new();
+        let url: Url = Url::parse("file:///tmp/index.html").unwrap();
+        let dom = html::html_to_dom(&"<img>".as_bytes().to_vec(), "".to_string());
+        let img = html::get_child_node_by_name(
+            &html::get_child_node_by_name(
+                &html::get_child_node_by_name(&dom.document, "html").unwrap(),
+                "body",
+            )
+            .unwrap(),
+            "img",
+        )
+        .unwrap();
+
+        let mut options = Options::default();
+        options.silent = true;
+
+        match html::retrieve_and_embed_asset(
+            session,
+            &client,
+            &url,
+            &img,
+            "src",
+            "monolith-missing-asset.png",
+            &options,
+            0,
+        ) {
+            Ok(()) => {
+                assert!(false);
+            }
+            Err(error) => {
+                assert!(matches!(error, AssetError::NotFound));
+            }
+        }
+
+        // Local references which could not be embedded get removed
+        assert_eq!(html::get_node_attr(&img, "src"), None);
+    }
+}
@@ -9,16 +9,16 @@
 mod passing {
     use html5ever::serialize::{serialize, SerializeOpts};
     use reqwest::blocking::Client;
-    use std::collections::HashMap;
     use url::Url;
 
     use monolith::html;
     use monolith::opts::Options;
+    use monolith::session::Session;
     use monolith::url::EMPTY_IMAGE_DATA_URL;
 
     #[test]
     fn basic() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let html: &str = "<div><P></P></div>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
@@ -29,7 +29,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -45,14 +45,14 @@ mod passing {
         let html = "<div><P></P><iframe src=\"\"></iframe></div>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.silent = true;
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -68,14 +68,14 @@ mod passing {
         let html = "<frameset><frame src=\"\"></frameset>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.silent = true;
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -96,7 +96,7 @@ mod passing {
         ";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_css = true;
@@ -104,7 +104,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -132,7 +132,7 @@ mod passing {
                     <div><img src=\"http://localhost/assets/mono_lisa.png\" /></div>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_images = true;
@@ -140,7 +140,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -169,7 +169,7 @@ mod passing {
             "<body background=\"no/such/image.png\" background=\"no/such/image2.png\"></body>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_images = true;
@@ -177,7 +177,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -193,7 +193,7 @@ mod passing {
         let html = "<frameset><frame src=\"http://trackbook.com\"></frameset>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_frames = true;
@@ -201,7 +201,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -225,7 +225,7 @@ mod passing {
         let html = "<iframe src=\"http://trackbook.com\"></iframe>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_frames = true;
@@ -233,7 +233,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -261,7 +261,7 @@ mod passing {
         ";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_js = true;
@@ -269,7 +269,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -296,14 +296,14 @@ mod passing {
                     <link integrity=\"sha384-12345\" rel=\"something\" href=\"https://some-site.com/some-file.ext\" />";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.silent = true;
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -331,7 +331,7 @@ mod passing {
         ";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_css = true;
@@ -340,7 +340,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -369,7 +369,7 @@ mod passing {
         ";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_css = true;
@@ -378,7 +378,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -413,7 +413,7 @@ mod passing {
         ";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_css = true;
@@ -424,7 +424,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -455,7 +455,7 @@ mod passing {
         </html>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.no_images = true;
@@ -463,7 +463,7 @@ mod passing {
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -491,14 +491,14 @@ mod passing {
         let html = "<script id=\"data\" type=\"application/json\">{\"mono\":\"lith\"}</script>";
         let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
         let url: Url = Url::parse("http://localhost").unwrap();
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
 
         let mut options = Options::default();
         options.silent = true;
 
         let client = Client::new();
 
-        html::walk_and_embed_assets(cache, &client, &url, &dom.document, &options, 0);
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
 
         let mut buf: Vec<u8> = Vec::new();
         serialize(&mut buf, &dom.document, SerializeOpts::default()).unwrap();
@@ -516,3 +516,49 @@ mod passing {
         );
     }
 }
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::blocking::Client;
+    use url::Url;
+
+    use monolith::html;
+    use monolith::opts::Options;
+    use monolith::session::Session;
+    use monolith::utils::AssetError;
+
+    #[test]
+    fn records_why_assets_failed() {
+        let server = crate::server::start(|_| {
+            crate::server::Response::new(404, "text/html", b"<h1>Not Found</h1>")
+        });
+        let html: String = format!(
+            "<link rel=\"stylesheet\" href=\"style.css\"><script src=\"{}\"></script>",
+            server.url("app.js")
+        );
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = server.url("");
+        let session = &mut Session::default();
+
+        let mut options = Options::default();
+        options.silent = true;
+
+        let client = Client::new();
+
+        html::walk_and_embed_assets(session, &client, &url, &dom.document, &options, 0);
+
+        let failed_assets = &session.document.failed_assets;
+        assert_eq!(failed_assets.len(), 2);
+        assert_eq!(failed_assets[0].0, server.url("style.css"));
+        assert!(matches!(failed_assets[0].1, AssetError::HttpStatus(404)));
+        assert_eq!(failed_assets[1].0, server.url("app.js"));
+        assert!(matches!(failed_assets[1].1, AssetError::HttpStatus(404)));
+    }
+}
@@ -0,0 +1,79 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::mhtml;
+    use monolith::utils::Resource;
+
+    #[test]
+    fn document_with_resources() {
+        let document_url: Url = Url::parse("https://site.local/").unwrap();
+        let resources: Vec<Resource> = vec![
+            Resource {
+                url: Url::parse("https://site.local/style.css").unwrap(),
+                media_type: "text/css".to_string(),
+                charset: "utf-8".to_string(),
+                data: b"p{color:red}".to_vec(),
+            },
+            Resource {
+                url: Url::parse("https://site.local/image.png").unwrap(),
+                media_type: "image/png".to_string(),
+                charset: "".to_string(),
+                data: b"\x89PNG".to_vec(),
+            },
+        ];
+
+        let mhtml: String = String::from_utf8(mhtml::create_mhtml(
+            b"<html></html>",
+            "utf-8",
+            &document_url,
+            &resources,
+        ))
+        .unwrap();
+
+        let boundary: &str = mhtml
+            .split("boundary=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap();
+        assert!(mhtml.contains("Snapshot-Content-Location: https://site.local/\r\n"));
+        assert!(mhtml.contains("Content-Type: multipart/related;\r\n\ttype=\"text/html\";\r\n"));
+
+        let parts: Vec<&str> = mhtml.split(&format!("--{}", boundary)).collect();
+        assert_eq!(parts.len(), 5);
+        assert_eq!(
+            parts[1],
+            "\r\n\
+            Content-Type: text/html; charset=utf-8\r\n\
+            Content-Transfer-Encoding: quoted-printable\r\n\
+            Content-Location: https://site.local/\r\n\r\n\
+            <html></html>\r\n"
+        );
+        assert_eq!(
+            parts[2],
+            "\r\n\
+            Content-Type: text/css; charset=utf-8\r\n\
+            Content-Transfer-Encoding: quoted-printable\r\n\
+            Content-Location: https://site.local/style.css\r\n\r\n\
+            p{color:red}\r\n"
+        );
+        assert_eq!(
+            parts[3],
+            "\r\n\
+            Content-Type: image/png\r\n\
+            Content-Transfer-Encoding: base64\r\n\
+            Content-Location: https://site.local/image.png\r\n\r\n\
+            iVBORw==\r\n"
+        );
+        assert_eq!(parts[4], "--\r\n");
+    }
+}
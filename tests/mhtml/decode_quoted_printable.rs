@@ -0,0 +1,66 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::mhtml;
+
+    #[test]
+    fn encoded_octets() {
+        assert_eq!(
+            mhtml::decode_quoted_printable(b"caf=C3=A9 a=3Db"),
+            "caf\u{e9} a=b".as_bytes()
+        );
+    }
+
+    #[test]
+    fn lowercase_hex_digits() {
+        assert_eq!(
+            mhtml::decode_quoted_printable(b"=c3=a9"),
+            "\u{e9}".as_bytes()
+        );
+    }
+
+    #[test]
+    fn soft_line_breaks() {
+        assert_eq!(
+            mhtml::decode_quoted_printable(b"long=\r\nline=\nhere\r\nnext"),
+            b"longlinehere\r\nnext"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        // Bare line feeds become CRLF line breaks, those are covered by another test
+        let data: Vec<u8> = (0..=255)
+            .filter(|byte| *byte != b'\n')
+            .chain(b" \t\r\n=".iter().cloned())
+            .collect();
+
+        assert_eq!(
+            mhtml::decode_quoted_printable(mhtml::encode_quoted_printable(&data).as_bytes()),
+            data
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::mhtml;
+
+    #[test]
+    fn malformed_sequences() {
+        assert_eq!(mhtml::decode_quoted_printable(b"a=ZZb="), b"a=ZZb=");
+    }
+}
@@ -0,0 +1,45 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::mhtml;
+
+    #[test]
+    fn plain_ascii() {
+        assert_eq!(
+            mhtml::encode_quoted_printable(b"body { color: red; }"),
+            "body { color: red; }"
+        );
+    }
+
+    #[test]
+    fn equals_sign_and_non_ascii() {
+        assert_eq!(
+            mhtml::encode_quoted_printable("a=b \u{e9}".as_bytes()),
+            "a=3Db =C3=A9"
+        );
+    }
+
+    #[test]
+    fn line_breaks() {
+        assert_eq!(
+            mhtml::encode_quoted_printable(b"one \ntwo\r\nthree"),
+            "one=20\r\ntwo\r\nthree"
+        );
+    }
+
+    #[test]
+    fn long_lines() {
+        let encoded: String = mhtml::encode_quoted_printable("x".repeat(100).as_bytes());
+
+        assert_eq!(
+            encoded,
+            format!("{}=\r\n{}", "x".repeat(75), "x".repeat(25))
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::mhtml::{create_mhtml, MhtmlArchive};
+    use monolith::opts::Options;
+    use monolith::session::Session;
+    use monolith::utils::{self, Resource};
+
+    #[test]
+    fn parse_saved_page() {
+        let archive = MhtmlArchive::open("tests/_data_/mhtml/page.mhtml").unwrap();
+        let document: &Resource = archive.document();
+
+        assert_eq!(document.url.as_str(), "https://site.local/page.html");
+        assert_eq!(document.media_type, "text/html");
+        assert_eq!(
+            String::from_utf8_lossy(&document.data),
+            "<html><head><meta charset=\"utf-8\"><link rel=\"stylesheet\" href=\"style.css\"></head><body><p>Caf\u{e9}</p><img src=\"pixel.gif\"></body></html>"
+        );
+        assert!(archive.contains(&Url::parse("https://site.local/pixel.gif").unwrap()));
+        assert!(archive.contains(&Url::parse("cid:frame-1@mhtml.blink").unwrap()));
+        assert!(!archive.contains(&Url::parse("https://site.local/favicon.ico").unwrap()));
+    }
+
+    #[test]
+    fn serve_parts_by_location() {
+        let archive = MhtmlArchive::open("tests/_data_/mhtml/page.mhtml").unwrap();
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+        let url: Url = Url::parse("https://site.local/pixel.gif#frame").unwrap();
+
+        let (data, final_url, media_type, _charset) =
+            utils::retrieve_asset(session, &archive, &url, &url, &options, 0).unwrap();
+
+        assert_eq!(data, base64::decode("R0lGODlhAQABAAAAACw=").unwrap());
+        assert_eq!(final_url.as_str(), "https://site.local/pixel.gif");
+        assert_eq!(media_type, "image/gif");
+    }
+
+    #[test]
+    fn root_part_referred_to_by_start_parameter() {
+        let archive = MhtmlArchive::parse(
+            b"Content-Type: multipart/related; boundary=b; start=\"<main>\"\n\
+            \n\
+            --b\n\
+            Content-Type: text/css\n\
+            Content-Location: https://site.local/style.css\n\
+            \n\
+            p{}\n\
+            --b\n\
+            Content-Type: text/html; charset=\"utf-8\"\n\
+            Content-ID: <main>\n\
+            \n\
+            <p>main</p>\n\
+            --b--\n",
+        )
+        .unwrap();
+
+        assert_eq!(archive.document().url.as_str(), "cid:main");
+        assert_eq!(archive.document().charset, "utf-8");
+        assert_eq!(archive.document().data, b"<p>main</p>");
+    }
+
+    #[test]
+    fn round_trip() {
+        let document_url: Url = Url::parse("https://site.local/").unwrap();
+        let resources: Vec<Resource> = vec![
+            Resource {
+                url: Url::parse("https://site.local/style.css").unwrap(),
+                media_type: "text/css".to_string(),
+                charset: "utf-8".to_string(),
+                data: b"p::before { content: \"\xc3\xa9\"; }".to_vec(),
+            },
+            Resource {
+                url: Url::parse("https://site.local/image.png").unwrap(),
+                media_type: "image/png".to_string(),
+                charset: "".to_string(),
+                data: (0..=255).collect(),
+            },
+        ];
+
+        let mhtml: Vec<u8> = create_mhtml(b"<p>Hi</p>", "utf-8", &document_url, &resources);
+        let archive = MhtmlArchive::parse(&mhtml).unwrap();
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+
+        assert_eq!(archive.document().url, document_url);
+        assert_eq!(archive.document().data, b"<p>Hi</p>");
+        for resource in &resources {
+            let url: &Url = &resource.url;
+            let (data, _final_url, media_type, _charset) =
+                utils::retrieve_asset(session, &archive, url, url, &options, 0).unwrap();
+
+            assert_eq!(data, resource.data);
+            assert_eq!(media_type, resource.media_type);
+        }
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::mhtml::MhtmlArchive;
+    use monolith::opts::Options;
+    use monolith::session::Session;
+    use monolith::utils::{self, AssetError};
+
+    #[test]
+    fn not_a_multipart_archive() {
+        let error = MhtmlArchive::parse(b"Content-Type: text/html\n\n<p>Hi</p>")
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "expected a multipart archive, found text/html"
+        );
+    }
+
+    #[test]
+    fn missing_boundary() {
+        let error = MhtmlArchive::parse(b"Content-Type: multipart/related\n\n")
+            .err()
+            .unwrap();
+
+        assert_eq!(error.to_string(), "multipart boundary is missing");
+    }
+
+    #[test]
+    fn missing_part() {
+        let archive = MhtmlArchive::open("tests/_data_/mhtml/page.mhtml").unwrap();
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+        let url: Url = Url::parse("https://site.local/missing.js").unwrap();
+
+        let result = utils::retrieve_asset(session, &archive, &url, &url, &options, 0);
+
+        assert!(matches!(result, Err(AssetError::NotFound)));
+    }
+}
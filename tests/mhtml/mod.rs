@@ -0,0 +1,4 @@
+mod create_mhtml;
+mod decode_quoted_printable;
+mod encode_quoted_printable;
+mod mhtml_archive;
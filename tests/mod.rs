@@ -1,8 +1,23 @@
+mod batch;
+mod cache;
 mod cli;
+mod cookies;
+mod core;
 mod css;
+mod directory;
+mod fetcher;
+mod har;
 mod html;
 mod js;
+mod mhtml;
 // mod macros;
 mod opts;
+mod proxy;
+mod report;
+mod reporter;
+mod server;
+mod session;
+mod tls;
 mod url;
 mod utils;
+mod warc;
@@ -7,28 +7,51 @@
 
 #[cfg(test)]
 mod passing {
-    use monolith::opts::Options;
+    use monolith::opts::{Options, OutputFormat};
 
     #[test]
     fn defaults() {
         let options: Options = Options::default();
 
         assert_eq!(options.no_audio, false);
+        assert_eq!(options.accept_status, None);
         assert_eq!(options.base_url, None);
+        assert_eq!(options.cache_dir, None);
         assert_eq!(options.no_css, false);
+        assert_eq!(options.cacert, None);
+        assert_eq!(options.cert, None);
         assert_eq!(options.charset, None);
+        assert_eq!(options.crawl_depth, 0);
         assert_eq!(options.no_frames, false);
         assert_eq!(options.no_fonts, false);
+        assert_eq!(options.fail_on_status, None);
+        assert_eq!(options.output_format, OutputFormat::Html);
+        assert_eq!(options.from_warc, None);
+        assert_eq!(options.har, None);
+        assert!(options.headers.is_empty());
         assert_eq!(options.no_images, false);
         assert_eq!(options.isolate, false);
+        assert_eq!(options.input_file, None);
+        assert_eq!(options.jobs, 0);
         assert_eq!(options.no_js, false);
         assert_eq!(options.insecure, false);
+        assert_eq!(options.keep_error_urls, false);
+        assert_eq!(options.key, None);
+        assert_eq!(options.log_format, None);
         assert_eq!(options.no_metadata, false);
+        assert_eq!(options.no_proxy, None);
         assert_eq!(options.output, "".to_string());
+        assert_eq!(options.proxy, None);
+        assert_eq!(options.report, None);
+        assert_eq!(options.referrer_policy, None);
+        assert_eq!(options.retries, 0);
+        assert_eq!(options.save_cookies, None);
         assert_eq!(options.silent, false);
+        assert_eq!(options.strict, false);
         assert_eq!(options.timeout, 0);
         assert_eq!(options.user_agent, None);
         assert_eq!(options.no_video, false);
+        assert_eq!(options.warc, None);
 
         assert_eq!(options.target, "".to_string());
     }
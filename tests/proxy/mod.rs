@@ -0,0 +1 @@
+mod proxy_config;
@@ -0,0 +1,167 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::collections::HashMap;
+
+    use monolith::proxy::ProxyConfig;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn explicit_proxy_over_environment() {
+        let proxy_config: ProxyConfig = ProxyConfig::new(
+            Some("socks5h://localhost:9050"),
+            None,
+            env(&[("HTTPS_PROXY", "http://proxy.local:3128")]),
+        )
+        .unwrap();
+        let url: Url = Url::parse("https://site.local/").unwrap();
+
+        assert_eq!(
+            proxy_config.proxy_for(&url).unwrap().as_str(),
+            "socks5h://localhost:9050"
+        );
+    }
+
+    #[test]
+    fn environment_variables() {
+        let proxy_config: ProxyConfig = ProxyConfig::new(
+            None,
+            None,
+            env(&[
+                ("http_proxy", "proxy.local:3128"),
+                ("ALL_PROXY", "socks5://localhost:1080"),
+            ]),
+        )
+        .unwrap();
+
+        // Lower case variants are accepted, missing scheme defaults to HTTP
+        assert_eq!(
+            proxy_config
+                .proxy_for(&Url::parse("http://site.local/").unwrap())
+                .unwrap()
+                .as_str(),
+            "http://proxy.local:3128/"
+        );
+        assert_eq!(
+            proxy_config
+                .proxy_for(&Url::parse("https://site.local/").unwrap())
+                .unwrap()
+                .as_str(),
+            "socks5://localhost:1080"
+        );
+    }
+
+    #[test]
+    fn no_proxy_hosts() {
+        let proxy_config: ProxyConfig = ProxyConfig::new(
+            Some("http://proxy.local:3128"),
+            Some("localhost, .intranet.local,10.0.0.1"),
+            env(&[("NO_PROXY", "site.local")]),
+        )
+        .unwrap();
+
+        assert!(proxy_config.bypasses("localhost"));
+        assert!(proxy_config.bypasses("intranet.local"));
+        assert!(proxy_config.bypasses("wiki.INTRANET.local"));
+        assert!(proxy_config.bypasses("10.0.0.1"));
+        // Explicitly given list takes precedence over NO_PROXY
+        assert!(!proxy_config.bypasses("site.local"));
+        assert!(proxy_config
+            .proxy_for(&Url::parse("http://wiki.intranet.local/").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn no_proxy_wildcard() {
+        let proxy_config: ProxyConfig = ProxyConfig::new(
+            None,
+            None,
+            env(&[("HTTP_PROXY", "http://proxy.local:3128"), ("no_proxy", "*")]),
+        )
+        .unwrap();
+
+        assert!(proxy_config
+            .proxy_for(&Url::parse("http://site.local/").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn no_proxy_configured() {
+        let proxy_config: ProxyConfig = ProxyConfig::new(None, None, env(&[])).unwrap();
+
+        assert!(proxy_config.is_empty());
+        assert!(proxy_config
+            .proxy_for(&Url::parse("https://site.local/").unwrap())
+            .is_none());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::core::MonolithError;
+    use monolith::proxy::ProxyConfig;
+
+    #[test]
+    fn unsupported_scheme() {
+        assert!(matches!(
+            ProxyConfig::new(Some("ftp://proxy.local"), None, |_| None),
+            Err(MonolithError::InvalidProxy(_))
+        ));
+        assert!(matches!(
+            ProxyConfig::new(Some("socks4://proxy.local:1080"), None, |_| None),
+            Err(MonolithError::InvalidProxy(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_environment_variable() {
+        assert!(matches!(
+            ProxyConfig::new(None, None, |name| match name {
+                "HTTPS_PROXY" => Some("http://[proxy".to_string()),
+                _ => None,
+            }),
+            Err(MonolithError::InvalidProxy(_))
+        ));
+    }
+
+    #[test]
+    fn subdomain_of_ip_address() {
+        let proxy_config: ProxyConfig =
+            ProxyConfig::new(Some("http://proxy.local:3128"), Some("0.1"), |_| None).unwrap();
+
+        assert!(!proxy_config.bypasses("10.0.0.1"));
+    }
+
+    #[test]
+    fn other_schemes() {
+        let proxy_config: ProxyConfig =
+            ProxyConfig::new(Some("http://proxy.local:3128"), None, |_| None).unwrap();
+
+        assert!(proxy_config
+            .proxy_for(&Url::parse("file:///home/user/index.html").unwrap())
+            .is_none());
+    }
+}
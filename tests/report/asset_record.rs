@@ -0,0 +1,127 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use serde_json::json;
+
+    use monolith::opts::Options;
+    use monolith::report::{record_cache_hit, AssetRecord};
+    use monolith::session::Session;
+    use monolith::utils::AssetError;
+
+    #[test]
+    fn new() {
+        let record = AssetRecord::new(
+            Some("img"),
+            "src",
+            "a.png",
+            &Url::parse("https://site.local/page").unwrap(),
+            &Url::parse("https://site.local/a.png").unwrap(),
+        );
+
+        assert_eq!(record.element.as_deref(), Some("img"));
+        assert_eq!(record.attribute, "src");
+        assert_eq!(record.value, "a.png");
+        assert_eq!(record.parent_url, "https://site.local/page");
+        assert_eq!(record.url, "https://site.local/a.png");
+        assert_eq!(record.final_url, None);
+        assert_eq!(record.error, None);
+    }
+
+    #[test]
+    fn succeeded() {
+        let mut options = Options::default();
+        options.report = Some("report.json".to_string());
+        let mut session = Session::default();
+        let url = Url::parse("https://site.local/a.png").unwrap();
+        record_cache_hit(&mut session, &options, &url, true);
+
+        let record = AssetRecord::new(Some("img"), "src", "a.png", &url, &url).succeeded(
+            &Url::parse("https://cdn.site.local/a.png").unwrap(),
+            "image/png",
+            3,
+            &session,
+        );
+
+        assert_eq!(
+            record.final_url.as_deref(),
+            Some("https://cdn.site.local/a.png")
+        );
+        assert_eq!(record.media_type.as_deref(), Some("image/png"));
+        assert_eq!(record.bytes, Some(3));
+        assert_eq!(record.from_cache, Some(true));
+    }
+
+    #[test]
+    fn failed() {
+        let url = Url::parse("https://site.local/a.png").unwrap();
+        let record =
+            AssetRecord::new(Some("img"), "src", "a.png", &url, &url).failed(&AssetError::NotFound);
+
+        assert_eq!(record.error.as_deref(), Some("not found"));
+        assert_eq!(record.final_url, None);
+    }
+
+    #[test]
+    fn to_json() {
+        let url = Url::parse("https://site.local/app.js").unwrap();
+        let mut record = AssetRecord::new(Some("script"), "src", "app.js", &url, &url);
+        record.integrity = Some(false);
+        record.error = Some("integrity check failed".to_string());
+
+        assert_eq!(
+            record.to_json(),
+            json!({
+                "element": "script",
+                "attribute": "src",
+                "value": "app.js",
+                "parent_url": "https://site.local/app.js",
+                "url": "https://site.local/app.js",
+                "final_url": null,
+                "media_type": null,
+                "bytes": null,
+                "from_cache": null,
+                "integrity": "failed",
+                "error": "integrity check failed",
+            })
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::opts::Options;
+    use monolith::report::{record_asset, record_cache_hit, AssetRecord};
+    use monolith::session::Session;
+
+    #[test]
+    fn nothing_recorded_without_report() {
+        let options = Options::default();
+        let mut session = Session::default();
+        let url = Url::parse("https://site.local/a.png").unwrap();
+        record_cache_hit(&mut session, &options, &url, true);
+        record_asset(
+            &mut session,
+            &options,
+            AssetRecord::new(Some("img"), "src", "a.png", &url, &url),
+        );
+
+        assert!(session.document.asset_records.is_empty());
+        assert!(session.document.cache_hits.is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use serde_json::{json, Value};
+
+    use monolith::report::{create_failed_target_report, create_report};
+
+    #[test]
+    fn lists_pages() {
+        let report: Value =
+            serde_json::from_str(&create_report(vec![create_failed_target_report(
+                "https://site.local/",
+                "Not found",
+            )]))
+            .unwrap();
+
+        assert_eq!(
+            report,
+            json!({
+                "pages": [
+                    {
+                        "url": "https://site.local/",
+                        "error": "Not found",
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let report: Value = serde_json::from_str(&create_report(vec![])).unwrap();
+
+        assert_eq!(report, json!({ "pages": [] }));
+    }
+}
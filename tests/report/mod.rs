@@ -0,0 +1,2 @@
+mod asset_record;
+mod create_report;
@@ -0,0 +1,119 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+    use std::sync::{Arc, Mutex};
+
+    use monolith::opts::Options;
+    use monolith::reporter::{report, Event, Reporter};
+    use monolith::session::Session;
+    use monolith::utils::AssetError;
+
+    struct RecordingReporter {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Reporter for RecordingReporter {
+        fn report(&self, event: &Event) {
+            let line: String = match event {
+                Event::AssetStarted { url, depth } => format!("started {} {}", url, depth),
+                Event::AssetFinished {
+                    url,
+                    bytes,
+                    from_cache,
+                    ..
+                } => format!("finished {} {} {}", url, bytes, from_cache),
+                Event::AssetRetrying {
+                    url,
+                    error,
+                    attempt,
+                    ..
+                } => format!("retrying {} {} {}", url, error, attempt),
+                Event::AssetFailed { url, error, .. } => format!("failed {} {}", url, error),
+                Event::DocumentFinished { url, bytes } => format!("document {} {}", url, bytes),
+                Event::BatchFinished { saved, failed } => {
+                    format!("batch {} {}", saved, failed.len())
+                }
+            };
+            self.events.lock().unwrap().push(line);
+        }
+    }
+
+    #[test]
+    fn custom_reporter() {
+        let events: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+        let options = Options::default();
+        let mut session = Session::default();
+        session.reporter = Some(Arc::new(RecordingReporter {
+            events: events.clone(),
+        }));
+        let url: Url = Url::parse("https://site.local/image.png").unwrap();
+
+        report(
+            &session,
+            &options,
+            Event::AssetStarted {
+                url: &url,
+                depth: 1,
+            },
+        );
+        report(
+            &session,
+            &options,
+            Event::AssetFinished {
+                url: &url,
+                final_url: &url,
+                bytes: 42,
+                from_cache: true,
+                depth: 1,
+            },
+        );
+        report(
+            &session,
+            &options,
+            Event::AssetFailed {
+                url: &url,
+                error: &AssetError::NotFound,
+                depth: 1,
+            },
+        );
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "started https://site.local/image.png 1",
+                "finished https://site.local/image.png 42 true",
+                "failed https://site.local/image.png not found",
+            ]
+        );
+    }
+
+    #[test]
+    fn silent_mode() {
+        let events: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+        let mut options = Options::default();
+        options.silent = true;
+        let mut session = Session::default();
+        session.reporter = Some(Arc::new(RecordingReporter {
+            events: events.clone(),
+        }));
+        let url: Url = Url::parse("https://site.local/").unwrap();
+
+        report(
+            &session,
+            &options,
+            Event::DocumentFinished {
+                url: &url,
+                bytes: 1,
+            },
+        );
+
+        assert!(events.lock().unwrap().is_empty());
+    }
+}
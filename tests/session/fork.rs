@@ -0,0 +1,28 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::session::Session;
+
+    #[test]
+    fn starts_empty() {
+        let mut session = Session::default();
+        session
+            .page_files
+            .insert("https://site.local/".to_string(), "index.html".to_string());
+        session
+            .document
+            .cache_hits
+            .insert("https://site.local/a.png".to_string(), true);
+
+        let worker_session = session.fork();
+
+        assert!(worker_session.page_files.is_empty());
+        assert!(worker_session.document.cache_hits.is_empty());
+    }
+}
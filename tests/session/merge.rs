@@ -0,0 +1,64 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::cache::CacheEntry;
+    use monolith::session::Session;
+    use monolith::utils::AssetError;
+
+    #[test]
+    fn keeps_first_cache_hit_and_errors() {
+        let mut session = Session::default();
+        session
+            .document
+            .cache_hits
+            .insert("https://site.local/a.png".to_string(), true);
+        let mut worker_session = session.fork();
+        worker_session
+            .document
+            .cache_hits
+            .insert("https://site.local/a.png".to_string(), false);
+        worker_session
+            .document
+            .cache_hits
+            .insert("https://site.local/b.png".to_string(), false);
+        let url: Url = Url::parse("https://site.local/b.png").unwrap();
+        worker_session.cache.insert(
+            url.to_string(),
+            CacheEntry::new(
+                b"b".to_vec(),
+                url.clone(),
+                "image/png".to_string(),
+                "".to_string(),
+            ),
+        );
+
+        worker_session.document.errors.insert(
+            "https://site.local/c.png".to_string(),
+            AssetError::HttpStatus(404),
+        );
+
+        session.merge(worker_session);
+
+        assert!(session.cache.contains_key("https://site.local/b.png"));
+        assert_eq!(
+            session.document.cache_hits.get("https://site.local/a.png"),
+            Some(&true)
+        );
+        assert_eq!(
+            session.document.cache_hits.get("https://site.local/b.png"),
+            Some(&false)
+        );
+        assert!(matches!(
+            session.document.errors.get("https://site.local/c.png"),
+            Some(AssetError::HttpStatus(404))
+        ));
+    }
+}
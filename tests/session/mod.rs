@@ -0,0 +1,2 @@
+mod fork;
+mod merge;
@@ -0,0 +1,2 @@
+mod find_pem_blocks;
+mod tls_config;
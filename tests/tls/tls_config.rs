@@ -0,0 +1,99 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::opts::Options;
+    use monolith::tls::TlsConfig;
+
+    #[test]
+    fn no_certificates() {
+        let tls_config: TlsConfig = TlsConfig::from_options(&Options::default()).unwrap();
+
+        assert!(tls_config.root_certificates.is_empty());
+        assert!(tls_config.identity.is_none());
+    }
+
+    #[test]
+    fn ca_certificate() {
+        let mut options = Options::default();
+        options.cacert = Some("tests/_data_/tls/ca.pem".to_string());
+
+        let tls_config: TlsConfig = TlsConfig::from_options(&options).unwrap();
+
+        assert_eq!(tls_config.root_certificates.len(), 1);
+    }
+
+    #[test]
+    fn client_certificate_and_key() {
+        let mut options = Options::default();
+        options.cert = Some("tests/_data_/tls/client.pem".to_string());
+        options.key = Some("tests/_data_/tls/client.key".to_string());
+
+        let tls_config: TlsConfig = TlsConfig::from_options(&options).unwrap();
+
+        assert!(tls_config.identity.is_some());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::MonolithError;
+    use monolith::opts::Options;
+    use monolith::tls::TlsConfig;
+
+    #[test]
+    fn missing_ca_file() {
+        let mut options = Options::default();
+        options.cacert = Some("tests/_data_/tls/missing.pem".to_string());
+
+        assert!(matches!(
+            TlsConfig::from_options(&options),
+            Err(MonolithError::InvalidCaCert(_))
+        ));
+    }
+
+    #[test]
+    fn no_certificates_in_ca_file() {
+        let mut options = Options::default();
+        options.cacert = Some("tests/_data_/tls/client.key".to_string());
+
+        assert!(matches!(
+            TlsConfig::from_options(&options),
+            Err(MonolithError::InvalidCaCert(_))
+        ));
+    }
+
+    #[test]
+    fn no_key_in_certificate_file() {
+        let mut options = Options::default();
+        options.cert = Some("tests/_data_/tls/client.pem".to_string());
+
+        assert!(matches!(
+            TlsConfig::from_options(&options),
+            Err(MonolithError::InvalidClientKey(_))
+        ));
+    }
+
+    #[test]
+    fn key_without_certificate() {
+        let mut options = Options::default();
+        options.key = Some("tests/_data_/tls/client.key".to_string());
+
+        assert!(matches!(
+            TlsConfig::from_options(&options),
+            Err(MonolithError::NoClientCert)
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::url;
+
+    #[test]
+    fn path_without_extension() {
+        assert_eq!(
+            url::create_path_slug(&Url::parse("https://site.local/docs/Intro.html").unwrap()),
+            "docs-Intro"
+        );
+    }
+
+    #[test]
+    fn query() {
+        assert_eq!(
+            url::create_path_slug(&Url::parse("https://site.local/page?id=1&lang=en").unwrap()),
+            "page-id-1-lang-en"
+        );
+    }
+
+    #[test]
+    fn percent_encoded_path() {
+        assert_eq!(
+            url::create_path_slug(&Url::parse("https://site.local/caf%C3%A9/").unwrap()),
+            "caf\u{e9}"
+        );
+    }
+
+    #[test]
+    fn root() {
+        assert_eq!(
+            url::create_path_slug(&Url::parse("https://site.local/").unwrap()),
+            "index"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::url;
+
+    #[test]
+    fn too_long() {
+        let long_url: Url = Url::parse(&format!("https://site.local/{}", "a".repeat(300))).unwrap();
+
+        assert_eq!(url::create_path_slug(&long_url).len(), 100);
+    }
+}
@@ -0,0 +1,29 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::url;
+
+    #[test]
+    fn words() {
+        assert_eq!(
+            url::create_slug("Team & Friends (2024)"),
+            "Team-Friends-2024"
+        );
+    }
+
+    #[test]
+    fn unicode() {
+        assert_eq!(url::create_slug("Ссылки, ссылки"), "Ссылки-ссылки");
+    }
+
+    #[test]
+    fn no_words() {
+        assert_eq!(url::create_slug(" ★ "), "");
+    }
+}
@@ -0,0 +1,114 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::opts::{Options, OutputFormat};
+    use monolith::session::Session;
+    use monolith::url;
+
+    #[test]
+    fn html_format_embeds_data_url() {
+        let session = &mut Session::default();
+        let options = Options::default();
+
+        let reference: String = url::embed_asset(
+            session,
+            "image/svg+xml",
+            "",
+            b"<svg></svg>",
+            &Url::parse("https://site.local/image.svg").unwrap(),
+            Some("fragment"),
+            &options,
+            0,
+        );
+
+        assert_eq!(
+            reference,
+            "data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=#fragment"
+        );
+        assert!(session.document.resources.is_empty());
+    }
+
+    #[test]
+    fn mhtml_format_refers_to_stored_resource() {
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.output_format = OutputFormat::Mhtml;
+        let image_url: Url = Url::parse("https://site.local/image.svg#original").unwrap();
+
+        let reference: String = url::embed_asset(
+            session,
+            "image/svg+xml",
+            "",
+            b"<svg></svg>",
+            &image_url,
+            Some("fragment"),
+            &options,
+            0,
+        );
+        // Same asset only gets stored once
+        url::embed_asset(
+            session,
+            "image/svg+xml",
+            "",
+            b"<svg></svg>",
+            &image_url,
+            None,
+            &options,
+            0,
+        );
+
+        assert_eq!(reference, "https://site.local/image.svg#fragment");
+        let resources = &session.document.resources;
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].url.as_str(), "https://site.local/image.svg");
+        assert_eq!(resources[0].media_type, "image/svg+xml");
+        assert_eq!(resources[0].data, b"<svg></svg>");
+    }
+
+    #[test]
+    fn dir_format_refers_to_asset_file() {
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.output_format = OutputFormat::Dir;
+        let image_url: Url = Url::parse("https://site.local/image").unwrap();
+
+        let reference_from_document: String = url::embed_asset(
+            session,
+            "image/svg+xml",
+            "",
+            b"<svg></svg>",
+            &image_url,
+            Some("fragment"),
+            &options,
+            0,
+        );
+        let reference_from_asset: String = url::embed_asset(
+            session,
+            "image/svg+xml",
+            "",
+            b"<svg></svg>",
+            &image_url,
+            None,
+            &options,
+            1,
+        );
+
+        assert_eq!(
+            reference_from_document,
+            "assets/9ed5d9c55c348ab8df6e84baeabeb87e729730ba.svg#fragment"
+        );
+        assert_eq!(
+            reference_from_asset,
+            "9ed5d9c55c348ab8df6e84baeabeb87e729730ba.svg"
+        );
+        assert_eq!(session.document.resources.len(), 1);
+    }
+}
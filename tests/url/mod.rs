@@ -1,5 +1,8 @@
 mod clean_url;
 mod create_data_url;
+mod create_path_slug;
+mod create_slug;
+mod embed_asset;
 mod is_url_and_has_protocol;
 mod parse_data_url;
 mod resolve_url;
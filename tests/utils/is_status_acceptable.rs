@@ -0,0 +1,81 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::opts::Options;
+    use monolith::utils::is_status_acceptable;
+
+    #[test]
+    fn successful_statuses() {
+        let options = Options::default();
+
+        assert!(is_status_acceptable(200, &options));
+        assert!(is_status_acceptable(203, &options));
+        assert!(is_status_acceptable(206, &options));
+    }
+
+    #[test]
+    fn accepted_statuses() {
+        let mut options = Options::default();
+        options.accept_status = Some("404,5xx".to_string());
+
+        assert!(is_status_acceptable(404, &options));
+        assert!(is_status_acceptable(503, &options));
+    }
+
+    #[test]
+    fn ignore_errors() {
+        let mut options = Options::default();
+        options.ignore_errors = true;
+
+        assert!(is_status_acceptable(404, &options));
+        assert!(is_status_acceptable(300, &options));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::opts::Options;
+    use monolith::utils::is_status_acceptable;
+
+    #[test]
+    fn unsuccessful_statuses() {
+        let options = Options::default();
+
+        assert!(!is_status_acceptable(304, &options));
+        assert!(!is_status_acceptable(404, &options));
+    }
+
+    #[test]
+    fn failing_statuses_over_everything_else() {
+        let mut options = Options::default();
+        options.ignore_errors = true;
+        options.accept_status = Some("2xx".to_string());
+        options.fail_on_status = Some("204".to_string());
+
+        assert!(!is_status_acceptable(204, &options));
+    }
+
+    #[test]
+    fn keep_error_urls() {
+        let mut options = Options::default();
+        options.ignore_errors = true;
+        options.keep_error_urls = true;
+
+        assert!(!is_status_acceptable(404, &options));
+        assert!(!is_status_acceptable(500, &options));
+        assert!(is_status_acceptable(300, &options));
+    }
+}
@@ -1,5 +1,10 @@
 mod detect_media_type;
 mod domain_is_within_domain;
 mod indent;
+mod is_status_acceptable;
 mod parse_content_type;
+mod parse_retry_after;
+mod parse_status_patterns;
 mod retrieve_asset;
+mod retrieve_assets;
+mod retry_delay;
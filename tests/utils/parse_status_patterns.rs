@@ -0,0 +1,49 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::utils::{parse_status_patterns, StatusPattern};
+
+    #[test]
+    fn codes_and_classes() {
+        assert_eq!(
+            parse_status_patterns("404, 410,5XX"),
+            Some(vec![
+                StatusPattern::Code(404),
+                StatusPattern::Code(410),
+                StatusPattern::Class(5),
+            ])
+        );
+    }
+
+    #[test]
+    fn matching() {
+        assert!(StatusPattern::Class(4).matches(451));
+        assert!(!StatusPattern::Class(4).matches(500));
+        assert!(StatusPattern::Code(410).matches(410));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::utils::parse_status_patterns;
+
+    #[test]
+    fn not_a_status_code() {
+        assert_eq!(parse_status_patterns("404,gone"), None);
+        assert_eq!(parse_status_patterns("99"), None);
+        assert_eq!(parse_status_patterns("6xx"), None);
+    }
+}
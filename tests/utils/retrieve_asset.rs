@@ -9,16 +9,20 @@
 mod passing {
     use reqwest::blocking::Client;
     use reqwest::Url;
-    use std::collections::HashMap;
     use std::env;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
+    use monolith::core::create_http_client;
+    use monolith::fetcher;
     use monolith::opts::Options;
+    use monolith::session::Session;
     use monolith::url;
     use monolith::utils;
 
     #[test]
     fn read_data_url() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
 
         let mut options = Options::default();
@@ -27,7 +31,7 @@ mod passing {
         // If both source and target are data URLs,
         //  ensure the result contains target data URL
         let (data, final_url, media_type, charset) = utils::retrieve_asset(
-            cache,
+            session,
             &client,
             &Url::parse("data:text/html;base64,c291cmNl").unwrap(),
             &Url::parse("data:text/html;base64,dGFyZ2V0").unwrap(),
@@ -49,7 +53,7 @@ mod passing {
 
     #[test]
     fn read_local_file_with_file_url_parent() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
 
         let mut options = Options::default();
@@ -60,7 +64,7 @@ mod passing {
         // Inclusion of local assets from local sources should be allowed
         let cwd = env::current_dir().unwrap();
         let (data, final_url, media_type, charset) = utils::retrieve_asset(
-            cache,
+            session,
             &client,
             &Url::parse(&format!(
                 "{file}{cwd}/tests/_data_/basic/local-file.html",
@@ -91,6 +95,199 @@ mod passing {
             .unwrap()
         );
     }
+
+    #[test]
+    fn revalidate_asset_cached_on_disk() {
+        let server = crate::server::start(|request| {
+            if request.header("If-None-Match") == Some("\"v1\"") {
+                crate::server::Response::new(304, "text/css", b"")
+            } else {
+                crate::server::Response::new(200, "text/css; charset=utf-8", b"body{}")
+                    .header("ETag", "\"v1\"")
+            }
+        });
+        let cache_dir = env::temp_dir().join("monolith-test-retrieve-asset-disk-cache");
+        let _ = std::fs::remove_dir_all(&cache_dir);
+
+        let mut options = Options::default();
+        options.silent = true;
+        options.cache_dir = Some(cache_dir.to_str().unwrap().to_string());
+
+        let client = Client::new();
+        let url: Url = server.url("style.css");
+
+        // Every run starts with an empty in-memory cache
+        for _ in 0..2 {
+            let (data, final_url, media_type, charset) =
+                utils::retrieve_asset(&mut Session::default(), &client, &url, &url, &options, 0)
+                    .unwrap();
+            assert_eq!(data, b"body{}");
+            assert_eq!(final_url, url);
+            assert_eq!(media_type, "text/css");
+            assert_eq!(charset, "utf-8");
+        }
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].header("If-None-Match"), None);
+        assert_eq!(requests[1].header("If-None-Match"), Some("\"v1\""));
+    }
+
+    #[test]
+    fn reuse_fresh_asset_cached_on_disk() {
+        let server = crate::server::start(|_| {
+            crate::server::Response::new(200, "text/css", b"body{}")
+                .header("Cache-Control", "max-age=3600")
+        });
+        let cache_dir = env::temp_dir().join("monolith-test-retrieve-asset-fresh-disk-cache");
+        let _ = std::fs::remove_dir_all(&cache_dir);
+
+        let mut options = Options::default();
+        options.silent = true;
+        options.cache_dir = Some(cache_dir.to_str().unwrap().to_string());
+
+        let client = Client::new();
+        let url: Url = server.url("style.css");
+
+        // Fresh copy gets used without revalidating it, even though it has no validators
+        for _ in 0..2 {
+            let (data, ..) =
+                utils::retrieve_asset(&mut Session::default(), &client, &url, &url, &options, 0)
+                    .unwrap();
+            assert_eq!(data, b"body{}");
+        }
+
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn keep_no_store_asset_off_disk() {
+        let server = crate::server::start(|_| {
+            crate::server::Response::new(200, "text/css", b"body{}")
+                .header("Cache-Control", "no-store")
+                .header("ETag", "\"v1\"")
+        });
+        let cache_dir = env::temp_dir().join("monolith-test-retrieve-asset-no-store-disk-cache");
+        let _ = std::fs::remove_dir_all(&cache_dir);
+
+        let mut options = Options::default();
+        options.silent = true;
+        options.cache_dir = Some(cache_dir.to_str().unwrap().to_string());
+
+        let client = Client::new();
+        let url: Url = server.url("style.css");
+        for _ in 0..2 {
+            utils::retrieve_asset(&mut Session::default(), &client, &url, &url, &options, 0)
+                .unwrap();
+        }
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].header("If-None-Match"), None);
+    }
+
+    #[test]
+    fn send_referer_and_origin() {
+        let server =
+            crate::server::start(|_| crate::server::Response::new(200, "font/woff2", b"wOF2"));
+
+        let mut options = Options::default();
+        options.silent = true;
+
+        let client = Client::new();
+        let parent_url: Url = Url::parse("https://site.local/css/style.css#x").unwrap();
+        let url: Url = server.url("font.woff2");
+        utils::retrieve_asset(
+            &mut Session::default(),
+            &client,
+            &parent_url,
+            &url,
+            &options,
+            0,
+        )
+        .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].header("Referer"), None);
+        assert_eq!(requests[0].header("Origin"), Some("https://site.local"));
+
+        options.referrer_policy = Some("unsafe-url".to_string());
+        utils::retrieve_asset(
+            &mut Session::default(),
+            &client,
+            &parent_url,
+            &url,
+            &options,
+            0,
+        )
+        .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[1].header("Referer"),
+            Some("https://site.local/css/style.css")
+        );
+    }
+
+    #[test]
+    fn scope_headers_on_every_redirect() {
+        let server = crate::server::start(|request| match request.path.as_str() {
+            "/old.css" => {
+                let port: &str = request.header("Host").unwrap().rsplit(':').next().unwrap();
+                crate::server::Response::new(302, "text/html", b"")
+                    .header("Location", &format!("http://localhost:{}/new.css", port))
+            }
+            _ => crate::server::Response::new(200, "text/css", b"body{}"),
+        });
+
+        let mut options = Options::default();
+        options.silent = true;
+        options.headers = vec![
+            fetcher::parse_request_header("127.0.0.1=Authorization: Bearer secret").unwrap(),
+            fetcher::parse_request_header("X-Everywhere: 1").unwrap(),
+        ];
+
+        let client = create_http_client(&options).unwrap();
+        let url: Url = server.url("old.css");
+        let (data, final_url, ..) =
+            utils::retrieve_asset(&mut Session::default(), &client, &url, &url, &options, 0)
+                .unwrap();
+
+        assert_eq!(data, b"body{}");
+        assert_eq!(final_url.host_str(), Some("localhost"));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].header("Authorization"), Some("Bearer secret"));
+        assert_eq!(requests[0].header("X-Everywhere"), Some("1"));
+        assert_eq!(requests[1].header("Authorization"), None);
+        assert_eq!(requests[1].header("X-Everywhere"), Some("1"));
+    }
+
+    #[test]
+    fn retry_server_error() {
+        let attempts: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let server_attempts = attempts.clone();
+        let server = crate::server::start(move |_| {
+            if server_attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                crate::server::Response::new(503, "text/html", b"").header("Retry-After", "0")
+            } else {
+                crate::server::Response::new(200, "text/css", b"body{}")
+            }
+        });
+
+        let mut options = Options::default();
+        options.silent = true;
+        options.retries = 2;
+
+        let client = Client::new();
+        let url: Url = server.url("style.css");
+        let (data, ..) =
+            utils::retrieve_asset(&mut Session::default(), &client, &url, &url, &options, 0)
+                .unwrap();
+
+        assert_eq!(data, b"body{}");
+        assert_eq!(server.requests().len(), 3);
+    }
 }
 
 //  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
@@ -104,14 +301,14 @@ mod passing {
 mod failing {
     use reqwest::blocking::Client;
     use reqwest::Url;
-    use std::collections::HashMap;
 
     use monolith::opts::Options;
+    use monolith::session::Session;
     use monolith::utils;
 
     #[test]
     fn read_local_file_with_data_url_parent() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
 
         let mut options = Options::default();
@@ -119,7 +316,7 @@ mod failing {
 
         // Inclusion of local assets from data URL sources should not be allowed
         match utils::retrieve_asset(
-            cache,
+            session,
             &client,
             &Url::parse("data:text/html;base64,SoUrCe").unwrap(),
             &Url::parse("file:///etc/passwd").unwrap(),
@@ -137,7 +334,7 @@ mod failing {
 
     #[test]
     fn read_local_file_with_https_parent() {
-        let cache = &mut HashMap::new();
+        let session = &mut Session::default();
         let client = Client::new();
 
         let mut options = Options::default();
@@ -145,7 +342,7 @@ mod failing {
 
         // Inclusion of local assets from remote sources should not be allowed
         match utils::retrieve_asset(
-            cache,
+            session,
             &client,
             &Url::parse("https://kernel.org/").unwrap(),
             &Url::parse("file:///etc/passwd").unwrap(),
@@ -155,9 +352,151 @@ mod failing {
             Ok((..)) => {
                 assert!(false);
             }
-            Err(_) => {
-                assert!(true);
+            Err(error) => {
+                assert!(matches!(error, utils::AssetError::SecurityPolicy));
+            }
+        }
+    }
+
+    #[test]
+    fn read_missing_local_file() {
+        let session = &mut Session::default();
+        let client = Client::new();
+
+        let mut options = Options::default();
+        options.silent = true;
+
+        match utils::retrieve_asset(
+            session,
+            &client,
+            &Url::parse("file:///tmp/index.html").unwrap(),
+            &Url::parse("file:///tmp/monolith-missing-asset.png").unwrap(),
+            &options,
+            0,
+        ) {
+            Ok((..)) => {
+                assert!(false);
+            }
+            Err(error) => {
+                assert!(matches!(error, utils::AssetError::NotFound));
+            }
+        }
+    }
+
+    #[test]
+    fn blacklisted_domain() {
+        let session = &mut Session::default();
+        let client = Client::new();
+
+        let mut options = Options::default();
+        options.silent = true;
+        options.blacklist_domains = true;
+        options.domains = Some(vec!["ads.local".to_string()]);
+
+        match utils::retrieve_asset(
+            session,
+            &client,
+            &Url::parse("https://site.local/").unwrap(),
+            &Url::parse("https://ads.local/banner.png").unwrap(),
+            &options,
+            0,
+        ) {
+            Ok((..)) => {
+                assert!(false);
+            }
+            Err(error) => {
+                assert!(matches!(error, utils::AssetError::Blocked));
+            }
+        }
+    }
+
+    #[test]
+    fn unsuccessful_http_status() {
+        let server = crate::server::start(|_| {
+            crate::server::Response::new(404, "text/html", b"<h1>Not Found</h1>")
+        });
+        let session = &mut Session::default();
+        let client = Client::new();
+
+        let mut options = Options::default();
+        options.silent = true;
+
+        match utils::retrieve_asset(
+            session,
+            &client,
+            &server.url(""),
+            &server.url("missing.png"),
+            &options,
+            0,
+        ) {
+            Ok((..)) => {
+                assert!(false);
             }
+            Err(error) => {
+                assert!(matches!(error, utils::AssetError::HttpStatus(404)));
+            }
+        }
+    }
+
+    #[test]
+    fn retries_exhausted() {
+        let server = crate::server::start(|_| {
+            crate::server::Response::new(429, "text/html", b"").header("Retry-After", "0")
+        });
+
+        let mut options = Options::default();
+        options.silent = true;
+        options.retries = 1;
+
+        let client = Client::new();
+        let url: Url = server.url("image.png");
+
+        assert!(matches!(
+            utils::retrieve_asset(&mut Session::default(), &client, &url, &url, &options, 0),
+            Err(utils::AssetError::HttpStatus(429))
+        ));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn no_retries_for_client_error() {
+        let server = crate::server::start(|_| {
+            crate::server::Response::new(404, "text/html", b"<h1>Not Found</h1>")
+        });
+
+        let mut options = Options::default();
+        options.silent = true;
+        options.retries = 3;
+
+        let client = Client::new();
+        let url: Url = server.url("missing.png");
+
+        assert!(
+            utils::retrieve_asset(&mut Session::default(), &client, &url, &url, &options, 0)
+                .is_err()
+        );
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn failure_not_retrieved_again() {
+        let server = crate::server::start(|_| {
+            crate::server::Response::new(404, "text/html", b"<h1>Not Found</h1>")
+        });
+        let session = &mut Session::default();
+
+        let mut options = Options::default();
+        options.silent = true;
+
+        let client = Client::new();
+        let url: Url = server.url("missing.png");
+
+        for _ in 0..2 {
+            assert!(matches!(
+                utils::retrieve_asset(session, &client, &url, &url, &options, 0),
+                Err(utils::AssetError::HttpStatus(404))
+            ));
         }
+        assert_eq!(server.requests().len(), 1);
     }
 }
@@ -0,0 +1,75 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::blocking::Client;
+    use reqwest::Url;
+    use std::sync::{Arc, Mutex};
+
+    use monolith::opts::Options;
+    use monolith::reporter::{Event, Reporter};
+    use monolith::session::Session;
+    use monolith::utils;
+
+    struct FailureCounter {
+        failures: Arc<Mutex<usize>>,
+    }
+
+    impl Reporter for FailureCounter {
+        fn report(&self, event: &Event) {
+            if let Event::AssetFailed { .. } = event {
+                *self.failures.lock().unwrap() += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn failures_not_retrieved_again_when_embedding() {
+        let server = crate::server::start(|request| {
+            if request.path == "/style.css" {
+                crate::server::Response::new(200, "text/css", b"body{}")
+            } else {
+                crate::server::Response::new(404, "text/html", b"<h1>Not Found</h1>")
+            }
+        });
+        let failures: Arc<Mutex<usize>> = Arc::new(Mutex::new(0));
+        let session = &mut Session::default();
+        session.reporter = Some(Arc::new(FailureCounter {
+            failures: failures.clone(),
+        }));
+
+        let mut options = Options::default();
+        options.jobs = 4;
+        options.retries = 2;
+
+        let client = Client::new();
+        let page_url: Url = server.url("");
+        let style_url: Url = server.url("style.css");
+        let missing_url: Url = server.url("missing.png");
+        utils::retrieve_assets(
+            session,
+            &client,
+            &[
+                (page_url.clone(), style_url.clone()),
+                (page_url.clone(), missing_url.clone()),
+            ],
+            &options,
+            0,
+        );
+
+        assert!(
+            utils::retrieve_asset(session, &client, &page_url, &style_url, &options, 0).is_ok()
+        );
+        assert!(matches!(
+            utils::retrieve_asset(session, &client, &page_url, &missing_url, &options, 0),
+            Err(utils::AssetError::HttpStatus(404))
+        ));
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(*failures.lock().unwrap(), 1);
+    }
+}
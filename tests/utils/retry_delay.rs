@@ -0,0 +1,43 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::time::Duration;
+
+    use monolith::utils::retry_delay;
+
+    #[test]
+    fn exponential_backoff() {
+        assert_eq!(retry_delay(1, None, 1.0), Duration::from_secs(1));
+        assert_eq!(retry_delay(2, None, 1.0), Duration::from_secs(2));
+        assert_eq!(retry_delay(3, None, 1.0), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn jitter() {
+        assert_eq!(retry_delay(3, None, 0.0), Duration::from_secs(2));
+        assert_eq!(retry_delay(3, None, 0.5), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        assert_eq!(retry_delay(30, None, 1.0), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn retry_after_over_backoff() {
+        assert_eq!(
+            retry_delay(3, Some(Duration::from_secs(10)), 0.0),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            retry_delay(1, Some(Duration::from_secs(3600)), 0.0),
+            Duration::from_secs(60)
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod create_digest;
+mod warc_recorder;
+mod warc_replay;
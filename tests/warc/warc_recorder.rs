@@ -0,0 +1,148 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use flate2::read::MultiGzDecoder;
+    use reqwest::blocking::Client;
+    use std::io::{self, Read, Write};
+    use std::sync::{Arc, Mutex};
+
+    use crate::server::{self, Response};
+    use monolith::core::create_http_client;
+    use monolith::opts::Options;
+    use monolith::session::Session;
+    use monolith::utils;
+    use monolith::warc::{WarcRecorder, WarcReplay, WarcWriter};
+
+    #[derive(Clone)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn record_http_exchanges() {
+        let server = server::start(|request| match request.path.as_str() {
+            "/style.css" => {
+                Response::new(200, "text/css", b"body{color:red}").header("ETag", "\"1\"")
+            }
+            _ => Response::new(404, "text/plain", b"Not found"),
+        });
+        let buffer = SharedBuffer(Arc::new(Mutex::new(vec![])));
+        let writer = Arc::new(WarcWriter::new(
+            Box::new(buffer.clone()),
+            true,
+            "test.warc.gz",
+        ));
+        let recorder = WarcRecorder::new(Box::new(Client::new()), writer.clone());
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+
+        let url = server.url("/style.css");
+        utils::retrieve_asset(session, &recorder, &url, &url, &options, 0).unwrap();
+        let missing_url = server.url("/missing.png");
+        assert!(
+            utils::retrieve_asset(session, &recorder, &url, &missing_url, &options, 0).is_err()
+        );
+        // Cached assets don't result in new exchanges
+        utils::retrieve_asset(session, &recorder, &url, &url, &options, 0).unwrap();
+        writer.finish().unwrap();
+
+        let mut warc: String = String::new();
+        MultiGzDecoder::new(&buffer.0.lock().unwrap()[..])
+            .read_to_string(&mut warc)
+            .unwrap();
+        let records: Vec<&str> = warc.split("WARC/1.1\r\n").skip(1).collect();
+
+        assert_eq!(records.len(), 5);
+        assert!(records[0].starts_with("WARC-Type: warcinfo\r\n"));
+        assert!(records[0].contains("WARC-Filename: test.warc.gz\r\n"));
+
+        assert!(records[1].starts_with("WARC-Type: response\r\n"));
+        assert!(records[1].contains(&format!("WARC-Target-URI: {}\r\n", url)));
+        assert!(records[1].contains("Content-Type: application/http;msgtype=response\r\n"));
+        assert!(records[1].contains("\r\n\r\nHTTP/1.1 200 OK\r\n"));
+        assert!(records[1].contains("etag: \"1\"\r\n"));
+        assert!(records[1].contains("content-length: 15\r\n\r\nbody{color:red}\r\n\r\n"));
+
+        assert!(records[2].starts_with("WARC-Type: request\r\n"));
+        let response_id: &str = records[1]
+            .lines()
+            .find(|line| line.starts_with("WARC-Record-ID: "))
+            .unwrap()
+            .trim_start_matches("WARC-Record-ID: ");
+        assert!(records[2].contains(&format!("WARC-Concurrent-To: {}\r\n", response_id)));
+        assert!(records[2].contains("\r\n\r\nGET /style.css HTTP/1.1\r\n"));
+        assert!(records[2].contains(&format!("host: 127.0.0.1:{}\r\n", url.port().unwrap())));
+
+        assert!(records[3].starts_with("WARC-Type: response\r\n"));
+        assert!(records[3].contains("\r\n\r\nHTTP/1.1 404 Not Found\r\n"));
+        assert!(records[4].starts_with("WARC-Type: request\r\n"));
+    }
+
+    #[test]
+    fn record_and_replay_redirects() {
+        let server = server::start(|request| match request.path.as_str() {
+            "/old.css" => Response::new(301, "text/html", b"Moved").header("Location", "/new.css"),
+            "/new.css" => Response::new(200, "text/css", b"body{color:red}"),
+            _ => Response::new(404, "text/plain", b"Not found"),
+        });
+        let buffer = SharedBuffer(Arc::new(Mutex::new(vec![])));
+        let writer = Arc::new(WarcWriter::new(
+            Box::new(buffer.clone()),
+            false,
+            "test.warc",
+        ));
+        let mut options = Options::default();
+        options.silent = true;
+        let client = create_http_client(&options).unwrap();
+        let recorder = WarcRecorder::new(Box::new(client), writer.clone());
+
+        let url = server.url("/old.css");
+        let (data, final_url, ..) =
+            utils::retrieve_asset(&mut Session::default(), &recorder, &url, &url, &options, 0)
+                .unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(data, b"body{color:red}");
+        assert_eq!(final_url, server.url("/new.css"));
+        assert_eq!(server.requests().len(), 2);
+
+        let warc: Vec<u8> = buffer.0.lock().unwrap().clone();
+        let text: String = String::from_utf8_lossy(&warc).to_string();
+        let records: Vec<&str> = text.split("WARC/1.1\r\n").skip(1).collect();
+        assert_eq!(records.len(), 5);
+        assert!(records[1].starts_with("WARC-Type: response\r\n"));
+        assert!(records[1].contains(&format!("WARC-Target-URI: {}\r\n", url)));
+        assert!(records[1].contains("\r\n\r\nHTTP/1.1 301 Moved Permanently\r\n"));
+        assert!(records[1].contains("location: /new.css\r\n"));
+        assert!(records[2].starts_with("WARC-Type: request\r\n"));
+        assert!(records[2].contains("\r\n\r\nGET /old.css HTTP/1.1\r\n"));
+        assert!(records[3].contains(&format!("WARC-Target-URI: {}\r\n", server.url("/new.css"))));
+        assert!(records[3].contains("\r\n\r\nHTTP/1.1 200 OK\r\n"));
+        assert!(records[4].contains("\r\n\r\nGET /new.css HTTP/1.1\r\n"));
+
+        // Redirect gets followed the same way when replaying the archive
+        let replay = WarcReplay::parse(&warc).unwrap();
+        let (data, final_url, ..) =
+            utils::retrieve_asset(&mut Session::default(), &replay, &url, &url, &options, 0)
+                .unwrap();
+
+        assert_eq!(data, b"body{color:red}");
+        assert_eq!(final_url, server.url("/new.css"));
+        assert_eq!(server.requests().len(), 2);
+    }
+}
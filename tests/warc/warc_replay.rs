@@ -0,0 +1,211 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use reqwest::Url;
+    use std::io::Write;
+
+    use monolith::fetcher::{AssetFetcher, AssetRequest, FetchedAsset};
+    use monolith::opts::Options;
+    use monolith::session::Session;
+    use monolith::utils;
+    use monolith::warc::WarcReplay;
+
+    fn response_record(target_uri: &str, http_response: &[u8]) -> Vec<u8> {
+        let mut record: Vec<u8> = format!(
+            "WARC/1.0\r\n\
+            WARC-Type: response\r\n\
+            WARC-Target-URI: {}\r\n\
+            Content-Type: application/http; msgtype=response\r\n\
+            Content-Length: {}\r\n\r\n",
+            target_uri,
+            http_response.len()
+        )
+        .into_bytes();
+        record.extend_from_slice(http_response);
+        record.extend_from_slice(b"\r\n\r\n");
+        record
+    }
+
+    #[test]
+    fn replay_recorded_response() {
+        let warc: Vec<u8> = response_record(
+            "https://site.local/style.css",
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/css; charset=utf-8\r\n\r\nbody{}",
+        );
+        let replay = WarcReplay::parse(&warc).unwrap();
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+        let url: Url = Url::parse("https://site.local/style.css#top").unwrap();
+
+        let (data, final_url, media_type, charset) =
+            utils::retrieve_asset(session, &replay, &url, &url, &options, 0).unwrap();
+
+        assert_eq!(data, b"body{}");
+        assert_eq!(final_url.as_str(), "https://site.local/style.css");
+        assert_eq!(media_type, "text/css");
+        assert_eq!(charset, "utf-8");
+    }
+
+    #[test]
+    fn follow_recorded_redirects() {
+        let mut warc: Vec<u8> = response_record(
+            "<https://site.local/old.png>",
+            b"HTTP/1.1 301 Moved Permanently\r\nLocation: /new.png\r\n\r\n",
+        );
+        warc.extend(response_record(
+            "https://site.local/new.png",
+            b"HTTP/1.1 200 OK\r\nContent-Type: image/png\r\n\r\nPNG",
+        ));
+        let replay = WarcReplay::parse(&warc).unwrap();
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+        let url: Url = Url::parse("https://site.local/old.png").unwrap();
+
+        let (data, final_url, media_type, _) =
+            utils::retrieve_asset(session, &replay, &url, &url, &options, 0).unwrap();
+
+        assert_eq!(data, b"PNG");
+        assert_eq!(final_url.as_str(), "https://site.local/new.png");
+        assert_eq!(media_type, "image/png");
+    }
+
+    #[test]
+    fn keep_recorded_redirects() {
+        let mut warc: Vec<u8> = response_record(
+            "https://site.local/old.png",
+            b"HTTP/1.1 302 Found\r\nLocation: https://cdn.site.local/new.png\r\n\r\n",
+        );
+        warc.extend(response_record(
+            "https://cdn.site.local/new.png",
+            b"HTTP/1.1 200 OK\r\nContent-Type: image/png\r\n\r\nPNG",
+        ));
+        let replay = WarcReplay::parse(&warc).unwrap();
+        let url: Url = Url::parse("https://site.local/old.png").unwrap();
+
+        let asset: FetchedAsset = replay
+            .fetch(&AssetRequest::new(&url, &url), &Options::default())
+            .unwrap();
+
+        assert_eq!(asset.status, 200);
+        assert_eq!(asset.redirects.len(), 1);
+        assert_eq!(asset.redirects[0].status, 302);
+        assert_eq!(asset.redirects[0].final_url, url);
+        assert_eq!(asset.redirect_location(), None);
+        assert_eq!(
+            asset.redirects[0].redirect_location().unwrap().as_str(),
+            "https://cdn.site.local/new.png"
+        );
+    }
+
+    #[test]
+    fn decode_chunked_gzip_payload() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"console.log(1);").unwrap();
+        let compressed: Vec<u8> = encoder.finish().unwrap();
+        let mut http_response: Vec<u8> = b"HTTP/1.1 200 OK\r\n\
+            Content-Type: application/javascript\r\n\
+            Content-Encoding: gzip\r\n\
+            Transfer-Encoding: chunked\r\n\r\n"
+            .to_vec();
+        http_response.extend(format!("{:x}\r\n", compressed.len()).into_bytes());
+        http_response.extend(&compressed);
+        http_response.extend(b"\r\n0\r\n\r\n");
+
+        let mut warc: Vec<u8> = vec![];
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&response_record(
+                "https://site.local/app.js",
+                &http_response,
+            ))
+            .unwrap();
+        warc.extend(encoder.finish().unwrap());
+        let replay = WarcReplay::parse(&warc).unwrap();
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+        let url: Url = Url::parse("https://site.local/app.js").unwrap();
+
+        let (data, _, _, _) =
+            utils::retrieve_asset(session, &replay, &url, &url, &options, 0).unwrap();
+
+        assert_eq!(data, b"console.log(1);");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::opts::Options;
+    use monolith::session::Session;
+    use monolith::utils::{self, AssetError};
+    use monolith::warc::WarcReplay;
+
+    #[test]
+    fn missing_url() {
+        let replay = WarcReplay::parse(b"").unwrap();
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+        let url: Url = Url::parse("https://site.local/missing.png").unwrap();
+
+        assert!(!replay.contains(&url));
+        assert!(matches!(
+            utils::retrieve_asset(session, &replay, &url, &url, &options, 0),
+            Err(AssetError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn not_a_warc_file() {
+        assert!(WarcReplay::parse(b"<html></html>").is_err());
+    }
+
+    #[test]
+    fn truncated_chunked_payload() {
+        // Chunk data isn't followed by CRLF and the last (empty) chunk
+        let http_response: &[u8] = b"HTTP/1.1 200 OK\r\n\
+            Content-Type: text/plain\r\n\
+            Transfer-Encoding: chunked\r\n\r\n\
+            5\r\nhello";
+        let mut warc: Vec<u8> = format!(
+            "WARC/1.0\r\n\
+            WARC-Type: response\r\n\
+            WARC-Target-URI: https://site.local/hello.txt\r\n\
+            Content-Type: application/http; msgtype=response\r\n\
+            Content-Length: {}\r\n\r\n",
+            http_response.len()
+        )
+        .into_bytes();
+        warc.extend_from_slice(http_response);
+        warc.extend_from_slice(b"\r\n\r\n");
+        let replay = WarcReplay::parse(&warc).unwrap();
+        let session = &mut Session::default();
+        let mut options = Options::default();
+        options.silent = true;
+        let url: Url = Url::parse("https://site.local/hello.txt").unwrap();
+
+        // Payload which can't be decoded is kept as it was recorded
+        let (data, _, _, _) =
+            utils::retrieve_asset(session, &replay, &url, &url, &options, 0).unwrap();
+        assert_eq!(data, b"5\r\nhello");
+    }
+}